@@ -14,6 +14,8 @@
 //! :---     | :---       | :---:     | :---   
 //! 0.1      | 2018-04-03 | Clunion   | creation
 //! 0.2      | 2021-05-24 | Clunion   | building up some source structure, renamed from rm_skin_gen to arise
+//! 0.3      | 2026-10-19 | Clunion   | subcommand import, infers an arise template from an existing skin
+//! 0.4      | 2026-10-19 | Clunion   | subcommand simulate, evaluates Calc measures and IfConditions with mock values
 //! ___________________________________________________________________________________________________________________________
 //!# Examples
 //!```text
@@ -27,6 +29,15 @@
 //! arise(.exe) --sourcefile=<an_existing_arise_generator_definition_file>
 //!     Reads the generator definition (<name>.arise) from the given definition file, interprets it and generates new 
 //!     Rainmeter-ini file based on it.
+//!
+//! arise(.exe) import <an_existing_rainmeter_skin.ini>
+//!     Infers a generator definition from a hand written skin: sections repeated for several drives (or other items)
+//!     become one multiplied block. The definition (<name>.arise) is written into the input directory, evolving it
+//!     gives the skin again.
+//!
+//! arise(.exe) simulate <skin> --values <mock_values.json>
+//!     Evaluates the Calc measures and IfConditions of the evolved skin, with the mock values for the other measures,
+//!     and writes the values, the actions which would fire and the divisions by zero to the console window.
 //!```
 //! ___________________________________________________________________________________________________________________________
 //!    
@@ -45,11 +56,13 @@ use std::path::PathBuf;
 use log::{trace, debug, info, warn, error};
 use flexi_logger::{Logger, FileSpec, Duplicate, Cleanup, Criterion, Naming};
 
-use clap::Arg;
+use clap::{Arg, SubCommand};
 
 use crate::modules::*;                    // crate::<dirname>::*
 use crate::modules::core_logic::*;        // crate::<filename>::*
 use crate::modules::config::*;            // crate::<filename>::*
+use crate::modules::import::import_skin;  // crate::<filename>::<function>
+use crate::modules::simulate::simulate_skin; // crate::<filename>::<function>
 
 //___ CONSTANTS: ______________________________________________________________________________________________________________
 //___ none ___
@@ -85,12 +98,13 @@ use crate::modules::config::*;            // crate::<filename>::*
 /// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
 /// :---    | :---       | :---:     | :---   
 /// 1.0     | 2020-04-## | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | subcommand import   
+/// 1.2     | 2026-10-19 | Clunion   | subcommand simulate   
 /// ___________________________________________________________________________________________________________________________
 /// **`TODO:       `**   
 ///  * define command line arguments for all configuration switches and variables    
 ///  * add handling of testing mode   
 /// ___________________________________________________________________________________________________________________________
-
 fn main() -> Result<(), MainError>
 {
 let mut arise_config: AriseConfig = AriseConfig::default();
@@ -138,6 +152,10 @@ let cmd_line = clap::App::new("Arise")
                        .short("t")
                        .long("test")
                        .takes_value(false))
+                   .arg(Arg::with_name("strict-mode")                  // <--STRICT-MODE-------------------------------------------
+                       .help("Treats warnings as errors, e.g. for builds in CI.")
+                       .long("strict")
+                       .takes_value(false))
                    .arg(Arg::with_name("debug-mode")                   // <--DEBUG-MODE--------------------------------------------
                        .short("d")
                        .long("debug")
@@ -149,6 +167,21 @@ let cmd_line = clap::App::new("Arise")
                        .value_name("SKIN")
                        .help("Sets the name of the skin, used as basename for source- and target-files, extensions are '.arise' and '.ini'.")
                        .takes_value(true))
+                   .subcommand(SubCommand::with_name("import")         // <--IMPORT of an existing skin----------------------------
+                       .about("Infers an arise template from an existing Rainmeter skin, which evolves into the skin again.")
+                       .arg(Arg::with_name("ini-file")
+                           .help("The existing skin (.ini), the template is written into the input directory.")
+                           .required(true)))
+                   .subcommand(SubCommand::with_name("simulate")       // <--SIMULATE the formulas of a skin---------------------
+                       .about("Evaluates the Calc measures and IfConditions of a skin offline, with mock values for the other measures.")
+                       .arg(Arg::with_name("skin")
+                           .help("The name of the skin, evolved from the input directory, or a Rainmeter skin (.ini).")
+                           .required(true))
+                       .arg(Arg::with_name("values")
+                           .long("values")
+                           .value_name("JSON")
+                           .help("A JSON object with the mock values of measures by name, like { \"Measure_Drive_C_Used\": 0 }.")
+                           .takes_value(true)))
                    .get_matches();
 
 // Increase the amount of logging based on how many times the user used the "verbose" flag (i.e. 'myprog -v' or 'myprog -vv' or 'myprog -v -v -v':
@@ -163,6 +196,7 @@ match cmd_line.occurrences_of("verbosity")
 
 // Handle the existence of command line parameters by matching over name:
 if  cmd_line.is_present("test-mode")     {info!("Test Mode enabled")    ; arise_config.test        = true; }
+if  cmd_line.is_present("strict-mode")   {info!("Strict Mode enabled")  ; arise_config.strict      = true; }
 if  cmd_line.is_present("debug-mode")    {info!("Debug Mode enabled")   ; arise_config.debug       = true; } 
 
 // Get the values of the known parameters, if supplied on command line, or set them with the defaults:
@@ -190,6 +224,28 @@ if !exists_dir(&arise_config.res_pathpart) {match create_dir(&arise_config.res_p
 if !exists_dir(&arise_config.inp_pathpart) {match create_dir(&arise_config.inp_pathpart) {Ok(_) => info!("created: '{}'",arise_config.inp_pathpart.display()), Err(error) => panic!("couldn't create dir '{}': {}", arise_config.inp_pathpart.display(), error),}; }
 if !exists_dir(&arise_config.out_pathpart) {match create_dir(&arise_config.out_pathpart) {Ok(_) => info!("created: '{}'",arise_config.out_pathpart.display()), Err(error) => panic!("couldn't create dir '{}': {}", arise_config.out_pathpart.display(), error),}; }
 
+// import an existing skin instead of evolving one:
+if let Some(import) = cmd_line.subcommand_matches("import")
+    {
+    let ini_file = PathBuf::from(import.value_of("ini-file").unwrap_or_default());
+    return match import_skin(&arise_config, &ini_file)
+        {
+        Err(error)       => { error!("Error importing the skin:\n{}", error); Err(error.into()) },
+        Ok(arise_file)   => { info!("OK, wrote '{}'.", arise_file.display()); Ok(()) },
+        };
+    }
+
+// simulate the formulas of a skin instead of evolving one:
+if let Some(simulate) = cmd_line.subcommand_matches("simulate")
+    {
+    let values = simulate.value_of("values").map(PathBuf::from);
+    return match simulate_skin(&arise_config, simulate.value_of("skin").unwrap_or_default(), values.as_deref())
+        {
+        Err(error)       => { error!("Error simulating the skin:\n{}", error); Err(error.into()) },
+        Ok(report)       => { println!("{}", report); Ok(()) },
+        };
+    }
+
 // do the real work:
 match core_logic(&arise_config)
     {
@@ -104,21 +104,19 @@ match pieces.next()
 pub(crate) fn console_line_format( w: &mut dyn std::io::Write, now: &mut DeferredNow, record: &Record, ) -> Result<(), std::io::Error> 
 {
 let level = record.level();
-let arise_style: Style;
-
 let  error_style: Style = Style::new(Color::Red).bold().italic();  // todo: move to a one-time initializer or change into static
 let   warn_style: Style = Style::new(Color::Yellow).italic()    ;  // todo: move to a one-time initializer or change into static
 let   info_style: Style = Style::new(Color::Cyan)               ;  // todo: move to a one-time initializer or change into static
 let  debug_style: Style = Style::new(Color::Default)            ;  // todo: move to a one-time initializer or change into static
 let  trace_style: Style = Style::new(Color::Blue)               ;  // todo: move to a one-time initializer or change into static
 
-match level 
+let arise_style: Style = match level 
     {
-    log::Level::Error => {arise_style = error_style;},
-    log::Level::Warn  => {arise_style =  warn_style;},
-    log::Level::Info  => {arise_style =  info_style;},
-    log::Level::Debug => {arise_style = debug_style;},
-    log::Level::Trace => {arise_style = trace_style;},
+    log::Level::Error => error_style,
+    log::Level::Warn  =>  warn_style,
+    log::Level::Info  =>  info_style,
+    log::Level::Debug => debug_style,
+    log::Level::Trace => trace_style,
     };
 
 write!( w, 
@@ -0,0 +1,378 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** bangs.rs 🦀   
+//! **`DESCRIPTION:`** splits the actions of the evolved skin into bangs, and checks their names, arguments and targets   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! IfTrueAction=[!HideMeterGroup "MeterGroup_Drive_C"][!Log "C is gone" Warning]   
+//! LeftMouseUpAction=["C:"]   
+//!```
+//! The first action holds two bangs, `!HideMeterGroup` with one argument and `!Log` with two; the second one is a   
+//! command, Rainmeter opens the drive C: with it.   
+//! Meters, measures and groups are only checked, if a bang does not name a config as its last argument, as a bang for   
+//! another skin refers to the sections of that skin.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::path::Path;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::ini::IniDocument;
+use crate::modules::expansion::SectionOrigin;
+use crate::modules::duplicates::locate_origins;
+use crate::modules::references::{KeyWarnings, SkinIndex, Target, unknown_message, unknown_help};
+use crate::modules::diagnostics::{Diagnostic, closest_name};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+/// The bangs of Rainmeter: name, least and most arguments, and which argument names what.   
+/// For the bangs naming a section or group, the most arguments include the config as the optional last one.   
+const BANGS: &[BangSpec] =
+    &[
+    // the skin:
+    ("Refresh",                 0, 1, None), ("Redraw",          0, 1, None), ("Update",            0, 1, None),
+    ("Show",                    0, 1, None), ("Hide",            0, 1, None), ("Toggle",            0, 1, None),
+    ("ShowFade",                0, 1, None), ("HideFade",        0, 1, None), ("ToggleFade",        0, 1, None),
+    ("FadeDuration",            1, 2, None), ("Move",            2, 3, None), ("ZPos",              1, 2, None),
+    ("SetTransparency",         1, 2, None), ("Draggable",       1, 2, None), ("KeepOnScreen",      1, 2, None),
+    ("ClickThrough",            1, 2, None), ("SnapEdges",       1, 2, None), ("AutoSelectScreen",  1, 2, None),
+    ("SetAnchor",               2, 3, None), ("SetWindowPosition", 2, 5, None),
+    ("SkinMenu",                0, 1, None), ("SkinCustomMenu",  0, 1, None),
+    // configs, groups of configs and the application:
+    ("ActivateConfig",          1, 2, None), ("DeactivateConfig", 0, 1, None), ("ToggleConfig",     2, 2, None),
+    ("ShowGroup",               1, 1, None), ("HideGroup",       1, 1, None), ("ToggleGroup",       1, 1, None),
+    ("ShowFadeGroup",           1, 1, None), ("HideFadeGroup",   1, 1, None), ("ToggleFadeGroup",   1, 1, None),
+    ("RefreshGroup",            1, 1, None), ("UpdateGroup",     1, 1, None), ("RedrawGroup",       1, 1, None),
+    ("DeactivateConfigGroup",   1, 1, None), ("LoadLayout",      1, 1, None), ("RefreshApp",        0, 0, None),
+    ("Manage",                  0, 3, None), ("About",           0, 1, None), ("TrayMenu",          0, 0, None),
+    ("Quit",                    0, 0, None), ("Log",             1, 2, None), ("Delay",             1, 1, None),
+    ("SetClip",                 1, 1, None), ("SetWallpaper",    1, 2, None), ("WriteKeyValue",     2, 4, None),
+    ("SetVariable",             2, 3, None), ("SetVariableGroup", 3, 3, None),
+    // meters:
+    ("ShowMeter",               1, 2, Some((0, Target::Meter))),        ("HideMeter",          1, 2, Some((0, Target::Meter))),
+    ("ToggleMeter",             1, 2, Some((0, Target::Meter))),        ("UpdateMeter",        1, 2, Some((0, Target::Meter))),
+    ("MoveMeter",               3, 4, Some((2, Target::Meter))),
+    ("ShowMeterGroup",          1, 2, Some((0, Target::MeterGroup))),   ("HideMeterGroup",     1, 2, Some((0, Target::MeterGroup))),
+    ("ToggleMeterGroup",        1, 2, Some((0, Target::MeterGroup))),   ("UpdateMeterGroup",   1, 2, Some((0, Target::MeterGroup))),
+    ("EnableMouseAction",       2, 3, Some((0, Target::Meter))),        ("DisableMouseAction", 2, 3, Some((0, Target::Meter))),
+    ("ToggleMouseAction",       2, 3, Some((0, Target::Meter))),        ("ClearMouseAction",   2, 3, Some((0, Target::Meter))),
+    ("EnableMouseActionGroup",  2, 3, Some((1, Target::MeterGroup))),   ("DisableMouseActionGroup", 2, 3, Some((1, Target::MeterGroup))),
+    ("ToggleMouseActionGroup",  2, 3, Some((1, Target::MeterGroup))),   ("ClearMouseActionGroup",   2, 3, Some((1, Target::MeterGroup))),
+    // measures:
+    ("EnableMeasure",           1, 2, Some((0, Target::Measure))),      ("DisableMeasure",     1, 2, Some((0, Target::Measure))),
+    ("ToggleMeasure",           1, 2, Some((0, Target::Measure))),      ("UpdateMeasure",      1, 2, Some((0, Target::Measure))),
+    ("PauseMeasure",            1, 2, Some((0, Target::Measure))),      ("UnpauseMeasure",     1, 2, Some((0, Target::Measure))),
+    ("TogglePauseMeasure",      1, 2, Some((0, Target::Measure))),      ("CommandMeasure",     2, 3, Some((0, Target::Measure))),
+    ("EnableMeasureGroup",      1, 2, Some((0, Target::MeasureGroup))), ("DisableMeasureGroup", 1, 2, Some((0, Target::MeasureGroup))),
+    ("ToggleMeasureGroup",      1, 2, Some((0, Target::MeasureGroup))), ("UpdateMeasureGroup",  1, 2, Some((0, Target::MeasureGroup))),
+    ("PauseMeasureGroup",       1, 2, Some((0, Target::MeasureGroup))), ("UnpauseMeasureGroup", 1, 2, Some((0, Target::MeasureGroup))),
+    ("TogglePauseMeasureGroup", 1, 2, Some((0, Target::MeasureGroup))),
+    // options of any section:
+    ("SetOption",               3, 4, Some((0, Target::Style))),        ("SetOptionGroup",     3, 4, Some((0, Target::Group))),
+    ];
+
+/// Before Rainmeter 2.3, the bangs were named like `!RainmeterShowMeter`, these names still work.   
+const OLD_BANG_PREFIX: &str = "Rainmeter";
+
+//___ TYPES: __________________________________________________________________________________________________________________
+/// A bang: name, least and most arguments, the argument naming a section or group and what it has to name.   
+type BangSpec = (&'static str, usize, usize, Option<(usize, Target)>);
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// One action between brackets.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Action
+{
+    Bang { name: String, args: Vec<String> },   // [!Name "argument" argument]
+    Command(String),                             // ["C:"], [notepad.exe]: started by Rainmeter
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+//___ none ___
+
+//___ METHODS: ________________________________________________________________________________________________________________
+//___ none ___
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  is_action_key   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name           `** the name of a key   
+/// **`RETURNS:    `** **` bool           `** true for keys holding actions, like `IfTrueAction2` or `LeftMouseUpAction`   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn is_action_key(name: &str) -> bool
+{
+name.trim_end_matches(|c: char| c.is_ascii_digit()).to_lowercase().ends_with("action")
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_actions   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` value          `** the value of an action key   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the actions in their order)   
+/// **`            `** **`     or -->     `** - Error, the text telling what is wrong with the brackets   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Brackets in quotes do not count, `[!SetOption Meter Text "[Measure]"]` is one bang. A value without brackets is a   
+/// single action, as Rainmeter allows `LeftMouseUpAction=!Refresh`.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_actions(value: &str) -> Result<Vec<Action>, String>
+{
+let value = value.trim();
+if value.is_empty()          { return Ok(Vec::new()); }
+if !value.starts_with('[')   { return Ok(vec![parse_action(value)]); }
+
+let mut actions = Vec::new();
+let mut depth   = 0;
+let mut quoted  = false;
+let mut start   = 0;
+for (position, c) in value.char_indices()
+    {
+    match c
+        {
+        '"' if depth > 0 => quoted = !quoted,
+        '[' if !quoted   =>
+            {
+            if depth == 0 { start = position + 1; }
+            depth += 1;
+            },
+        ']' if !quoted   =>
+            {
+            if depth == 0 { return Err("there is a ']' without a '['".to_string()); }
+            depth -= 1;
+            if depth == 0 { actions.push(parse_action(&value[start .. position])); }
+            },
+        _ if depth == 0 && !c.is_whitespace() =>
+            {
+            let text: String = value[position ..].chars().take(20).collect();
+            return Err(format!("'{}' is outside of the brackets", text));
+            },
+        _ => {},
+        }
+    }
+if depth > 0 { return Err("a '[' is not closed".to_string()); }
+Ok(actions)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_action / arguments   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// One action, the text between its brackets, and the arguments of a bang: separated by spaces, quoted by `"` or by   
+/// `"""` (which may contain `"`), the quotes are removed.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn parse_action(text: &str) -> Action
+{
+let text = text.trim();
+match text.strip_prefix('!')
+    {
+    Some(bang) =>
+        {
+        let end = bang.find(char::is_whitespace).unwrap_or(bang.len());
+        Action::Bang { name: bang[.. end].to_string(), args: arguments(&bang[end ..]) }
+        },
+    None => Action::Command(text.trim_matches('"').to_string()),
+    }
+}
+
+fn arguments(text: &str) -> Vec<String>
+{
+let mut args = Vec::new();
+let mut rest = text.trim_start();
+while !rest.is_empty()
+    {
+    let (arg, after) = if let Some(inner) = rest.strip_prefix("\"\"\"")
+                           {
+                           match inner.find("\"\"\"") { Some(end) => (&inner[.. end], &inner[end + 3 ..]), None => (inner, "") }
+                           }
+                       else if let Some(inner) = rest.strip_prefix('"')
+                           {
+                           match inner.find('"') { Some(end) => (&inner[.. end], &inner[end + 1 ..]), None => (inner, "") }
+                           }
+                       else
+                           {
+                           let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                           (&rest[.. end], &rest[end ..])
+                           };
+    args.push(arg.to_string());
+    rest = after.trim_start();
+    }
+args
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  bang_actions   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file           `** the arise file, named for sections without a known origin   
+/// **`            `** **` skin           `** the evolved skin   
+/// **`            `** **` origins        `** where the sections come from, in the order they were evolved   
+/// **`RETURNS:    `** **` Vec            `** a warning for every action with broken brackets, every unknown bang, wrong   
+/// **`            `** **`                `** number of arguments and unknown meter, measure or group, one per template line   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Targets built from variables or section variables, and `*` for all meters or measures, are only known at runtime.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn bang_actions(file: &Path, skin: &IniDocument, origins: &[SectionOrigin]) -> Vec<Diagnostic>
+{
+let index   = SkinIndex::new(skin);
+let located = locate_origins(skin, origins);
+let names: Vec<&str> = BANGS.iter().map(|bang| bang.0).collect();
+
+let mut warnings = KeyWarnings::new();
+for (position, section) in skin.sections.iter().enumerate()
+    {
+    for key in section.keys().filter(|key| is_action_key(&key.name))
+        {
+        let mut problems: Vec<(String, String)> = Vec::new();
+        match parse_actions(&key.value)
+            {
+            Err(why)    => problems.push((format!("broken brackets in {} of section [{}]", key.name, section.name), why)),
+            Ok(actions) =>
+                {
+                for (name, args) in actions.iter().filter_map(|action| match action { Action::Bang { name, args } => Some((name, args)), _ => None })
+                    {
+                    let (known, least, most, target) = match BANGS.iter().find(|bang| bang.0.eq_ignore_ascii_case(name))
+                        {
+                        Some(bang) => *bang,
+                        None       =>
+                            {
+                            let old  = name.get(.. OLD_BANG_PREFIX.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(OLD_BANG_PREFIX))
+                                       && names.iter().any(|known| known.eq_ignore_ascii_case(&name[OLD_BANG_PREFIX.len() ..]));
+                            let help = if old { format!("it is the old name of !{}, use that", &name[OLD_BANG_PREFIX.len() ..]) }
+                                       else   { closest_name(name, names.iter().copied()).map_or("it is none of Rainmeter's bangs".to_string(), |closest| format!("did you mean '!{}'?", closest)) };
+                            problems.push((format!("unknown bang '!{}' in {} of section [{}]", name, key.name, section.name), help));
+                            continue;
+                            },
+                        };
+
+                    if args.len() < least || args.len() > most
+                        {
+                        let takes = if least == most { format!("{}", least) } else { format!("{} to {}", least, most) };
+                        problems.push((format!("!{} has {} argument(s) in {} of section [{}]", known, args.len(), key.name, section.name),
+                                       format!("it takes {}; are quotes missing around an argument with spaces?", takes)));
+                        continue;
+                        }
+
+                    if let Some((argument, target)) = target
+                        {
+                        let name = &args[argument];
+                        if args.len() == most || name == "*" || name.contains('#') || name.contains('[') || index.knows(target, name) { continue; }
+                        problems.push((unknown_message(target, name, &key.name, &section.name), unknown_help(target, closest_name(name, index.candidates(target)))));
+                        }
+                    }
+                },
+            }
+        for (nth, (message, help)) in problems.into_iter().enumerate()
+            {
+            warnings.add(located[position], &key.name, nth, &section.name, message, help);
+            }
+        }
+    }
+
+let warnings = warnings.into_diagnostics(file);
+debug!("checked the actions of {} sections, {} warning(s)", skin.sections.len(), warnings.len());
+warnings
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for bangs   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  use std::path::PathBuf;
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** actions_are_split_into_bangs()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that bangs, their quoted arguments and commands are found   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn actions_are_split_into_bangs()
+  {
+  let actions = parse_actions(r#"[!HideMeterGroup "MeterGroup_Drive_C"] [!SetOption Meter Text """say "[Measure]" """]["C:"]"#).unwrap();
+  assert_eq!(actions, [Action::Bang    { name: "HideMeterGroup".to_string(), args: vec!["MeterGroup_Drive_C".to_string()] },
+                       Action::Bang    { name: "SetOption".to_string(),      args: vec!["Meter".to_string(), "Text".to_string(), "say \"[Measure]\" ".to_string()] },
+                       Action::Command("C:".to_string())]);
+  assert_eq!(parse_actions("!Refresh").unwrap(), [Action::Bang { name: "Refresh".to_string(), args: vec![] }]);
+  assert!(parse_actions("[!Refresh").is_err());
+  assert!(parse_actions("[!Refresh] !Redraw").is_err());
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** bangs_are_checked()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that unknown bangs, wrong numbers of arguments and undeclared groups are reported   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn bangs_are_checked()
+  {
+  let skin = IniDocument::parse("[Measure_Total]\nMeasure=FreeDiskSpace\nGroup=MeasureGroup_Drive_C\n\
+                                 IfTrueAction=[!HideMeterGroup \"MeterGroup_Drive_C\"][!UpdateMeasureGroup MeasureGroup_Drive_C]\n\
+                                 IfFalseAction=[!ShowMeterGroup \"MeterGroup_Drive_D\"][!HideMeterGroup Other OtherSkin\\Config]\n\
+                                 [Meter_Frame]\nMeter=IMAGE\nGroup=Frames | MeterGroup_Drive_C\n\
+                                 LeftMouseUpAction=[\"C:\"][!Refrsh][!RainmeterHideMeter Meter_Frame][!SetOption Meter_Frame X 1 2 3]\n");
+
+  let warnings = bang_actions(&PathBuf::from("t.arise"), &skin, &[]);
+  let messages: Vec<&str> = warnings.iter().map(|warning| warning.message.as_str()).collect();
+  assert_eq!(messages, ["unknown meter group 'MeterGroup_Drive_D' in IfFalseAction of section [Measure_Total]",
+                        "unknown bang '!Refrsh' in LeftMouseUpAction of section [Meter_Frame]",
+                        "unknown bang '!RainmeterHideMeter' in LeftMouseUpAction of section [Meter_Frame]",
+                        "!SetOption has 5 argument(s) in LeftMouseUpAction of section [Meter_Frame]"]);
+  assert_eq!(warnings[0].help.as_deref(), Some("did you mean 'MeterGroup_Drive_C'?"));
+  assert_eq!(warnings[1].help.as_deref(), Some("did you mean '!Refresh'?"));
+  assert_eq!(warnings[2].help.as_deref(), Some("it is the old name of !HideMeter, use that"));
+  }
+
+} // End of: mod test
@@ -0,0 +1,727 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** calc.rs 🦀   
+//! **`DESCRIPTION:`** parses the formulas of Calc measures and IfConditions, and finds possible divisions by zero   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | evaluation of formulas, for the simulation   
+//! 0.3      | 2026-10-19 | Clunion   | Formula checked in Calc measures only   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! Formula=100/(Measure_Drive_C_Total / Measure_Drive_C_Used)   
+//! IfCondition=(Measure_Drive_C_Total = 0) && !Measure_Drive_C_Type   
+//! Formula=(Measure_Drive_C_Used = 0 ? 0 : Measure_Drive_C_Total / Measure_Drive_C_Used)   
+//!```
+//! The first formula divides by zero for an empty drive (and for a removed one, twice), the last one does not: the   
+//! measures tested for 0 by a condition are taken as not 0 in its other branch.   
+//! Operators from loosest to tightest: `? :`, `||`, `&&`, `= == != <> < > <= >=`, `+ -`, `* / %`, unary `- + !`, `^`.   
+//! Variables `#Name#` and section variables `[Name]` are taken as values.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::collections::BTreeSet;
+use std::path::Path;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::ini::{IniDocument, IniSection};
+use crate::modules::expansion::SectionOrigin;
+use crate::modules::duplicates::locate_origins;
+use crate::modules::references::KeyWarnings;
+use crate::modules::diagnostics::{Diagnostic, closest_name};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+/// The functions of Calc, with their least and most arguments.   
+pub(crate) const CALC_FUNCTIONS: &[(&str, usize, usize)] =
+    &[
+    ("Abs",  1, 1), ("Acos", 1, 1), ("Asin",  1, 1), ("Atan", 1, 1), ("Atan2", 2, 2), ("Ceil", 1, 1), ("Clamp", 3, 3),
+    ("Cos",  1, 1), ("Exp",  1, 1), ("Floor", 1, 1), ("Frac", 1, 1), ("Ln",    1, 1), ("Log",  1, 1), ("Max",   2, 2),
+    ("Min",  2, 2), ("Rad",  1, 1), ("Round", 1, 2), ("Sgn",  1, 1), ("Sin",   1, 1), ("Sqrt", 1, 1), ("Tan",   1, 1),
+    ("Trunc", 1, 1),
+    ];
+
+/// Names in formulas which are no measures.   
+pub(crate) const CALC_CONSTANTS: &[&str] = &["Pi", "E", "Counter"];
+
+/// The binary operators by precedence, loosest first; `^` binds tighter than the unary operators.   
+const BINARY_LEVELS: &[&[&str]] = &[&["||"], &["&&"], &["=", "==", "!=", "<>", "<", ">", "<=", ">="], &["+", "-"], &["*", "/", "%"]];
+
+/// All operators, the longer ones first.   
+const OPERATORS: &[&str] = &["&&", "||", "==", "!=", "<>", "<=", ">=", "<", ">", "=", "+", "-", "*", "/", "%", "^", "!", "?", ":", "(", ")", ","];
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// A parsed formula.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Formula
+{
+    Number(f64),
+    Name(String),                                       // a measure, or one of CALC_CONSTANTS
+    Section(String),                                    // [Name], the value of a measure at runtime
+    Variable(String),                                   // #Name#
+    Unary(&'static str, Box<Formula>),
+    Binary(&'static str, Box<Formula>, Box<Formula>),
+    Condition(Box<Formula>, Box<Formula>, Box<Formula>), // test ? then : else
+    Call(String, Vec<Formula>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token
+{
+    Number(f64),
+    Name(String),
+    Section(String),
+    Variable(String),
+    Operator(&'static str),
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// What is wrong with a formula.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CalcError
+{
+    pub(crate) message: String,
+    pub(crate) help:    Option<String>,
+}
+
+/// The tokens of a formula, with their 1-based column and text.   
+struct Parser
+{
+    tokens:   Vec<(Token, usize, String)>,
+    position: usize,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+impl Formula
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  names   
+/// **`TYPE:       `**  method of Formula   
+/// ___________________________________________________________________________________________________________________________
+/// **`RETURNS:    `** **` Vec            `** the measures named in the formula (not the section variables), in their order   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn names(&self) -> Vec<String>
+    {
+        match self
+            {
+            Formula::Name(name) if !is_constant(name) => vec![name.clone()],
+            Formula::Unary(_, inner)                  => inner.names(),
+            Formula::Binary(_, left, right)           => [left.names(), right.names()].concat(),
+            Formula::Condition(test, then, other)     => [test.names(), then.names(), other.names()].concat(),
+            Formula::Call(_, args)                    => args.iter().flat_map(Formula::names).collect(),
+            _                                         => Vec::new(),
+            }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  evaluate   
+/// **`TYPE:       `**  method of Formula   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` value_of       `** gives the values of measures, section variables and variables   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the value of the formula)   
+/// **`            `** **`     or -->     `** - Error, a division by zero or what value_of says   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Comparisons and logical operators give 1 or 0. Only the branch of a condition which is taken is evaluated, as is   
+/// the right side of `&&` and `||` only if needed, so guarded divisions are no errors. `Counter` is 1, as in the   
+/// first update.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn evaluate(&self, value_of: &mut dyn FnMut(&Formula) -> Result<f64, String>) -> Result<f64, String>
+    {
+        let truth = |value: bool| if value { 1.0 } else { 0.0 };
+        match self
+            {
+            Formula::Number(number)                       => Ok(*number),
+            Formula::Name(name) if name.eq_ignore_ascii_case("Pi")      => Ok(std::f64::consts::PI),
+            Formula::Name(name) if name.eq_ignore_ascii_case("E")       => Ok(std::f64::consts::E),
+            Formula::Name(name) if name.eq_ignore_ascii_case("Counter") => Ok(1.0),
+            Formula::Name(_) | Formula::Section(_) | Formula::Variable(_) => value_of(self),
+            Formula::Unary(operator, inner)               =>
+                {
+                let value = inner.evaluate(value_of)?;
+                Ok(match *operator { "-" => -value, "!" => truth(value == 0.0), _ => value })
+                },
+            Formula::Binary("&&", left, right)            => Ok(truth(left.evaluate(value_of)? != 0.0 && right.evaluate(value_of)? != 0.0)),
+            Formula::Binary("||", left, right)            => Ok(truth(left.evaluate(value_of)? != 0.0 || right.evaluate(value_of)? != 0.0)),
+            Formula::Binary(operator, left, right)        =>
+                {
+                let (left_value, right_value) = (left.evaluate(value_of)?, right.evaluate(value_of)?);
+                if (*operator == "/" || *operator == "%") && right_value == 0.0
+                    {
+                    return Err(format!("division by zero, '{}' is 0", display(right)));
+                    }
+                Ok(match *operator
+                    {
+                    "+"         => left_value + right_value,
+                    "-"         => left_value - right_value,
+                    "*"         => left_value * right_value,
+                    "/"         => left_value / right_value,
+                    "%"         => left_value % right_value,
+                    "^"         => left_value.powf(right_value),
+                    "=" | "=="  => truth(left_value == right_value),
+                    "!=" | "<>" => truth(left_value != right_value),
+                    "<"         => truth(left_value <  right_value),
+                    ">"         => truth(left_value >  right_value),
+                    "<="        => truth(left_value <= right_value),
+                    _           => truth(left_value >= right_value),
+                    })
+                },
+            Formula::Condition(test, then, other)         =>
+                if test.evaluate(value_of)? != 0.0 { then.evaluate(value_of) } else { other.evaluate(value_of) },
+            Formula::Call(name, args)                     =>
+                {
+                let mut values = Vec::new();
+                for arg in args { values.push(arg.evaluate(value_of)?); }
+                let x = values[0];
+                let y = values.get(1).copied().unwrap_or(0.0);
+                Ok(match name.to_lowercase().as_str()
+                    {
+                    "abs"   => x.abs(),   "acos" => x.acos(), "asin"  => x.asin(),  "atan" => x.atan(),  "atan2" => x.atan2(y),
+                    "ceil"  => x.ceil(),  "cos"  => x.cos(),  "exp"   => x.exp(),   "floor" => x.floor(), "frac" => x.fract(),
+                    "ln"    => x.ln(),    "log"  => x.log10(), "max"  => x.max(y),  "min"  => x.min(y),   "rad"  => x.to_radians(),
+                    "sgn"   => if x == 0.0 { 0.0 } else { x.signum() },
+                    "sin"   => x.sin(),   "sqrt" => x.sqrt(), "tan"   => x.tan(),   "trunc" => x.trunc(),
+                    "clamp" => x.max(y).min(values[2]),
+                    "round" => { let scale = 10f64.powf(y.trunc()); (x * scale).round() / scale },
+                    _       => return Err(format!("unknown function '{}'", name)),
+                    })
+                },
+            }
+    }
+
+} // End of impl: Formula
+
+
+impl Parser
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  condition / binary / unary / power / primary   
+/// **`TYPE:       `**  methods of Parser   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// A recursive descent over the precedence levels, each method parses the operators of its level and leaves the   
+/// tighter ones to the next.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn condition(&mut self) -> Result<Formula, CalcError>
+    {
+        let test = self.binary(0)?;
+        if !self.eat("?") { return Ok(test); }
+        let then = self.condition()?;
+        if !self.eat(":") { return Err(self.unexpected("a ':' of the '?'")); }
+        let other = self.condition()?;
+        Ok(Formula::Condition(Box::new(test), Box::new(then), Box::new(other)))
+    }
+
+fn binary(&mut self, level: usize) -> Result<Formula, CalcError>
+    {
+        if level == BINARY_LEVELS.len() { return self.unary(); }
+        let mut left = self.binary(level + 1)?;
+        while let Some(operator) = BINARY_LEVELS[level].iter().find(|operator| self.peek() == Some(&Token::Operator(operator)))
+            {
+            self.position += 1;
+            let right = self.binary(level + 1)?;
+            left = Formula::Binary(operator, Box::new(left), Box::new(right));
+            }
+        Ok(left)
+    }
+
+fn unary(&mut self) -> Result<Formula, CalcError>
+    {
+        for operator in ["-", "+", "!"]
+            {
+            if self.eat(operator) { return Ok(Formula::Unary(operator, Box::new(self.unary()?))); }
+            }
+        self.power()
+    }
+
+fn power(&mut self) -> Result<Formula, CalcError>
+    {
+        let base = self.primary()?;
+        if !self.eat("^") { return Ok(base); }
+        Ok(Formula::Binary("^", Box::new(base), Box::new(self.unary()?)))
+    }
+
+fn primary(&mut self) -> Result<Formula, CalcError>
+    {
+        let Some((token, column, text)) = self.tokens.get(self.position).cloned() else { return Err(self.unexpected("a value")); };
+        self.position += 1;
+        match token
+            {
+            Token::Number(number)     => Ok(Formula::Number(number)),
+            Token::Section(name)      => Ok(Formula::Section(name)),
+            Token::Variable(name)     => Ok(Formula::Variable(name)),
+            Token::Operator("(")      =>
+                {
+                let inner = self.condition()?;
+                if !self.eat(")") { return Err(CalcError { message: format!("the '(' at column {} is not closed", column), help: None }); }
+                Ok(inner)
+                },
+            Token::Name(name) if self.peek() == Some(&Token::Operator("(")) =>
+                {
+                self.position += 1;
+                let mut args = vec![self.condition()?];
+                while self.eat(",") { args.push(self.condition()?); }
+                if !self.eat(")") { return Err(CalcError { message: format!("the '(' of {} at column {} is not closed", name, column), help: None }); }
+                match CALC_FUNCTIONS.iter().find(|function| function.0.eq_ignore_ascii_case(&name))
+                    {
+                    None => Err(CalcError { message: format!("unknown function '{}' at column {}", name, column),
+                                            help:    closest_name(&name, CALC_FUNCTIONS.iter().map(|function| function.0)).map(|closest| format!("did you mean '{}'?", closest)) }),
+                    Some((known, least, most)) if args.len() < *least || args.len() > *most =>
+                        {
+                        let takes = if least == most { format!("{}", least) } else { format!("{} to {}", least, most) };
+                        Err(CalcError { message: format!("{} at column {} has {} argument(s)", known, column, args.len()), help: Some(format!("it takes {}", takes)) })
+                        },
+                    Some(_) => Ok(Formula::Call(name, args)),
+                    }
+                },
+            Token::Name(name)         => Ok(Formula::Name(name)),
+            Token::Operator(_)        => Err(CalcError { message: format!("unexpected '{}' at column {}, a value is missing", text, column), help: None }),
+            }
+    }
+
+fn peek(&self) -> Option<&Token>
+    {
+        self.tokens.get(self.position).map(|(token, _, _)| token)
+    }
+
+fn eat(&mut self, operator: &str) -> bool
+    {
+        let found = matches!(self.peek(), Some(Token::Operator(found)) if *found == operator);
+        if found { self.position += 1; }
+        found
+    }
+
+fn unexpected(&self, expected: &str) -> CalcError
+    {
+        match self.tokens.get(self.position)
+            {
+            Some((_, column, text)) => CalcError { message: format!("unexpected '{}' at column {}, expected is {}", text, column, expected), help: None },
+            None                    => CalcError { message: format!("the formula ends where {} is expected", expected), help: None },
+            }
+    }
+
+} // End of impl: Parser
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_formula   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a formula, like the value of `Formula=` or `IfCondition=`   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the parsed formula)   
+/// **`            `** **`     or -->     `** - Error, what is wrong at which column   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_formula(text: &str) -> Result<Formula, CalcError>
+{
+let mut parser = Parser { tokens: tokenize(text)?, position: 0 };
+if parser.tokens.is_empty() { return Err(CalcError { message: "the formula is empty".to_string(), help: None }); }
+let formula = parser.condition()?;
+match parser.tokens.get(parser.position)
+    {
+    Some((Token::Operator(")"), column, _)) => Err(CalcError { message: format!("the ')' at column {} has no '('", column), help: None }),
+    Some((_, column, text))                 => Err(CalcError { message: format!("unexpected '{}' at column {}, an operator is missing", text, column), help: None }),
+    None                                    => Ok(formula),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  tokenize   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a formula   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the tokens with their column and text)   
+/// **`            `** **`     or -->     `** - Error, for chars which belong to no token   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Numbers are decimal (`1.5`, `500e9`), hexadecimal (`0xFF`), binary (`0b101`) or octal (`0o17`).   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn tokenize(text: &str) -> Result<Vec<(Token, usize, String)>, CalcError>
+{
+let chars: Vec<char> = text.chars().collect();
+let mut tokens = Vec::new();
+let mut index  = 0;
+while index < chars.len()
+    {
+    let c     = chars[index];
+    let start = index;
+    let rest: String = chars[index ..].iter().collect();
+    if c.is_whitespace() { index += 1; continue; }
+
+    let token = if c.is_ascii_digit() || (c == '.' && chars.get(index + 1).is_some_and(char::is_ascii_digit))
+        {
+        let radix = match rest.get(.. 2).map(str::to_ascii_lowercase).as_deref() { Some("0x") => 16, Some("0b") => 2, Some("0o") => 8, _ => 10 };
+        if radix != 10
+            {
+            index += 2;
+            while index < chars.len() && chars[index].is_digit(radix) { index += 1; }
+            let digits: String = chars[start + 2 .. index].iter().collect();
+            Token::Number(i64::from_str_radix(&digits, radix).map_err(|_| invalid(start, &chars[start .. index]))? as f64)
+            }
+        else
+            {
+            while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') { index += 1; }
+            if index < chars.len() && (chars[index] == 'e' || chars[index] == 'E')
+                {
+                let sign = usize::from(chars.get(index + 1).is_some_and(|sign| *sign == '+' || *sign == '-'));
+                if chars.get(index + 1 + sign).is_some_and(char::is_ascii_digit)
+                    {
+                    index += 1 + sign;
+                    while index < chars.len() && chars[index].is_ascii_digit() { index += 1; }
+                    }
+                }
+            let number: String = chars[start .. index].iter().collect();
+            Token::Number(number.parse().map_err(|_| invalid(start, &chars[start .. index]))?)
+            }
+        }
+    else if c.is_alphabetic() || c == '_'
+        {
+        while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') { index += 1; }
+        Token::Name(chars[start .. index].iter().collect())
+        }
+    else if c == '[' || c == '#'
+        {
+        let close = if c == '[' { ']' } else { '#' };
+        let Some(length) = chars[index + 1 ..].iter().position(|found| *found == close)
+        else { return Err(CalcError { message: format!("the '{}' at column {} is not closed", c, start + 1), help: None }); };
+        index += length + 2;
+        let name: String = chars[start + 1 .. index - 1].iter().collect();
+        if c == '[' { Token::Section(name) } else { Token::Variable(name) }
+        }
+    else
+        {
+        let Some(operator) = OPERATORS.iter().find(|operator| rest.starts_with(**operator))
+        else { return Err(CalcError { message: format!("unexpected '{}' at column {}", c, start + 1), help: None }); };
+        index += operator.chars().count();
+        Token::Operator(operator)
+        };
+    tokens.push((token, start + 1, chars[start .. index].iter().collect()));
+    }
+Ok(tokens)
+}
+
+fn invalid(start: usize, chars: &[char]) -> CalcError
+{
+CalcError { message: format!("invalid number '{}' at column {}", chars.iter().collect::<String>(), start + 1), help: None }
+}
+
+fn is_constant(name: &str) -> bool
+{
+CALC_CONSTANTS.iter().any(|constant| constant.eq_ignore_ascii_case(name))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  formula_warnings   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file           `** the arise file, named for sections without a known origin   
+/// **`            `** **` skin           `** the evolved skin   
+/// **`            `** **` origins        `** where the sections come from, in the order they were evolved   
+/// **`RETURNS:    `** **` Vec            `** a warning for every formula which does not parse, and for every division by   
+/// **`            `** **`                `** something which can be 0, one per template line   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Checked are `Formula` of Calc measures and `IfCondition`, `IfCondition2`, ... of all measures. Measures which do   
+/// not exist are reported by the check of the references.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | only the formulas of measures, Formula only in Calc measures   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn formula_warnings(file: &Path, skin: &IniDocument, origins: &[SectionOrigin]) -> Vec<Diagnostic>
+{
+let located = locate_origins(skin, origins);
+let mut warnings = KeyWarnings::new();
+for (position, section) in skin.sections.iter().enumerate()
+    {
+    let Some(kind) = section.keys().find(|key| key.name.eq_ignore_ascii_case("Measure")) else { continue; };
+    let calc = kind.value.trim().eq_ignore_ascii_case("Calc");
+    for key in section.keys().filter(|key| is_formula_key(&key.name) && (calc || !key.name.eq_ignore_ascii_case("Formula")) && !key.value.trim().is_empty())
+        {
+        match parse_formula(&key.value)
+            {
+            Err(why) =>
+                warnings.add(located[position], &key.name, 0, &section.name,
+                             format!("{} in {} of section [{}]", why.message, key.name, section.name),
+                             why.help.unwrap_or_else(|| format!("{}={}", key.name, key.value.trim()))),
+            Ok(formula) =>
+                {
+                let mut divisions = Vec::new();
+                zero_divisions(&formula, skin, &BTreeSet::new(), &mut divisions);
+                for (nth, (divisor, reason)) in divisions.into_iter().enumerate()
+                    {
+                    let cause = match divisor { Formula::Name(name) | Formula::Section(name) => format!("{} can be 0", name),
+                                                _                                            => format!("'{}' is 0 if {}", display(&divisor), reason) };
+                    warnings.add(located[position], &key.name, nth, &section.name,
+                                 format!("possible division by zero in {} of section [{}]", key.name, section.name),
+                                 format!("{}; guard it like (X = 0 ? 0 : .../X)", cause));
+                    }
+                },
+            }
+        }
+    }
+
+let warnings = warnings.into_diagnostics(file);
+debug!("checked the formulas of {} sections, {} warning(s)", skin.sections.len(), warnings.len());
+warnings
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  is_formula_key   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name           `** the name of a key   
+/// **`RETURNS:    `** **` bool           `** true for `Formula` and `IfCondition`, `IfCondition2`, ...   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn is_formula_key(name: &str) -> bool
+{
+let name = name.to_lowercase();
+name == "formula" || name.strip_prefix("ifcondition").is_some_and(|number| number.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  zero_divisions / zero_reason   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The divisors (of `/` and `%`) which can be 0, with the reason. A measure can be 0, unless it has a `MinValue`   
+/// above 0 or it is a Calc measure whose formula cannot be 0; names tested for 0 by a condition are not 0 in the   
+/// branch where the test says so (`X = 0 ? 0 : 1/X`, `X > 0 ? 1/X : 0`). The reasons are a guess on the safe side:   
+/// a sum is only 0 if all of its parts can be, a difference always can.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn zero_divisions(formula: &Formula, skin: &IniDocument, not_zero: &BTreeSet<String>, found: &mut Vec<(Formula, String)>)
+{
+match formula
+    {
+    Formula::Binary(operator, left, right) =>
+        {
+        zero_divisions(left, skin, not_zero, found);
+        zero_divisions(right, skin, not_zero, found);
+        if *operator == "/" || *operator == "%"
+            {
+            if let Some(reason) = zero_reason(right, skin, not_zero, 0) { found.push(((**right).clone(), reason)); }
+            }
+        },
+    Formula::Condition(test, then, other) =>
+        {
+        zero_divisions(test, skin, not_zero, found);
+        let (zero_in_then, zero_in_other) = tested_names(test);
+        let mut then_known  = not_zero.clone();
+        let mut other_known = not_zero.clone();
+        then_known.extend(zero_in_other);
+        other_known.extend(zero_in_then);
+        zero_divisions(then, skin, &then_known, found);
+        zero_divisions(other, skin, &other_known, found);
+        },
+    Formula::Unary(_, inner) => zero_divisions(inner, skin, not_zero, found),
+    Formula::Call(_, args)   => for arg in args { zero_divisions(arg, skin, not_zero, found); },
+    _                        => {},
+    }
+}
+
+fn zero_reason(formula: &Formula, skin: &IniDocument, not_zero: &BTreeSet<String>, depth: usize) -> Option<String>
+{
+if depth > 8 { return None; }
+match formula
+    {
+    Formula::Number(number)                       => (*number == 0.0).then(|| "it is always 0".to_string()),
+    Formula::Name(name) | Formula::Section(name)  =>
+        {
+        if is_constant(name) || not_zero.contains(&name.to_lowercase()) { return None; }
+        let measure = measure_section(skin, name)?;
+        let value_of = |key: &str| measure.keys().find(|found| found.name.eq_ignore_ascii_case(key)).map(|found| found.value.trim().to_string());
+        if value_of("MinValue").and_then(|min| min.parse::<f64>().ok()).is_some_and(|min| min > 0.0) { return None; }
+        if value_of("Measure").is_some_and(|kind| kind.eq_ignore_ascii_case("Calc"))
+            {
+            let formula = parse_formula(&value_of("Formula").unwrap_or_default()).ok()?;
+            return zero_reason(&formula, skin, &BTreeSet::new(), depth + 1).map(|_| format!("{} is 0", name));
+            }
+        Some(format!("{} is 0", name))
+        },
+    Formula::Variable(_)                          => None,
+    Formula::Unary("-", inner) | Formula::Unary("+", inner) => zero_reason(inner, skin, not_zero, depth + 1),
+    Formula::Binary("+", left, right)             =>
+        {
+        let left = zero_reason(left, skin, not_zero, depth + 1)?;
+        let right = zero_reason(right, skin, not_zero, depth + 1)?;
+        Some(format!("{} and {}", left, right))
+        },
+    Formula::Binary("*", left, right)             => zero_reason(left, skin, not_zero, depth + 1).or_else(|| zero_reason(right, skin, not_zero, depth + 1)),
+    Formula::Binary("/", left, _) | Formula::Binary("^", left, _) => zero_reason(left, skin, not_zero, depth + 1),
+    Formula::Condition(_, then, other)            => zero_reason(then, skin, not_zero, depth + 1).or_else(|| zero_reason(other, skin, not_zero, depth + 1)),
+    Formula::Call(name, args) if ["Abs", "Sqrt"].iter().any(|known| known.eq_ignore_ascii_case(name)) => zero_reason(&args[0], skin, not_zero, depth + 1),
+    Formula::Call(name, args) if name.eq_ignore_ascii_case("Max") =>
+        {
+        let left = zero_reason(&args[0], skin, not_zero, depth + 1)?;
+        zero_reason(&args[1], skin, not_zero, depth + 1).map(|right| format!("{} and {}", left, right))
+        },
+    Formula::Call(name, _) if name.eq_ignore_ascii_case("Exp") => None,
+    _                                             => Some(format!("'{}' is 0", display(formula))),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  tested_names / measure_section / display   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The names a condition tests for 0: those which are 0 in its then-branch (`X = 0`, `X <= 0`, `!X`) and those which   
+/// are 0 in its else-branch (`X > 0`, `X != 0`, `X <> 0`, `X`); the section of a measure; a formula as text.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn tested_names(test: &Formula) -> (Vec<String>, Vec<String>)
+{
+let name_of = |formula: &Formula| match formula { Formula::Name(name) | Formula::Section(name) => Some(name.to_lowercase()), _ => None };
+match test
+    {
+    Formula::Binary(operator, left, right) if **right == Formula::Number(0.0) =>
+        {
+        let Some(name) = name_of(left) else { return (Vec::new(), Vec::new()); };
+        match *operator
+            {
+            "=" | "==" | "<=" => (vec![name], Vec::new()),
+            "!=" | "<>" | ">" => (Vec::new(), vec![name]),
+            _                 => (Vec::new(), Vec::new()),
+            }
+        },
+    Formula::Unary("!", inner) => { let (then, other) = tested_names(inner); (other, then) },
+    _                          => (Vec::new(), name_of(test).into_iter().collect()),
+    }
+}
+
+fn measure_section<'a>(skin: &'a IniDocument, name: &str) -> Option<&'a IniSection>
+{
+skin.sections.iter().find(|section| section.name.eq_ignore_ascii_case(name) && section.keys().any(|key| key.name.eq_ignore_ascii_case("Measure")))
+}
+
+fn display(formula: &Formula) -> String
+{
+match formula
+    {
+    Formula::Number(number)                 => format!("{}", number),
+    Formula::Name(name)                     => name.clone(),
+    Formula::Section(name)                  => format!("[{}]", name),
+    Formula::Variable(name)                 => format!("#{}#", name),
+    Formula::Unary(operator, inner)         => format!("{}{}", operator, display(inner)),
+    Formula::Binary(operator, left, right)  => format!("{} {} {}", display(left), operator, display(right)),
+    Formula::Condition(test, then, other)   => format!("({} ? {} : {})", display(test), display(then), display(other)),
+    Formula::Call(name, args)               => format!("{}({})", name, args.iter().map(display).collect::<Vec<String>>().join(", ")),
+    }
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for calc   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  use std::path::PathBuf;
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** formulas_are_parsed()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks precedence, the names of measures, and the errors of broken formulas   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn formulas_are_parsed()
+  {
+  let formula = parse_formula("100/(Total / Used) + -2^2 * Round(Pi, 2)").unwrap();
+  assert_eq!(display(&formula), "100 / Total / Used + -2 ^ 2 * Round(Pi, 2)");
+  assert_eq!(formula.names(), ["Total", "Used"]);
+  assert!(matches!(parse_formula("A = 0 ? 0xFF : 500e9 % #Width#").unwrap(), Formula::Condition(..)));
+
+  let error = |text: &str| parse_formula(text).unwrap_err().message;
+  assert_eq!(error("100/(Total / Used"), "the '(' at column 5 is not closed");
+  assert_eq!(error("(Total))"), "the ')' at column 8 has no '('");
+  assert_eq!(error("Total * / Used"), "unexpected '/' at column 9, a value is missing");
+  assert_eq!(error("Total Used"), "unexpected 'Used' at column 7, an operator is missing");
+  assert_eq!(error("Sqr(4)"), "unknown function 'Sqr' at column 1");
+  assert_eq!(error("Max(1)"), "Max at column 1 has 1 argument(s)");
+  assert_eq!(error("1 +"), "the formula ends where a value is expected");
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** divisions_by_zero_are_found()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that divisions by measures which can be 0 are reported, and guarded ones are not, and   
+  /// **`            `** that a Formula outside of Calc measures and the keys of meters are not checked   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn divisions_by_zero_are_found()
+  {
+  let skin = IniDocument::parse("[Total]\nMeasure=FreeDiskSpace\nTotal=1\n[Used]\nMeasure=FreeDiskSpace\n[Speed]\nMeasure=CPU\nMinValue=1\nFormula=1/(\n[Meter]\nMeter=String\nIfCondition=(\n\
+                                 [Percent]\nMeasure=Calc\nFormula=100/(Total / Used)\n\
+                                 [Guarded]\nMeasure=Calc\nFormula=(Used = 0 ? 0 : Total / Used) + 1/Speed + 1/Max(Used, 1)\nIfCondition=Percent > (50\n");
+
+  let warnings = formula_warnings(&PathBuf::from("t.arise"), &skin, &[]);
+  let messages: Vec<&str> = warnings.iter().map(|warning| warning.message.as_str()).collect();
+  assert_eq!(messages, ["possible division by zero in Formula of section [Percent]",
+                        "possible division by zero in Formula of section [Percent]",
+                        "the '(' at column 11 is not closed in IfCondition of section [Guarded]"]);
+  assert_eq!(warnings[0].help.as_deref(), Some("Used can be 0; guard it like (X = 0 ? 0 : .../X)"));
+  assert_eq!(warnings[1].help.as_deref(), Some("'Total / Used' is 0 if Total is 0; guard it like (X = 0 ? 0 : .../X)"));
+  }
+
+} // End of: mod test
@@ -0,0 +1,470 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** colors.rs 🦀   
+//! **`DESCRIPTION:`** Rainmeter colors and the functions deriving colors from other colors   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | gradients, in RGB or HSL, with any number of stops   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! ACCENT := 0,160,255                                                 <-- or 00A0FF, or #00A0FF in expressions   
+//!   
+//! SolidColor=<:= ACCENT:>                     0,160,255   
+//! SolidColor=<:= lighten(ACCENT, 20):>        102,198,255             20 percent points more lightness   
+//! SolidColor=<:= darken(ACCENT, 20):>         0,96,153                20 percent points less lightness   
+//! SolidColor=<:= alpha(ACCENT, 128):>         0,160,255,128           the alpha channel replaced, 0 to 255   
+//! SolidColor=<:= mix(ACCENT, #FF0000, 0.5):>  128,80,128              half way from the first to the second color   
+//! SolidColor=<:= to_hex(ACCENT):>             00A0FF                  Rainmeter's hex notation   
+//! SolidColor=<:= to_rgb(#00A0FF80):>          0,160,255,128           Rainmeter's r,g,b[,a] notation   
+//!   
+//! SolidColor=<:= gradient(#00ff00, #ff0000, #index, #count):>             from green for the first item to red   
+//! SolidColor=<:= gradient_hsl(#00ff00, #ff0000, #index, #count):>         the same, through yellow instead of brown   
+//! SolidColor=<:= gradient(#00ff00, #ffff00, #ff0000, #index, #count):>    with a stop in the middle   
+//!```
+//! The stops of a gradient are spread evenly from the first item (`#index` 0) to the last one (`#count` - 1), so the   
+//! first and last item always get the first and last stop, however many items the multiplier list has.   
+//! Derived colors are written in the notation of the (first) color they are derived from, the alpha channel only   
+//! if it is not 255. So a whole palette follows the notation of its base color.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::fmt;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::expression::Value;
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const FUNCTION_LIGHTEN : &str = "lighten";
+pub(crate) const FUNCTION_DARKEN  : &str = "darken";
+pub(crate) const FUNCTION_ALPHA   : &str = "alpha";
+pub(crate) const FUNCTION_MIX     : &str = "mix";
+pub(crate) const FUNCTION_TO_HEX  : &str = "to_hex";
+pub(crate) const FUNCTION_TO_RGB  : &str = "to_rgb";
+
+pub(crate) const FUNCTION_GRADIENT     : &str = "gradient";        // interpolates the RGB channels
+pub(crate) const FUNCTION_GRADIENT_HSL : &str = "gradient_hsl";    // interpolates hue, saturation and lightness
+pub(crate) const GRADIENT_FUNCTIONS    : [&str; 2] = [FUNCTION_GRADIENT, FUNCTION_GRADIENT_HSL];
+
+/// Every color function with the kinds of its arguments.   
+pub(crate) const COLOR_FUNCTIONS: [(&str, &[ColorArgument]); 6] =
+    [
+    (FUNCTION_LIGHTEN, &[ColorArgument::Color, ColorArgument::Number]),
+    (FUNCTION_DARKEN,  &[ColorArgument::Color, ColorArgument::Number]),
+    (FUNCTION_ALPHA,   &[ColorArgument::Color, ColorArgument::Number]),
+    (FUNCTION_MIX,     &[ColorArgument::Color, ColorArgument::Color, ColorArgument::Number]),
+    (FUNCTION_TO_HEX,  &[ColorArgument::Color]),
+    (FUNCTION_TO_RGB,  &[ColorArgument::Color]),
+    ];
+
+pub(crate) const HEX_PREFIX : char = '#';       // marks hex colors in expressions, optional in values
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// The notation a color is written in.   
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Notation
+{
+    #[default]
+    Rgb,        // 255,0,0 or 255,0,0,128
+    Hex,        // FF0000 or FF000080
+}
+
+/// The kind of an argument of a color function.   
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorArgument
+{
+    Color,
+    Number,
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// A Rainmeter color, remembering the notation it was written in.   
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Color
+{
+    pub(crate) red:      u8,
+    pub(crate) green:    u8,
+    pub(crate) blue:     u8,
+    pub(crate) alpha:    u8,
+    pub(crate) notation: Notation,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl Color
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  parse   
+/// **`TYPE:       `**  method of Color   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text         `** a color like `255,0,0`, `255,0,0,128`, `FF0000`, `FF000080` or `#FF0000`   
+/// **`RETURNS:    `** **` Option       `** the color, None if the text is no color   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse(text: &str) -> Option<Color>
+    {
+        let text = text.trim();
+        if text.contains(',')
+            {
+            let channels = text.split(',').map(|part| part.trim().parse::<u8>().ok()).collect::<Option<Vec<u8>>>()?;
+            return match channels[..]
+                {
+                [red, green, blue]        => Some(Color { red, green, blue, alpha: 255, notation: Notation::Rgb }),
+                [red, green, blue, alpha] => Some(Color { red, green, blue, alpha, notation: Notation::Rgb }),
+                _                         => None,
+                };
+            }
+
+        let digits = text.strip_prefix(HEX_PREFIX).unwrap_or(text);
+        if !(digits.len() == 6 || digits.len() == 8) || !digits.chars().all(|c| c.is_ascii_hexdigit()) { return None; }
+        let channel = |index: usize| u8::from_str_radix(&digits[index * 2 .. index * 2 + 2], 16).ok();
+        Some(Color { red: channel(0)?, green: channel(1)?, blue: channel(2)?, alpha: if digits.len() == 8 { channel(3)? } else { 255 }, notation: Notation::Hex })
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  to_hsl / with_hsl   
+/// **`TYPE:       `**  methods of Color   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Converts into hue (0 to 360), saturation and lightness (0 to 1), or back, keeping alpha and notation.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn to_hsl(self) -> (f64, f64, f64)
+    {
+        let (r, g, b) = (self.red as f64 / 255.0, self.green as f64 / 255.0, self.blue as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        if max == min { return (0.0, 0.0, lightness); }
+
+        let delta = max - min;
+        let saturation = if lightness > 0.5 { delta / (2.0 - max - min) } else { delta / (max + min) };
+        let hue = if max == r      { (g - b) / delta + if g < b { 6.0 } else { 0.0 } }
+                  else if max == g { (b - r) / delta + 2.0 }
+                  else             { (r - g) / delta + 4.0 };
+        (hue * 60.0, saturation, lightness)
+    }
+
+pub(crate) fn with_hsl(self, hue: f64, saturation: f64, lightness: f64) -> Color
+    {
+        let hue = hue.rem_euclid(360.0) / 360.0;
+        let (saturation, lightness) = (saturation.clamp(0.0, 1.0), lightness.clamp(0.0, 1.0));
+        let channel = |value: f64| (value * 255.0).round() as u8;
+        if saturation == 0.0
+            {
+            return Color { red: channel(lightness), green: channel(lightness), blue: channel(lightness), ..self };
+            }
+
+        let q = if lightness < 0.5 { lightness * (1.0 + saturation) } else { lightness + saturation - lightness * saturation };
+        let p = 2.0 * lightness - q;
+        let component = |t: f64|
+            {
+            let t = t.rem_euclid(1.0);
+            if t < 1.0 / 6.0      { p + (q - p) * 6.0 * t }
+            else if t < 0.5       { q }
+            else if t < 2.0 / 3.0 { p + (q - p) * (2.0 / 3.0 - t) * 6.0 }
+            else                  { p }
+            };
+        Color { red: channel(component(hue + 1.0 / 3.0)), green: channel(component(hue)), blue: channel(component(hue - 1.0 / 3.0)), ..self }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  mix   
+/// **`TYPE:       `**  method of Color   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` other        `** the color to mix in   
+/// **`            `** **` weight       `** 0: only this color, 1: only the other one   
+/// **`RETURNS:    `** **` Color        `** all channels interpolated, in the notation of this color   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn mix(self, other: Color, weight: f64) -> Color
+    {
+        let between = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * weight).round().clamp(0.0, 255.0) as u8;
+        Color { red: between(self.red, other.red), green: between(self.green, other.green), blue: between(self.blue, other.blue), alpha: between(self.alpha, other.alpha), ..self }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  to_rgb / to_hex   
+/// **`TYPE:       `**  methods of Color   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Writes the color in Rainmeter's notations, `r,g,b[,a]` or `RRGGBB[AA]`, the alpha only if it is not 255.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn to_rgb(self) -> String
+    {
+        if self.alpha == 255 { format!("{},{},{}", self.red, self.green, self.blue) }
+        else                 { format!("{},{},{},{}", self.red, self.green, self.blue, self.alpha) }
+    }
+
+pub(crate) fn to_hex(self) -> String
+    {
+        if self.alpha == 255 { format!("{:02X}{:02X}{:02X}", self.red, self.green, self.blue) }
+        else                 { format!("{:02X}{:02X}{:02X}{:02X}", self.red, self.green, self.blue, self.alpha) }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  mix_hsl   
+/// **`TYPE:       `**  method of Color   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` other        `** the color to mix in   
+/// **`            `** **` weight       `** 0: only this color, 1: only the other one   
+/// **`RETURNS:    `** **` Color        `** hue, saturation, lightness and alpha interpolated, in the notation of this color   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The hue takes the shorter way around the color wheel. Greys have no hue of their own, they take the other one.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn mix_hsl(self, other: Color, weight: f64) -> Color
+    {
+        let (mut hue, saturation, lightness) = self.to_hsl();
+        let (mut other_hue, other_saturation, other_lightness) = other.to_hsl();
+        if saturation == 0.0       { hue = other_hue; }
+        if other_saturation == 0.0 { other_hue = hue; }
+
+        let turn = (other_hue - hue + 540.0).rem_euclid(360.0) - 180.0;
+        let between = |a: f64, b: f64| a + (b - a) * weight;
+        let alpha = between(self.alpha as f64, other.alpha as f64).round().clamp(0.0, 255.0) as u8;
+        Color { alpha, ..self.with_hsl(hue + turn * weight, between(saturation, other_saturation), between(lightness, other_lightness)) }
+    }
+
+} // End of impl: Color
+
+
+impl fmt::Display for Color
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self.notation
+            {
+            Notation::Rgb => write!(f, "{}", self.to_rgb()),
+            Notation::Hex => write!(f, "{}", self.to_hex()),
+            }
+    }
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  call_color_function   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name           `** name of one of the COLOR_FUNCTIONS   
+/// **`            `** **` arguments      `** the evaluated arguments   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the derived color, or the color as text for to_hex and to_rgb)   
+/// **`            `** **`     or -->     `** - Error(message), for wrong arguments   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn call_color_function(name: &str, arguments: &[Value]) -> Result<Value, String>
+{
+let kinds = match COLOR_FUNCTIONS.iter().find(|(known, _)| *known == name)
+    {
+    Some((_, kinds)) => kinds,
+    None             => return Err(format!("unknown function '{}'", name)),
+    };
+if arguments.len() != kinds.len() { return Err(format!("'{}' takes {} arguments, got {}", name, kinds.len(), arguments.len())); }
+
+let mut colors  = Vec::new();
+let mut numbers = Vec::new();
+for (position, (argument, kind)) in arguments.iter().zip(kinds.iter()).enumerate()
+    {
+    match (kind, argument)
+        {
+        (ColorArgument::Color, Value::Color(color))                      => colors.push(*color),
+        (ColorArgument::Color, Value::Text(text)) if Color::parse(text).is_some() => colors.push(Color::parse(text).unwrap_or_default()),
+        (ColorArgument::Number, value) if value.as_number().is_some()   => numbers.push(value.as_number().unwrap_or_default()),
+        (ColorArgument::Color, other)  => return Err(format!("argument {} of '{}' has to be a color, got '{}'", position + 1, name, other)),
+        (ColorArgument::Number, other) => return Err(format!("argument {} of '{}' has to be a number, got '{}'", position + 1, name, other)),
+        }
+    }
+
+let color = colors[0];
+match name
+    {
+    FUNCTION_LIGHTEN | FUNCTION_DARKEN =>
+        {
+        let (hue, saturation, lightness) = color.to_hsl();
+        let amount = if name == FUNCTION_LIGHTEN { numbers[0] } else { -numbers[0] };
+        Ok(Value::Color(color.with_hsl(hue, saturation, lightness + amount / 100.0)))
+        },
+    FUNCTION_ALPHA if (0.0 ..= 255.0).contains(&numbers[0]) => Ok(Value::Color(Color { alpha: numbers[0].round() as u8, ..color })),
+    FUNCTION_ALPHA                                           => Err(format!("the alpha of '{}' has to be 0 to 255, got {}", name, numbers[0])),
+    FUNCTION_MIX if (0.0 ..= 1.0).contains(&numbers[0])     => Ok(Value::Color(color.mix(colors[1], numbers[0]))),
+    FUNCTION_MIX                                             => Err(format!("the weight of '{}' has to be 0 to 1, got {}", name, numbers[0])),
+    FUNCTION_TO_HEX                                          => Ok(Value::Text(color.to_hex())),
+    _                                                        => Ok(Value::Text(color.to_rgb())),
+    }
+}
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  call_gradient   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name           `** one of the GRADIENT_FUNCTIONS   
+/// **`            `** **` arguments      `** the evaluated arguments: two or more colors, the index and the count   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the color of the gradient at index, in the notation of the first stop)   
+/// **`            `** **`     or -->     `** - Error(message), for wrong arguments or an index out of the count   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn call_gradient(name: &str, arguments: &[Value]) -> Result<Value, String>
+{
+if arguments.len() < 4 { return Err(format!("'{}' takes two or more colors, the index and the count, got {} arguments", name, arguments.len())); }
+
+let (stops, position) = arguments.split_at(arguments.len() - 2);
+let stops = stops.iter().enumerate().map(|(position, stop)| match stop
+    {
+    Value::Color(color) => Ok(*color),
+    Value::Text(text)   => Color::parse(text).ok_or_else(|| format!("argument {} of '{}' has to be a color, got '{}'", position + 1, name, text)),
+    other               => Err(format!("argument {} of '{}' has to be a color, got '{}'", position + 1, name, other)),
+    }).collect::<Result<Vec<Color>, String>>()?;
+
+let whole = |value: &Value| value.as_number().filter(|number| number.fract() == 0.0 && *number >= 0.0);
+let (Some(index), Some(count)) = (whole(&position[0]), whole(&position[1]))
+    else { return Err(format!("the index and the count of '{}' have to be whole numbers, got '{}' and '{}'", name, position[0], position[1])); };
+if index >= count { return Err(format!("the index of '{}' has to be 0 to {}, got {}", name, count - 1.0, index)); }
+
+let at = if count > 1.0 { index / (count - 1.0) * (stops.len() - 1) as f64 } else { 0.0 };
+let segment = (at.floor() as usize).min(stops.len() - 2);
+let (from, to, weight) = (stops[segment], stops[segment + 1], at - segment as f64);
+let color = if name == FUNCTION_GRADIENT_HSL { from.mix_hsl(to, weight) } else { from.mix(to, weight) };
+Ok(Value::Color(Color { notation: stops[0].notation, ..color }))
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for colors   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  fn call(name: &str, arguments: &[&str]) -> Result<String, String>
+  {
+  let values: Vec<Value> = arguments.iter().map(|argument| Value::Text(argument.to_string())).collect();
+  let called = if GRADIENT_FUNCTIONS.contains(&name) { call_gradient(name, &values) } else { call_color_function(name, &values) };
+  called.map(|value| value.to_string())
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** colors_are_parsed_and_written()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks both notations, with and without alpha, and the conversion to HSL and back   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn colors_are_parsed_and_written()
+  {
+  assert_eq!(Color::parse("64, 64, 64, 128").map(|color| color.to_string()), Some("64,64,64,128".to_string()));
+  assert_eq!(Color::parse("#00a0ff").map(|color| color.to_string()), Some("00A0FF".to_string()));
+  assert_eq!(Color::parse("00A0FF80").map(|color| color.to_rgb()), Some("0,160,255,128".to_string()));
+  assert_eq!(Color::parse("256,0,0"), None);
+  assert_eq!(Color::parse("CDEF"), None);
+
+  for text in ["0,160,255", "12,34,56", "255,255,255", "200,10,10"]
+      {
+      let color = Color::parse(text).unwrap();
+      let (hue, saturation, lightness) = color.to_hsl();
+      assert_eq!(color.with_hsl(hue, saturation, lightness), color);
+      }
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** colors_are_derived()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the color functions, the notation of their results and wrong arguments   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn colors_are_derived()
+  {
+  assert_eq!(call(FUNCTION_LIGHTEN, &["0,160,255", "20"]), Ok("102,198,255".to_string()));
+  assert_eq!(call(FUNCTION_DARKEN, &["00A0FF", "20"]), Ok("006099".to_string()));
+  assert_eq!(call(FUNCTION_ALPHA, &["0,160,255", "128"]), Ok("0,160,255,128".to_string()));
+  assert_eq!(call(FUNCTION_MIX, &["0,160,255", "FF0000", "0.5"]), Ok("128,80,128".to_string()));
+  assert_eq!(call(FUNCTION_TO_HEX, &["0,160,255,128"]), Ok("00A0FF80".to_string()));
+  assert_eq!(call(FUNCTION_TO_RGB, &["#00A0FF"]), Ok("0,160,255".to_string()));
+
+  assert_eq!(call(FUNCTION_ALPHA, &["0,160,255", "300"]), Err("the alpha of 'alpha' has to be 0 to 255, got 300".to_string()));
+  assert_eq!(call(FUNCTION_MIX, &["0,160,255", "red", "0.5"]), Err("argument 2 of 'mix' has to be a color, got 'red'".to_string()));
+  assert_eq!(call(FUNCTION_LIGHTEN, &["0,160,255"]), Err("'lighten' takes 2 arguments, got 1".to_string()));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** gradients_are_spread_over_the_items()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks gradients in RGB and HSL, with several stops, for any count of items   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn gradients_are_spread_over_the_items()
+  {
+  let gradient = |name: &str, stops: &[&str], count: usize| -> Vec<String>
+      {
+      (0 .. count).map(|index|
+          {
+          let mut arguments: Vec<&str> = stops.to_vec();
+          let (index, count) = (index.to_string(), count.to_string());
+          arguments.extend([index.as_str(), count.as_str()]);
+          call(name, &arguments).unwrap()
+          }).collect()
+      };
+  assert_eq!(gradient(FUNCTION_GRADIENT, &["#00ff00", "#ff0000"], 3), vec!["00FF00", "808000", "FF0000"]);
+  assert_eq!(gradient(FUNCTION_GRADIENT_HSL, &["0,255,0", "255,0,0"], 3), vec!["0,255,0", "255,255,0", "255,0,0"]);
+  assert_eq!(gradient(FUNCTION_GRADIENT, &["0,255,0", "255,255,0", "255,0,0"], 5), vec!["0,255,0", "128,255,0", "255,255,0", "255,128,0", "255,0,0"]);
+  assert_eq!(gradient(FUNCTION_GRADIENT, &["0,255,0", "255,0,0"], 1), vec!["0,255,0"]);
+  assert_eq!(gradient(FUNCTION_GRADIENT_HSL, &["128,128,128", "255,0,0,0"], 2), vec!["128,128,128", "255,0,0,0"]);
+
+  assert_eq!(call(FUNCTION_GRADIENT, &["0,255,0", "255,0,0", "3", "3"]), Err("the index of 'gradient' has to be 0 to 2, got 3".to_string()));
+  assert_eq!(call(FUNCTION_GRADIENT, &["0,255,0", "3", "3"]), Err("'gradient' takes two or more colors, the index and the count, got 3 arguments".to_string()));
+  assert_eq!(call(FUNCTION_GRADIENT, &["0,255,0", "red", "1", "3"]), Err("argument 2 of 'gradient' has to be a color, got 'red'".to_string()));
+  }
+
+} // End of: mod test
@@ -41,13 +41,15 @@ use log::{trace, debug, info, warn, error};
 
 
 //___ CONSTANTS: ______________________________________________________________________________________________________________
-pub (crate) const ARISE_FILE_EXTENSION: &str = "arise";
-pub (crate) const SKIN_FILE_EXTENSION:  &str = "ini";
+pub (crate) const ARISE_FILE_EXTENSION:      &str = "arise";
+pub (crate) const SKIN_FILE_EXTENSION:       &str = "ini";
+pub (crate) const DEPENDENCY_FILE_EXTENSION: &str = "deps";   // lists the files a skin is evolved from, next to the skin
 
 // Default values for configuration:
 pub (crate) const DEFAULT_VERBOSITY:                   u8 = 0;
 pub (crate) const DEFAULT_DEBUG_MODE:                 bool = false;
 pub (crate) const DEFAULT_TEST_MODE:                  bool = false;
+pub (crate) const DEFAULT_STRICT_MODE:                bool = false;
 pub (crate) const DEFAULT_BASE_PATHPART:              &str = ".";
 pub (crate) const DEFAULT_RES_PATHPART:               &str = "resources";
 pub (crate) const DEFAULT_INP_PATHPART:               &str = "input";
@@ -74,6 +76,7 @@ pub(crate) struct AriseConfig
     pub(crate) verbosity:                  u8,
     pub(crate) debug:                      bool,
     pub(crate) test:                       bool,
+    pub(crate) strict:                     bool,          // warnings are errors, for CI builds
     pub(crate) base_pathpart:              PathBuf,
     pub(crate) res_pathpart:               PathBuf,
     pub(crate) inp_pathpart:               PathBuf,
@@ -91,7 +94,7 @@ pub(crate) struct AriseConfig
 //___ METHODS: ________________________________________________________________________________________________________________
 
 //-- Arise-Config -------------------------------------------
-impl <'lt_ariseconf> Default for AriseConfig
+impl Default for AriseConfig
 {
 /// ___________________________________________________________________________________________________________________________
 /// **`METHOD:     `**  default   
@@ -117,6 +120,7 @@ impl <'lt_ariseconf> Default for AriseConfig
         verbosity:                  DEFAULT_VERBOSITY,
         debug:                      DEFAULT_DEBUG_MODE,
         test:                       DEFAULT_TEST_MODE,
+        strict:                     DEFAULT_STRICT_MODE,
         base_pathpart:              PathBuf::from(DEFAULT_BASE_PATHPART),
         res_pathpart:               PathBuf::from(DEFAULT_RES_PATHPART),
         inp_pathpart:               PathBuf::from(DEFAULT_INP_PATHPART),
@@ -133,7 +137,7 @@ impl <'lt_ariseconf> Default for AriseConfig
 }
 
 //-- Arise-Config -------------------------------------------
-impl <'lt_ariseconf> AriseConfig 
+impl AriseConfig 
 {
 /// ___________________________________________________________________________________________________________________________
 /// **`METHOD:     `**  new   
@@ -160,6 +164,7 @@ fn new() -> AriseConfig
             verbosity:                  DEFAULT_VERBOSITY,
             debug:                      DEFAULT_DEBUG_MODE,
             test:                       DEFAULT_TEST_MODE,
+            strict:                     DEFAULT_STRICT_MODE,
             base_pathpart:              PathBuf::from(DEFAULT_BASE_PATHPART),
             res_pathpart:               PathBuf::from(DEFAULT_RES_PATHPART),
             inp_pathpart:               PathBuf::from(DEFAULT_INP_PATHPART),
@@ -186,8 +191,6 @@ mod tests
   use super::*;            // importing names from outer (for mod tests) scope
 
   use crate::config::AriseConfig;
-  #[allow(unused_imports)]
-
 
 
   /// ___________________________________________________________________________________________________________________________
@@ -203,6 +206,7 @@ mod tests
   assert_eq!(defaults.verbosity                  , DEFAULT_VERBOSITY);
   assert_eq!(defaults.debug                      , DEFAULT_DEBUG_MODE);
   assert_eq!(defaults.test                       , DEFAULT_TEST_MODE);
+  assert_eq!(defaults.strict                     , DEFAULT_STRICT_MODE);
   assert_eq!(defaults.base_pathpart              , PathBuf::from(DEFAULT_BASE_PATHPART));
   assert_eq!(defaults.res_pathpart               , PathBuf::from(DEFAULT_RES_PATHPART));
   assert_eq!(defaults.inp_pathpart               , PathBuf::from(DEFAULT_INP_PATHPART));
@@ -229,6 +233,7 @@ mod tests
     assert_eq!(a_conf.verbosity                  , DEFAULT_VERBOSITY);
     assert_eq!(a_conf.debug                      , DEFAULT_DEBUG_MODE);
     assert_eq!(a_conf.test                       , DEFAULT_TEST_MODE);
+    assert_eq!(a_conf.strict                     , DEFAULT_STRICT_MODE);
     assert_eq!(a_conf.base_pathpart              , PathBuf::from(DEFAULT_BASE_PATHPART));
     assert_eq!(a_conf.res_pathpart               , PathBuf::from(DEFAULT_RES_PATHPART));
     assert_eq!(a_conf.inp_pathpart               , PathBuf::from(DEFAULT_INP_PATHPART));
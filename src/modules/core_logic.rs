@@ -19,6 +19,18 @@
 //! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
 //! :---     | :---       | :---:     | :---   
 //! 0.1      | 2021-05-23 | Clunion   | creation
+//! 0.2      | 2026-10-19 | Clunion   | warnings for unused definitions, --strict turns them into errors   
+//! 0.3      | 2026-10-19 | Clunion   | type check of the template before evolving   
+//! 0.4      | 2026-10-19 | Clunion   | the skin is evolved into an INI document, written as text at the end   
+//! 0.5      | 2026-10-19 | Clunion   | evolve_skin for the import, frame, line ending and encoding of the skin   
+//! 0.6      | 2026-10-19 | Clunion   | warnings for duplicate sections and keys in the evolved skin   
+//! 0.7      | 2026-10-19 | Clunion   | warnings for unknown references and unused measures in the evolved skin   
+//! 0.8      | 2026-10-19 | Clunion   | warnings for the bangs in the actions of the evolved skin   
+//! 0.9      | 2026-10-19 | Clunion   | warnings for options and values the schema does not know   
+//! 0.10     | 2026-10-19 | Clunion   | keys renamed to the casing of the Rainmeter manual with ARISE_KEY_CASE := canonical   
+//! 0.11     | 2026-10-19 | Clunion   | warnings for broken formulas and possible divisions by zero   
+//! 0.12     | 2026-10-19 | Clunion   | the files a skin depends on are written into <skin>.deps next to it   
+//! 0.13     | 2026-10-19 | Clunion   | warnings for copies of a base section nobody inherits from   
 //! ___________________________________________________________________________________________________________________________
 //!# Examples
 //!```
@@ -32,6 +44,19 @@
 // mod modules;                              // <dirname>
 
 use crate::modules::config::*;            // crate::<filename>::*
+use crate::modules::template::*;          // crate::<filename>::*
+use crate::modules::expansion::{Scope, SectionOrigin, control_scope, bind_item, item_separator, parse_nodes, render_nodes};
+use crate::modules::inheritance::{resolve_inheritance, conflicting_bases};
+use crate::modules::diagnostics::Diagnostic;
+use crate::modules::usage::unused_definitions;
+use crate::modules::duplicates::duplicate_definitions;
+use crate::modules::references::cross_references;
+use crate::modules::bangs::bang_actions;
+use crate::modules::options::{option_warnings, canonical_keys};
+use crate::modules::calc::formula_warnings;
+use crate::modules::types::check_types;
+use crate::modules::ini::IniDocument;
+use crate::modules::ini_syntax::{LineEnding, encode_text};
 
 //___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
 
@@ -58,31 +83,7 @@ use log::{trace, debug, info, warn, error};
 
 
 //___ CONSTANTS: ______________________________________________________________________________________________________________
-const COMMENT_SINGLELINE      : &str = ";";
-const COMMENT_MULTILINE_BEGIN : &str = "/*";
-const COMMENT_MULTILINE_END   : &str = "*/";
-
-const OPERATOR_ASSIGN         : &str = ":=";
-const OPERATOR_PLUS           : &str = "+";
-const OPERATOR_MINUS          : &str = "-";
-
-const KEY_NAME_BEGIN          : &str = "<:";
-const KEY_NAME_END            : &str = ":>";
-
-const MULTIPLIER_LIST_BEGIN   : &str = "<multiplier list begin>";
-const MULTIPLIER_LIST_END     : &str = "<multiplier list end>";
-
-const OFFSET_VARIABLES_BEGIN  : &str = "<offset variables begin>";
-const OFFSET_VARIABLES_END    : &str = "<offset variables end>";
-
-const SECTION_HEADER_BEGIN    : &str = "<header begin>";
-const SECTION_HEADER_END      : &str = "<header end>";
-const SECTION_MEASURES_BEGIN  : &str = "<measures begin>";
-const SECTION_MEASURES_END    : &str = "<measures end>";
-const SECTION_METERS_BEGIN    : &str = "<meters begin>";
-const SECTION_METERS_END      : &str = "<meters end>";
-const SECTION_FOOTER_BEGIN    : &str = "<footer begin>";
-const SECTION_FOOTER_END      : &str = "<footer end>";
+//___ none ___
 
 //___ TYPES: __________________________________________________________________________________________________________________
 //___ none ___
@@ -124,7 +125,10 @@ struct AriseBucket
     section_footer_begin_cnt    : i32,
     section_footer_end_cnt      : i32,
     arise_in: String,  // will be shortened from the head      by each section-function (empty at end)
-    skin_out: String,  // will get newly evolved code appended by each section-function (empty at start)
+    skin_out: IniDocument,  // will get newly evolved sections appended by each section-function (empty at start)
+    template: AriseTemplate,  // the parsed generator definition, source of all section-functions
+    origins:  Vec<SectionOrigin>,  // where the sections of skin_out come from, for diagnostics
+    base_warnings: Vec<Diagnostic>,  // about the base sections, which are gone from skin_out after evolving
 }
 
 //___ METHODS: ________________________________________________________________________________________________________________
@@ -180,7 +184,10 @@ fn new() -> AriseBucket
         section_footer_begin_cnt   : 0,
         section_footer_end_cnt     : 0,
         arise_in: "uninitialized".to_string(), // ugly, todo: replace with Option (?)
-        skin_out: "uninitialized".to_string(), // ugly, todo: replace with Option (?)
+        skin_out: IniDocument::default(),
+        template: AriseTemplate::default(),
+        origins:  Vec::new(),
+        base_warnings: Vec::new(),
         }
     }
 
@@ -256,7 +263,7 @@ f.write_all(s.as_bytes())
 #[allow(dead_code)]
 pub(crate) fn touch(path: &Path) -> io::Result<()>
 {
-match OpenOptions::new().create(true).write(true).open(path)
+match OpenOptions::new().create(true).truncate(false).write(true).open(path)
     {
     Ok(_) => Ok(()),
     Err(e) => Err(e),
@@ -385,15 +392,15 @@ retval
 /// 1.1     | 2020-01-17 | Clunion   | changed: parameter to PathBuf reference, return-types to File and io::Error, added println outputs     
 /// ___________________________________________________________________________________________________________________________
 #[allow(dead_code)]
-pub(crate) fn open_file(file_name: &Path) -> Result<File, std::io::Error>
+pub(crate) fn open_file(file_name: &Path) -> Result<File, io::Error>
 {
-let _file = match File::open(file_name)
+match File::open(file_name)
     {
     Ok(f)    => { debug!("OK, file opened: {}", file_name.display());
-                  return  Ok(f)},
+                  Ok(f)},
     Err(error) => { error!("Error, couldn't open file '{}': {}", file_name.display(), error);
-                  return Err(error)},
-    };
+                  Err(error)},
+    }
 }
 
 /// ___________________________________________________________________________________________________________________________
@@ -412,7 +419,7 @@ let _file = match File::open(file_name)
 /// :---    | :---       | :---:     | :---   
 /// 1.0     | 2020-01-17 | Clunion   | created, initial version   
 /// ___________________________________________________________________________________________________________________________
-pub(crate) fn read_file_fully(file_path: &Path) -> Result<String, std::io::Error>
+pub(crate) fn read_file_fully(file_path: &Path) -> Result<String, io::Error>
 {
 let mut data = String::new();
 
@@ -441,6 +448,17 @@ match file.read_to_string(&mut data)
 /// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
 /// :---    | :---       | :---:     | :---   
 /// 1.0     | 2020-01-17 | Clunion   | created, initial version   
+/// 1.1     | 2026-10-19 | Clunion   | warnings for unused definitions, strict mode   
+/// 1.2     | 2026-10-19 | Clunion   | type check   
+/// 1.3     | 2026-10-19 | Clunion   | INI document   
+/// 1.4     | 2026-10-19 | Clunion   | evolving moved to evolve, line ending and encoding of the skin   
+/// 1.5     | 2026-10-19 | Clunion   | warnings for duplicate sections and keys   
+/// 1.6     | 2026-10-19 | Clunion   | warnings for unknown references and unused measures   
+/// 1.7     | 2026-10-19 | Clunion   | warnings for bangs   
+/// 1.8     | 2026-10-19 | Clunion   | warnings for options against the schema   
+/// 1.9     | 2026-10-19 | Clunion   | warnings for formulas   
+/// 1.10    | 2026-10-19 | Clunion   | the files the skin depends on are written next to it   
+/// 1.11    | 2026-10-19 | Clunion   | warnings for conflicting base sections   
 /// ___________________________________________________________________________________________________________________________
 //-> Result<AriseBucket, Box<dyn Error>>
 //pub(crate) fn core_logic(conf_p: &AriseConfig) -> Result<bool, io::Error>
@@ -482,35 +500,36 @@ arise.arise_in = match read_file_fully(&inp_full_filename)
     Ok(s_arise) => {debug!("Read file {} OK."           , inp_full_filename.display()); s_arise},
 };
 
-debug!("-----------------------------------------------------------");
-
-match build_metainfo(arise)
+arise.template = match parse_template(&inp_full_filename, &arise.arise_in)
 {
-    Err(why)           => {error!("couldn't evolve skin metadata: {}", why); return Err(why)}
-    Ok(arise_metainfo) => { arise = arise_metainfo }  // be careful here, it's tricky...
+    Err(why)     => {error!("couldn't parse {}:\n{}", inp_full_filename.display(), why); return Err(why)},
+    Ok(template) => template,
 };
-debug!("ok, lengths now: arise-in {:4}, skin-out {:4}", arise.arise_in.len(), arise.skin_out.len());
 
-match build_skin_header(arise)
-{
-    Err(why)           => {error!("couldn't evolve skin header: {}", why); return Err(why)}
-    Ok(arise_header)   => { arise = arise_header }  // be careful here, it's tricky...
-};
-debug!("ok, lengths now: arise-in {:4}, skin-out {:4}", arise.arise_in.len(), arise.skin_out.len());
+// definitions nobody uses are only warnings, in strict mode (for CI) they stop the evolving:
+report_warnings(&inp_full_filename, unused_definitions(&arise.template), conf_p.strict)?;
 
-match build_skin_body(arise)
-{
-    Err(why)           => {error!("couldn't evolve skin body: {}", why); return Err(why)}
-    Ok(arise_body)     => { arise = arise_body }  // be careful here, it's tricky...
-};
-debug!("ok, lengths now: arise-in {:4}, skin-out {:4}", arise.arise_in.len(), arise.skin_out.len());
+// values mixed with values of another type would give broken skins, so nothing is evolved then:
+let type_errors = check_types(&arise.template);
+if !type_errors.is_empty()
+    {
+    for why in &type_errors { error!("\n{}", why); }
+    return Err(Diagnostic::error(&inp_full_filename, 0, format!("{} type error(s), nothing was written", type_errors.len())).into());
+    }
 
-match build_skin_footer(arise)
-{
-    Err(why)           => {error!("couldn't evolve skin footer: {}", why); return Err(why)}
-    Ok(arise_footer)   => { arise = arise_footer }  // be careful here, it's tricky...
-};
-debug!("ok, lengths now: arise-in {:4}, skin-out {:4}", arise.arise_in.len(), arise.skin_out.len());
+debug!("-----------------------------------------------------------");
+
+arise = evolve(arise, &inp_full_filename)?;
+
+// Rainmeter silently ignores all but one of them, so they are reported before writing:
+report_warnings(&inp_full_filename, std::mem::take(&mut arise.base_warnings), conf_p.strict)?;
+report_warnings(&inp_full_filename, duplicate_definitions(&inp_full_filename, &arise.skin_out, &arise.origins), conf_p.strict)?;
+
+// a misspelled name is no error for Rainmeter, the meter just stays empty:
+report_warnings(&inp_full_filename, cross_references(&inp_full_filename, &arise.skin_out, &arise.origins), conf_p.strict)?;
+report_warnings(&inp_full_filename, bang_actions(&inp_full_filename, &arise.skin_out, &arise.origins), conf_p.strict)?;
+report_warnings(&inp_full_filename, option_warnings(&inp_full_filename, &arise.skin_out, &arise.origins), conf_p.strict)?;
+report_warnings(&inp_full_filename, formula_warnings(&inp_full_filename, &arise.skin_out, &arise.origins), conf_p.strict)?;
 
 
 debug!("-----------------------------------------------------------");
@@ -545,17 +564,161 @@ let mut file = match File::create(&out_full_filename)
     };
 
 // Write the full contents of generated rainmeter-ini-file to the skin-file, return io::Result<()> if successful
- match file.write_all(arise.skin_out.as_bytes()) 
+ match file.write_all(&skin_bytes(&arise)) 
+    {
+    Err(why) => {error!("couldn't write to {}: {}", out_full_filename.display(), why); return Err(why.into())}
+    Ok(_)    => {debug!("successfully wrote to {}", out_full_filename.display()); }
+    }
+
+// Every file listed here influences the generated skin, so a watcher or a cache has to regenerate it when one of them changes:
+let dependency_file = out_full_filename.with_extension(DEPENDENCY_FILE_EXTENSION);
+match echo(&dependency_list(&arise.template.dependencies), &dependency_file)
     {
-    Err(why) => {error!("couldn't write to {}: {}", out_full_filename.display(), why); Err(why.into())}
-    Ok(_)    => {debug!("successfully wrote to {}", out_full_filename.display());      Ok(true) }
+    Err(why) => {error!("couldn't write to {}: {}", dependency_file.display(), why); Err(why.into())}
+    Ok(_)    => {debug!("successfully wrote to {}", dependency_file.display());      Ok(true) }
     }
 
 // file -handle goes out of scope and the file gets closed.
 }
 
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  dependency_list   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` dependencies_p`** the arise file, its includes, bases and data files, as the template found them   
+/// **`RETURNS:    `** **` String        `** the content of the dependency file: every file once, one per line   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn dependency_list(dependencies_p: &[PathBuf]) -> String
+{
+let mut listed: Vec<&PathBuf> = Vec::new();
+let mut list = String::new();
+for dependency in dependencies_p
+    {
+    if listed.contains(&dependency) { continue; }
+    listed.push(dependency);
+    list.push_str(&format!("{}\n", dependency.display()));
+    }
+list
+}
+
 
  
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  evolve_skin   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file_p        `** path of the arise file, its data files are read relative to it   
+/// **`            `** **` text_p        `** the text of the arise file   
+/// **`RETURNS:    `** **` Result -->    `** - OK(the bytes of the evolved skin, as they would be written)   
+/// **`            `** **`     or -->    `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Evolves a template in memory, without the checks for warnings and types, e.g. to verify an imported template.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn evolve_skin(file_p: &Path, text_p: &str) -> Result<Vec<u8>, Box<dyn Error>>
+{
+let mut arise : AriseBucket = AriseBucket::new();
+arise.arise_in = text_p.to_string();
+arise.template = parse_template(file_p, text_p)?;
+arise = evolve(arise, file_p)?;
+Ok(skin_bytes(&arise))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  evolve / skin_bytes   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` arise_p       `** Arise-bucket with the parsed template   
+/// **`            `** **` file_p        `** the arise file, named in diagnostics   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Evolves all blocks into the INI document, resolves the section inheritance and, if asked for, renames the keys to   
+/// the casing of the Rainmeter manual; gives the bytes of the document   
+/// in the line ending and encoding the template asks for.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, moved here from core_logic   
+/// 1.1     | 2026-10-19 | Clunion   | keys in canonical case   
+/// 1.2     | 2026-10-19 | Clunion   | warnings for conflicting base sections   
+/// ___________________________________________________________________________________________________________________________
+fn evolve(mut arise_p: AriseBucket, file_p: &Path) -> Result<AriseBucket, Box<dyn Error>>
+{
+match build_metainfo(arise_p)
+{
+    Err(why)           => {error!("couldn't evolve skin metadata: {}", why); return Err(why)}
+    Ok(arise_metainfo) => { arise_p = arise_metainfo }  // be careful here, it's tricky...
+};
+debug!("ok, lengths now: arise-in {:4}, skin-out {:4} sections", arise_p.arise_in.len(), arise_p.skin_out.sections.len());
+
+match build_skin_blocks(arise_p)
+{
+    Err(why)           => {error!("couldn't evolve skin blocks: {}", why); return Err(why)}
+    Ok(arise_blocks)   => { arise_p = arise_blocks }  // be careful here, it's tricky...
+};
+debug!("ok, lengths now: arise-in {:4}, skin-out {:4} sections", arise_p.arise_in.len(), arise_p.skin_out.sections.len());
+
+// sections like [Measure_Drive_C_Free : DriveBase] get the keys of their base, the base sections are dropped,
+// so a copy of a base nobody inherits from is reported before:
+arise_p.base_warnings = conflicting_bases(file_p, &arise_p.skin_out, &arise_p.origins);
+arise_p.skin_out = match resolve_inheritance(arise_p.skin_out)
+{
+    Err(why)           => {let why = Diagnostic::error(file_p, 0, why); error!("couldn't resolve the section inheritance:\n{}", why); return Err(why.into())}
+    Ok(skin)           => skin,
+};
+
+if arise_p.template.key_case == KeyCase::Canonical { canonical_keys(&mut arise_p.skin_out); }
+Ok(arise_p)
+}
+
+fn skin_bytes(arise_p: &AriseBucket) -> Vec<u8>
+{
+let text = arise_p.skin_out.to_string();
+let text = match arise_p.template.line_ending
+    {
+    LineEnding::CrLf => text.replace('\n', "\r\n"),
+    _                => text,
+    };
+encode_text(&text, arise_p.template.encoding)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  report_warnings   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file_p        `** the arise file, named in the summary   
+/// **`            `** **` warnings_p    `** the warnings found   
+/// **`            `** **` strict_p      `** true: the warnings are reported as errors and evolving stops   
+/// **`RETURNS:    `** **` Result -->    `** - OK(), if there are no warnings or they are only warnings   
+/// **`            `** **`     or -->    `** - Error, in strict mode if there is any warning   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn report_warnings(file_p: &Path, warnings_p: Vec<Diagnostic>, strict_p: bool) -> Result<(), Box<dyn Error>>
+{
+if warnings_p.is_empty() { return Ok(()); }
+if !strict_p
+    {
+    for warning in &warnings_p { warn!("\n{}", warning); }
+    return Ok(());
+    }
+
+let count = warnings_p.len();
+for warning in warnings_p { error!("\n{}", warning.into_error()); }
+Err(Diagnostic::error(file_p, 0, format!("{} warning(s), treated as errors in strict mode", count))
+               .with_help("fix them, or run without --strict").into())
+}
+
 /// ___________________________________________________________________________________________________________________________
 /// **`FUNCTION:   `**  ``build_metainfo``   
 /// **`TYPE:       `**  local, common function   
@@ -571,8 +734,9 @@ let mut file = match File::create(&out_full_filename)
 /// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
 /// :---    | :---       | :---:     | :---   
 /// 1.0     | 2021-11-06 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | starts the INI document   
+/// 1.2     | 2026-10-19 | Clunion   | no metainfo with ARISE_FRAME := none   
 /// ___________________________________________________________________________________________________________________________
-
 fn build_metainfo(mut arise_p: AriseBucket) -> Result<AriseBucket, Box<dyn Error>>
 {
 let metainfo : String = "; -- Metainfo-Text --\n".to_owned();
@@ -609,13 +773,13 @@ for s_cur_line in s_arise_lines
 
     }
 
-arise_p.skin_out = metainfo;
+if arise_p.template.frame == SkinFrame::Comments { arise_p.skin_out = IniDocument::parse(&metainfo); }
 
 Ok(arise_p)
 }
 
 /// ___________________________________________________________________________________________________________________________
-/// **`FUNCTION:   `**  ``build_skin_header``   
+/// **`FUNCTION:   `**  ``build_skin_blocks``   
 /// **`TYPE:       `**  local, common function   
 /// ___________________________________________________________________________________________________________________________
 /// **`PARAMETER:  `** **` arise_p       `** Arise-bucket to be processed, contains (remainder of input and start of output)   
@@ -623,98 +787,111 @@ Ok(arise_p)
 /// **`            `** **`     or -->    `** - Error   
 /// ___________________________________________________________________________________________________________________________
 /// **`DESCRIPTION:`**   
-/// Processes the skin_header-section of the arise-input, evolves it into a part of the rainmeter-skin output.   
-/// The input gets shortened by the processed section, the output is extended with the generated Rainmeter-ini-code.
+/// Evolves all blocks of the arise-input in the order of the file, header, measures, meters and footer included.   
+/// The INI document is extended with the generated Rainmeter-ini-code, every block enclosed in Start/End comments.   
+/// With `ARISE_ORDER := interleaved` neighbouring blocks multiplied by the same list are evolved together, item by item.   
 /// ___________________________________________________________________________________________________________________________
 /// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
 /// :---    | :---       | :---:     | :---   
-/// 1.0     | 2021-11-06 | Clunion   | initial version   
-/// ___________________________________________________________________________________________________________________________
-
-fn build_skin_header(mut arise_p: AriseBucket) -> Result<AriseBucket, Box<dyn Error>>
+/// 1.0     | 2026-10-19 | Clunion   | initial version, replaces build_skin_header, build_skin_body and build_skin_footer   
+/// 1.1     | 2026-10-19 | Clunion   | grouped or interleaved order   
+/// 1.2     | 2026-10-19 | Clunion   | appends to the INI document   
+/// 1.3     | 2026-10-19 | Clunion   | without Start/End comments with ARISE_FRAME := none   
+/// 1.4     | 2026-10-19 | Clunion   | keeps the origins of the sections   
+/// 1.5     | 2026-10-19 | Clunion   | the evolved sections may name a base   
+/// ___________________________________________________________________________________________________________________________
+fn build_skin_blocks(mut arise_p: AriseBucket) -> Result<AriseBucket, Box<dyn Error>>
 {
-let header : String = 
-  "; --- Skin Header-Start ---\n".to_owned()
-+ "[Rainmeter]\n"
-+ "Update=10000\n"
-+ "\n"
-+ "[Metadata]\n"
-+ "Name=AriseWorld\n"
-+ "Author=clunion\n"
-+ "Information=Shows a \"Hello, World!\"-kind text display\n"
-+ "Version=0.1\n"
-+ "License=Creative Commons Attribution - Non - Commercial - Share Alike 3.0\n"
-+ "; --- Skin Header-End -----\n"
-+ "\n";
-
-arise_p.skin_out = format!("{}\n{}", arise_p.skin_out, header);
+let mut scope = control_scope(&arise_p.template);
+let sections  = &arise_p.template.sections;
 
-Ok(arise_p)
-}
+let mut start = 0;
+while start < sections.len()
+    {
+    let mut end = start + 1;
+    if arise_p.template.order == BlockOrder::Interleaved && sections[start].repeat.is_some()
+        {
+        while end < sections.len() && sections[end].repeat == sections[start].repeat { end += 1; }
+        }
+    let run = &sections[start .. end];
+    start = end;
 
-/// ___________________________________________________________________________________________________________________________
-/// **`FUNCTION:   `**  ``build_skin_body``   
-/// **`TYPE:       `**  local, common function   
-/// ___________________________________________________________________________________________________________________________
-/// **`PARAMETER:  `** **` arise_p       `** Arise-bucket to be processed, contains (remainder of input and start of output)   
-/// **`RETURNS:    `** **` Result -->    `** - OK(status flag: true = successful, false = failed)   
-/// **`            `** **`     or -->    `** - Error   
-/// ___________________________________________________________________________________________________________________________
-/// **`DESCRIPTION:`**   
-/// Processes the skin_body-section of the arise-input, evolves it into a part of the rainmeter-skin output.   
-/// The input gets shortened by the processed section, the output is extended with the generated Rainmeter-ini-code.
-/// ___________________________________________________________________________________________________________________________
-/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
-/// :---    | :---       | :---:     | :---   
-/// 1.0     | 2021-11-06 | Clunion   | initial version   
-/// ___________________________________________________________________________________________________________________________
+    let titles: Vec<String> = run.iter().map(|section|
+        {
+        let mut title: Vec<char> = section.name.chars().collect();
+        if let Some(first) = title.first_mut() { *first = first.to_ascii_uppercase(); }
+        title.into_iter().collect()
+        }).collect();
+    let title = titles.join(" & ");
+
+    let evolved = build_blocks(&arise_p.template, run, &mut scope)?;
+    if arise_p.template.frame == SkinFrame::None
+        {
+        arise_p.skin_out.push_template_text(&evolved);
+        continue;
+        }
 
-fn build_skin_body(mut arise_p: AriseBucket) -> Result<AriseBucket, Box<dyn Error>>
-{
-let body : String = 
-  "; --- Skin Body-Start ---\n".to_owned()
-+ "[SimpleMeter]\n"
-+ "Meter=String\n"
-+ "Text=\" Arise, World!\"\n"
-+ "AntiAlias=1\n"
-+ "FontSize=40\n"
-+ "FontFace=SegoeUI\n"
-+ "FontColor=200,220,255\n"
-+ "SolidColor=64,64,64,128,1\n"
-+ "; --- Skin Body-End -----\n"
-+ "\n";
-
-arise_p.skin_out = format!("{}\n{}", arise_p.skin_out, body);
+    arise_p.skin_out.push_line("");
+    arise_p.skin_out.push_line(&format!("; --- Skin {}-Start ---", title));
+    arise_p.skin_out.push_template_text(&evolved);
+    arise_p.skin_out.push_line(&format!("; --- Skin {}-End -----", title));
+    arise_p.skin_out.push_line("");
+    }
 
+arise_p.origins = scope.take_origins();
 Ok(arise_p)
 }
 
 /// ___________________________________________________________________________________________________________________________
-/// **`FUNCTION:   `**  ``build_skin_footer``   
+/// **`FUNCTION:   `**  ``build_blocks``   
 /// **`TYPE:       `**  local, common function   
 /// ___________________________________________________________________________________________________________________________
-/// **`PARAMETER:  `** **` arise_p       `** Arise-bucket to be processed, contains (remainder of input and start of output)   
-/// **`RETURNS:    `** **` Result -->    `** - OK(status flag: true = successful, false = failed)   
+/// **`PARAMETER:  `** **` template_p    `** the parsed arise template   
+/// **`            `** **` sections_p    `** the blocks to be evolved together, all multiplied by the same list (or by none)   
+/// **`            `** **` scope_p       `** the names known outside of the blocks   
+/// **`RETURNS:    `** **` Result -->    `** - OK(the evolved Rainmeter-ini-code, one copy of the blocks per item)   
 /// **`            `** **`     or -->    `** - Error   
 /// ___________________________________________________________________________________________________________________________
 /// **`DESCRIPTION:`**   
-/// Processes the skin_footer-section of the arise-input, evolves it into a part of the rainmeter-skin output.   
-/// The input gets shortened by the processed section, the output is extended with the generated Rainmeter-ini-code.
+/// Evolves the blocks once for every item of their multiplier list, each item preceded by a separator comment.   
+/// Without a multiplier list the blocks are evolved once.   
 /// ___________________________________________________________________________________________________________________________
 /// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
 /// :---    | :---       | :---:     | :---   
-/// 1.0     | 2021-11-06 | Clunion   | initial version   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, as build_multiplied_section   
+/// 1.1     | 2026-10-19 | Clunion   | every block has its own multiplier list   
+/// 1.2     | 2026-10-19 | Clunion   | several blocks at once, separator comments   
+/// 1.3     | 2026-10-19 | Clunion   | the item is added to the origins of its sections   
 /// ___________________________________________________________________________________________________________________________
-
-
-fn build_skin_footer(mut arise_p: AriseBucket) -> Result<AriseBucket, Box<dyn Error>>
+fn build_blocks(template_p: &AriseTemplate, sections_p: &[TemplateSection], scope_p: &mut Scope) -> Result<String, Box<dyn Error>>
 {
-let footer : String = 
-  "; --- Skin Footer-Start ---\n".to_owned()
-+ "; --- Skin Footer-End -----\n"
-+ "\n";
+let mut nodes = Vec::new();
+for section in sections_p
+    {
+    nodes.push(parse_nodes(&section.lines)?);
+    }
 
-arise_p.skin_out = format!("{}\n{}", arise_p.skin_out, footer);
+let mut evolved = String::new();
+let multiplier = match sections_p.first().and_then(|section| section.repeat.as_deref()).and_then(|name| template_p.multiplier(name))
+    {
+    Some(multiplier) => multiplier,
+    None             =>
+        {
+        for block in &nodes { evolved.push_str(&render_nodes(block, scope_p)?); }
+        return Ok(evolved);
+        },
+    };
 
-Ok(arise_p)
+for index in 0 .. multiplier.items.len()
+    {
+    bind_item(scope_p, template_p, multiplier, index);
+    let separator = item_separator(template_p, multiplier, scope_p);
+    let from = scope_p.origin_count();
+    let rendered: Result<Vec<String>, _> = nodes.iter().map(|block| render_nodes(block, scope_p)).collect();
+    scope_p.add_origin_item(from, &format!("{} '{}'", multiplier.name, multiplier.items[index].primary(&multiplier.name)));
+    scope_p.pop_frame();
+    evolved.push_str(&separator?);
+    evolved.push_str(&rendered?.concat());
+    }
+Ok(evolved)
 }
@@ -0,0 +1,435 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** data_source.rs 🦀   
+//! **`DESCRIPTION:`** loads the items of a multiplier list from an external data file (CSV, JSON or TOML)   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! <multiplier list begin>   
+//!     <:drive:> := @data("drives.csv")   
+//! <multiplier list end>   
+//!   
+//! drives.csv:                     drives.json:                           drives.toml:   
+//!     drive,label,removable           [ {"drive": "C", "label": "System"},    [[drive]]   
+//!     C,System,0                        {"drive": "D", "label": "Data"} ]     drive = "C"   
+//!     D,Data,1                                                                label = "System"   
+//!```
+//! Every row (CSV), object (JSON) or table (TOML) becomes one item of the multiplier list.   
+//! The placeholder `<:drive:>` is replaced by the field named like the multiplier (or by the first field, if there is   
+//! no such field), every other field is reachable as `<:drive.label:>`.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::error::Error;
+use std::path::Path;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::core_logic::read_file_fully;
+use crate::modules::diagnostics::Diagnostic;
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const DATA_SOURCE_KEYWORD : &str = "@data";
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DataFormat
+{
+    Csv,
+    Json,
+    Toml,
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// One item of a multiplier list: an ordered list of named fields.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct DataRecord
+{
+    pub(crate) fields: Vec<(String, String)>,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl DataRecord
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  single   
+/// **`TYPE:       `**  method of DataRecord   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** name of the only field   
+/// **`            `** **` value        `** value of the only field   
+/// **`RETURNS:    `** **` DataRecord   `** a record with exactly one field   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn single(name: &str, value: &str) -> DataRecord
+    {
+        DataRecord { fields: vec![(name.to_string(), value.to_string())] }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  get   
+/// **`TYPE:       `**  method of DataRecord   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** name of the field to look up   
+/// **`RETURNS:    `** **` Option       `** the value of the field, None if the record has no such field   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn get(&self, name: &str) -> Option<&str>
+    {
+        self.fields.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  primary   
+/// **`TYPE:       `**  method of DataRecord   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** name of the multiplier variable the record belongs to   
+/// **`RETURNS:    `** **` &str         `** the value the plain placeholder `<:name:>` gets replaced with   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The field named like the multiplier variable wins, otherwise the first field of the record is used.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn primary(&self, name: &str) -> &str
+    {
+        match self.get(name)
+            {
+            Some(value) => value,
+            None        => self.fields.first().map(|(_, value)| value.as_str()).unwrap_or(""),
+            }
+    }
+
+} // End of impl: DataRecord
+
+
+impl DataFormat
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  from_path   
+/// **`TYPE:       `**  method of DataFormat   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` path         `** path of the data file   
+/// **`RETURNS:    `** **` Option       `** the format derived from the file extension, None for unknown extensions   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn from_path(path: &Path) -> Option<DataFormat>
+    {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        match extension.as_str()
+            {
+            "csv"  => Some(DataFormat::Csv),
+            "json" => Some(DataFormat::Json),
+            "toml" => Some(DataFormat::Toml),
+            _      => None,
+            }
+    }
+
+} // End of impl: DataFormat
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  load_data_source   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` path           `** path of the data file, already resolved relative to the template   
+/// **`RETURNS:    `** **` Result -->     `** - OK(list of records, one per item of the multiplier list)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Reads a CSV (with header row), JSON (array of objects) or TOML (tables) file and converts it into data records.   
+/// The format is chosen by the file extension.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn load_data_source(path: &Path) -> Result<Vec<DataRecord>, Box<dyn Error>>
+{
+let format = match DataFormat::from_path(path)
+    {
+    Some(format) => format,
+    None         => return Err(Diagnostic::error(path, 0, "unknown format of data file")
+                                          .with_help("supported extensions are '.csv', '.json' and '.toml'").into()),
+    };
+
+let text = match read_file_fully(path)
+    {
+    Ok(text)   => text,
+    Err(error) => return Err(Diagnostic::error(path, 0, format!("couldn't read data file: {}", error)).into()),
+    };
+
+let records = match format
+    {
+    DataFormat::Csv  => parse_csv(&text),
+    DataFormat::Json => parse_json(&text),
+    DataFormat::Toml => parse_toml(&text),
+    };
+
+match records
+    {
+    Ok(records) => { debug!("loaded {} records from {}", records.len(), path.display()); Ok(records) },
+    Err(why)    => Err(Diagnostic::error(path, 0, why).into()),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_csv   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** content of a CSV file, the first row contains the field names   
+/// **`RETURNS:    `** **` Result -->     `** - OK(one record per data row)   
+/// **`            `** **`     or -->     `** - Error(message)   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_csv(text: &str) -> Result<Vec<DataRecord>, String>
+{
+let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(text.as_bytes());
+
+let headers: Vec<String> = match reader.headers()
+    {
+    Ok(headers) => headers.iter().map(|h| h.to_string()).collect(),
+    Err(why)    => return Err(format!("invalid CSV header row: {}", why)),
+    };
+
+let mut records = Vec::new();
+for row in reader.records()
+    {
+    let row = row.map_err(|why| format!("invalid CSV row: {}", why))?;
+    let fields = headers.iter().cloned().zip(row.iter().map(|value| value.to_string())).collect();
+    records.push(DataRecord { fields });
+    }
+Ok(records)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_json   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** content of a JSON file, expected to be an array of objects   
+/// **`RETURNS:    `** **` Result -->     `** - OK(one record per object)   
+/// **`            `** **`     or -->     `** - Error(message)   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// An array of plain values is accepted too, each value then becomes a record with the single field 'value'.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_json(text: &str) -> Result<Vec<DataRecord>, String>
+{
+let json: serde_json::Value = serde_json::from_str(text).map_err(|why| format!("invalid JSON: {}", why))?;
+
+let items = match json
+    {
+    serde_json::Value::Array(items) => items,
+    _                               => return Err("JSON data must be an array of objects".to_string()),
+    };
+
+let mut records = Vec::new();
+for item in items
+    {
+    match item
+        {
+        serde_json::Value::Object(object) => records.push(DataRecord { fields: object.iter().map(|(key, value)| (key.clone(), json_to_string(value))).collect() }),
+        other                             => records.push(DataRecord::single("value", &json_to_string(&other))),
+        }
+    }
+Ok(records)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_toml   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** content of a TOML file   
+/// **`RETURNS:    `** **` Result -->     `** - OK(one record per table)   
+/// **`            `** **`     or -->     `** - Error(message)   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Two layouts are understood:   
+/// * an array of tables (`[[drive]]`), each table becomes a record   
+/// * a set of named tables (`[C]`, `[D]`), each table becomes a record, its name is stored in the first field 'key'   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_toml(text: &str) -> Result<Vec<DataRecord>, String>
+{
+let toml: toml::Value = text.parse().map_err(|why| format!("invalid TOML: {}", why))?;
+
+let root = match toml
+    {
+    toml::Value::Table(root) => root,
+    _                        => return Err("TOML data must be a table".to_string()),
+    };
+
+// an array of tables like [[drive]]:
+if root.len() == 1
+    {
+    if let Some(toml::Value::Array(items)) = root.values().next()
+        {
+        let mut records = Vec::new();
+        for item in items
+            {
+            match item
+                {
+                toml::Value::Table(table) => records.push(DataRecord { fields: table.iter().map(|(key, value)| (key.clone(), toml_to_string(value))).collect() }),
+                other                     => records.push(DataRecord::single("value", &toml_to_string(other))),
+                }
+            }
+        return Ok(records);
+        }
+    }
+
+// a set of named tables like [C], [D]:
+let mut records = Vec::new();
+for (name, value) in root.iter()
+    {
+    match value
+        {
+        toml::Value::Table(table) =>
+            {
+            let mut fields = vec![("key".to_string(), name.clone())];
+            fields.extend(table.iter().map(|(key, value)| (key.clone(), toml_to_string(value))));
+            records.push(DataRecord { fields });
+            },
+        _ => return Err(format!("TOML entry '{}' is not a table, expected '[[name]]' or '[name]' tables", name)),
+        }
+    }
+Ok(records)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  json_to_string   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` value          `** a JSON value   
+/// **`RETURNS:    `** **` String         `** the text to be inserted into the skin (strings without quotes)   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn json_to_string(value: &serde_json::Value) -> String
+{
+match value
+    {
+    serde_json::Value::Null        => String::new(),
+    serde_json::Value::String(s)   => s.clone(),
+    serde_json::Value::Bool(b)     => (if *b {"1"} else {"0"}).to_string(),
+    other                          => other.to_string(),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  toml_to_string   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` value          `** a TOML value   
+/// **`RETURNS:    `** **` String         `** the text to be inserted into the skin (strings without quotes)   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn toml_to_string(value: &toml::Value) -> String
+{
+match value
+    {
+    toml::Value::String(s)   => s.clone(),
+    toml::Value::Boolean(b)  => (if *b {"1"} else {"0"}).to_string(),
+    other                    => other.to_string(),
+    }
+}
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for data_source   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** all_formats_give_the_same_records()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that CSV, JSON and TOML describing the same drives give the same records   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn all_formats_give_the_same_records()
+  {
+  let csv  = parse_csv("drive, label, removable\nC, System, 0\nD, Data, 1\n").unwrap();
+  let json = parse_json(r#"[{"drive": "C", "label": "System", "removable": false}, {"drive": "D", "label": "Data", "removable": true}]"#).unwrap();
+  let toml = parse_toml("[[drive]]\ndrive = \"C\"\nlabel = \"System\"\nremovable = false\n[[drive]]\ndrive = \"D\"\nlabel = \"Data\"\nremovable = true\n").unwrap();
+
+  for records in [&csv, &json, &toml]
+      {
+      assert_eq!(records.len(), 2);
+      assert_eq!(records[1].primary("drive"),   "D");
+      assert_eq!(records[1].get("label"),       Some("Data"));
+      assert_eq!(records[1].get("removable"),   Some("1"));
+      }
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** toml_named_tables()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that named TOML tables give records with the table name as primary value   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn toml_named_tables()
+  {
+  let records = parse_toml("[C]\nlabel = \"System\"\n[D]\nlabel = \"Data\"\n").unwrap();
+  assert_eq!(records.len(), 2);
+  assert_eq!(records[0].primary("drive"), "C");
+  assert_eq!(records[0].get("label"),     Some("System"));
+  }
+
+} // End of: mod test
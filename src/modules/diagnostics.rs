@@ -0,0 +1,332 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** diagnostics.rs 🦀   
+//! **`DESCRIPTION:`** error and warning messages which point to a file and a line, used by all parts reading arise input   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | spans: the source line is shown with the problem underlined   
+//! 0.3      | 2026-10-19 | Clunion   | closest_name for did-you-mean hints   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! error: multiplier list is not closed, missing '<multiplier list end>'   
+//!   --> input/StorageMon.arise:36   
+//!   
+//! error: unknown filter 'lowr'   
+//!   --> input/StorageMon.arise:112:23   
+//!    |   
+//! 112|        Drive=<:drive|lowr:>:   
+//!    |                      ^^^^   
+//!    = help: known filters: lower, upper, trim, quote, pad, replace, fixed   
+//!```
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::fmt;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+//___ none ___
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Severity
+{
+    Warning,
+    Error,
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// The part of a source line a diagnostic is about.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Span
+{
+    pub(crate) source: String,            // the whole source line
+    pub(crate) column: usize,             // 0-based char offset of the problem in the line
+    pub(crate) length: usize,             // length of the problem in chars, at least one is underlined
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Diagnostic
+{
+    pub(crate) severity: Severity,
+    pub(crate) file:     PathBuf,         // file the message points into
+    pub(crate) line:     usize,           // 1-based line number, 0 = no line known
+    pub(crate) message:  String,
+    pub(crate) help:     Option<String>,  // optional hint, printed below the location
+    pub(crate) span:     Option<Box<Span>>,    // optional part of the line, printed underlined
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl Diagnostic
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  error   
+/// **`TYPE:       `**  method of Diagnostic   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file         `** file the error was found in   
+/// **`            `** **` line         `** 1-based line number (0 if unknown)   
+/// **`            `** **` message      `** text describing the error   
+/// **`RETURNS:    `** **` Diagnostic   `** a newly created diagnostic with severity Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn error(file: &Path, line: usize, message: impl Into<String>) -> Diagnostic
+    {
+        Diagnostic
+        {
+        severity: Severity::Error,
+        file:     file.to_path_buf(),
+        line,
+        message:  message.into(),
+        help:     None,
+        span:     None,
+        }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  warning   
+/// **`TYPE:       `**  method of Diagnostic   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file         `** file the problem was found in   
+/// **`            `** **` line         `** 1-based line number (0 if unknown)   
+/// **`            `** **` message      `** text describing the problem   
+/// **`RETURNS:    `** **` Diagnostic   `** a newly created diagnostic with severity Warning   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn warning(file: &Path, line: usize, message: impl Into<String>) -> Diagnostic
+    {
+        Diagnostic
+        {
+        severity: Severity::Warning,
+        ..Diagnostic::error(file, line, message)
+        }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  with_help   
+/// **`TYPE:       `**  method of Diagnostic   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` help         `** hint on how to fix the problem   
+/// **`RETURNS:    `** **` Diagnostic   `** the diagnostic, extended by the hint   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn with_help(mut self, help: impl Into<String>) -> Diagnostic
+    {
+        self.help = Some(help.into());
+        self
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  with_span   
+/// **`TYPE:       `**  method of Diagnostic   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` source       `** the source line the diagnostic points into   
+/// **`            `** **` column       `** 0-based char offset of the problem in the line   
+/// **`            `** **` length       `** length of the problem in chars   
+/// **`RETURNS:    `** **` Diagnostic   `** the diagnostic, extended by the span   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn with_span(mut self, source: &str, column: usize, length: usize) -> Diagnostic
+    {
+        self.span = Some(Box::new(Span { source: source.to_string(), column, length }));
+        self
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  into_error   
+/// **`TYPE:       `**  method of Diagnostic   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` Diagnostic   `** the diagnostic with severity Error, for warnings treated as errors   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn into_error(self) -> Diagnostic
+    {
+        Diagnostic { severity: Severity::Error, ..self }
+    }
+
+} // End of impl: Diagnostic
+
+
+impl fmt::Display for Diagnostic
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let severity = match self.severity
+            {
+            Severity::Warning => "warning",
+            Severity::Error   => "error",
+            };
+
+        write!(f, "{}: {}", severity, self.message)?;
+        match (&self.span, self.line)
+            {
+            (_, 0)          => write!(f, "\n  --> {}",       self.file.display())?,
+            (None, line)    => write!(f, "\n  --> {}:{}",    self.file.display(), line)?,
+            (Some(span), line) =>
+                {
+                // tabs would shift the underline, so they are shown as single spaces:
+                let source: String = span.source.chars().map(|c| if c == '\t' { ' ' } else { c }).collect();
+                let number = line.to_string();
+                let margin = " ".repeat(number.len().max(3));
+                write!(f, "\n  --> {}:{}:{}", self.file.display(), line, span.column + 1)?;
+                write!(f, "\n{}|",     margin)?;
+                write!(f, "\n{:<3}|{}", number, source.trim_end())?;
+                write!(f, "\n{}|{}{}", margin, " ".repeat(span.column), "^".repeat(span.length.max(1)))?;
+                },
+            }
+        if let Some(help) = &self.help { write!(f, "\n   = help: {}", help)?; }
+        Ok(())
+    }
+}
+
+impl Error for Diagnostic {}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  closest_name   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name           `** a name which is not known   
+/// **`            `** **` candidates     `** the names which are known   
+/// **`RETURNS:    `** **` Option         `** the known name most similar to it, None if none is similar enough   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Similar means: at most a third of the chars (but at least one) has to be inserted, removed or replaced.   
+/// Case is ignored, so a name only differing in case is always found.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn closest_name<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str>
+{
+let limit = (name.chars().count() / 3).max(1);
+candidates.into_iter()
+          .map(|candidate| (edit_distance(&name.to_lowercase(), &candidate.to_lowercase()), candidate))
+          .filter(|(distance, candidate)| *distance <= limit && *candidate != name)
+          .min_by_key(|(distance, _)| *distance)
+          .map(|(_, candidate)| candidate)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  edit_distance   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` a, b           `** two texts   
+/// **`RETURNS:    `** **` usize          `** the number of chars to insert, remove or replace to turn a into b (Levenshtein)   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn edit_distance(a: &str, b: &str) -> usize
+{
+let b: Vec<char> = b.chars().collect();
+let mut previous: Vec<usize> = (0 ..= b.len()).collect();
+for (i, ca) in a.chars().enumerate()
+    {
+    let mut current = vec![i + 1];
+    for (j, cb) in b.iter().enumerate()
+        {
+        let replace = previous[j] + usize::from(ca != *cb);
+        current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+    previous = current;
+    }
+previous[b.len()]
+}
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for diagnostics   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** display_points_to_file_and_line()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks if the displayed text contains severity, message, location and help   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn display_points_to_file_and_line()
+  {
+  let diag = Diagnostic::error(Path::new("input/Test.arise"), 12, "something is wrong").with_help("do it right");
+  assert_eq!(diag.to_string(), "error: something is wrong\n  --> input/Test.arise:12\n   = help: do it right");
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** display_underlines_the_span()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that the source line is shown with the span underlined   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn display_underlines_the_span()
+  {
+  let diag = Diagnostic::error(Path::new("Test.arise"), 7, "unknown filter 'lowr'").with_span("\tX=<:drive|lowr:>", 11, 4);
+  assert_eq!(diag.to_string(), "error: unknown filter 'lowr'\n  --> Test.arise:7:12\n   |\n7  | X=<:drive|lowr:>\n   |           ^^^^");
+  }
+
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** similar_names_are_found()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the suggestions for misspelled names   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn similar_names_are_found()
+  {
+  let known = ["drive", "drive.label", "#index", "BarImage"];
+  assert_eq!(closest_name("drve",     known), Some("drive"));
+  assert_eq!(closest_name("Barimage", known), Some("BarImage"));
+  assert_eq!(closest_name("#idx",     known), None);
+  assert_eq!(closest_name("sensor",   known), None);
+  }
+
+} // End of: mod test
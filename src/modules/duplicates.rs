@@ -0,0 +1,258 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** duplicates.rs 🦀   
+//! **`DESCRIPTION:`** finds sections of the evolved skin with the same name, and keys repeated within a section   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | locating the origins shared with the cross reference check   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! warning: section [Meter_Drive_Label] is evolved 24 times, Rainmeter only uses the first one   
+//!   --> input/StorageMon.arise:88:1   
+//!    |   
+//! 88 |[Meter_Drive_Label]   
+//!    |^^^^^^^^^^^^^^^^^^^   
+//!    = help: is a placeholder like <:drive:> missing in its name? The copies come from input/StorageMon.arise:88 (drive 'C'), ...   
+//!```
+//! Rainmeter silently takes the first section of a name (names are compared ignoring case), so 24 drives collapse   
+//! into one visible meter. The warnings point to the template line of the section header, with the multiplier items   
+//! and loop elements it was evolved for.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::ini::{IniDocument, IniKey};
+use crate::modules::expansion::SectionOrigin;
+use crate::modules::diagnostics::Diagnostic;
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+//___ none ___
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// A key repeated in the sections evolved from the same template line.   
+struct RepeatedKey<'a>
+{
+    origin:   Option<&'a SectionOrigin>,
+    key:      String,
+    sections: Vec<String>,
+    values:   Vec<String>,      // of the first section
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+//___ none ___
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  duplicate_definitions   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file           `** the arise file, named for sections without a known origin   
+/// **`            `** **` skin           `** the evolved skin   
+/// **`            `** **` origins        `** where the sections come from, in the order they were evolved   
+/// **`RETURNS:    `** **` Vec            `** a warning for every section name used more than once, and for every key repeated   
+/// **`            `** **`                `** in a section, one per template line   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The n-th section of a name is the one evolved from the n-th section header of that name.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | origins located by locate_origins   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn duplicate_definitions(file: &Path, skin: &IniDocument, origins: &[SectionOrigin]) -> Vec<Diagnostic>
+{
+let located = locate_origins(skin, origins);
+
+let mut warnings = Vec::new();
+for (index, section) in skin.sections.iter().enumerate()
+    {
+    let copies: Vec<usize> = (0 .. skin.sections.len()).filter(|other| skin.sections[*other].name.eq_ignore_ascii_case(&section.name)).collect();
+    if copies.len() < 2 || copies[0] != index { continue; }
+    let from: Vec<String> = copies.iter().map(|copy| describe(located[*copy])).collect();
+    warnings.push(warning_at(file, located[index], format!("section [{}] is evolved {} times, Rainmeter only uses the first one", section.name, copies.len()))
+                  .with_help(format!("is a placeholder like <:drive:> missing in its name? The copies come from {}", from.join(", "))));
+    }
+
+let mut repeated: Vec<RepeatedKey> = Vec::new();
+for (index, section) in skin.sections.iter().enumerate()
+    {
+    let keys: Vec<&IniKey> = section.keys().collect();
+    for (position, key) in keys.iter().enumerate()
+        {
+        if keys[.. position].iter().any(|known| known.name.eq_ignore_ascii_case(&key.name)) { continue; }
+        let values: Vec<String> = keys.iter().filter(|other| other.name.eq_ignore_ascii_case(&key.name)).map(|other| other.value.clone()).collect();
+        if values.len() < 2 { continue; }
+
+        let origin = located[index];
+        let known  = repeated.iter_mut().find(|known| known.key.eq_ignore_ascii_case(&key.name) && same_line(known.origin, origin));
+        match known
+            {
+            Some(known) => known.sections.push(section.name.clone()),
+            None        => repeated.push(RepeatedKey { origin, key: key.name.clone(), sections: vec![section.name.clone()], values }),
+            }
+        }
+    }
+for repeat in repeated
+    {
+    let values: Vec<String> = repeat.values.iter().map(|value| format!("'{}'", value)).collect();
+    let mut help = format!("Rainmeter reads only one of them, remove the others; the values are {}", values.join(", "));
+    if repeat.sections.len() > 1
+        {
+        let others: Vec<String> = repeat.sections[1 ..].iter().map(|section| format!("[{}]", section)).collect();
+        help.push_str(&format!("; the same in {}", others.join(", ")));
+        }
+    warnings.push(warning_at(file, repeat.origin, format!("key '{}' is repeated in section [{}]", repeat.key, repeat.sections[0])).with_help(help));
+    }
+
+debug!("checked {} sections for duplicates, {} warning(s)", skin.sections.len(), warnings.len());
+warnings
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  locate_origins   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` skin           `** the evolved skin   
+/// **`            `** **` origins        `** where the sections come from, in the order they were evolved   
+/// **`RETURNS:    `** **` Vec            `** the origin of each section of the skin, None if it is not known   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, taken out of duplicate_definitions   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn locate_origins<'a>(skin: &IniDocument, origins: &'a [SectionOrigin]) -> Vec<Option<&'a SectionOrigin>>
+{
+let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+skin.sections.iter().map(|section|
+    {
+    let name  = section.name.to_lowercase();
+    let count = counts.entry(name.clone()).or_insert(0);
+    let origin = origins.iter().filter(|origin| origin.name.to_lowercase() == name).nth(*count);
+    *count += 1;
+    origin
+    }).collect()
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  describe / same_line / warning_at   
+/// **`TYPE:       `**  common functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The origin of a section as text like `input/StorageMon.arise:88 (drive 'C')`, whether two sections come from the   
+/// same template line, and a warning pointing to that line.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | same_line and warning_at used by the cross reference check   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn describe(origin: Option<&SectionOrigin>) -> String
+{
+match origin
+    {
+    Some(origin) if origin.items.is_empty() => format!("{}:{}", origin.line.file.display(), origin.line.number),
+    Some(origin)                            => format!("{}:{} ({})", origin.line.file.display(), origin.line.number, origin.items.join(", ")),
+    None                                    => "an unknown line".to_string(),
+    }
+}
+
+pub(crate) fn same_line(first: Option<&SectionOrigin>, second: Option<&SectionOrigin>) -> bool
+{
+match (first, second)
+    {
+    (Some(first), Some(second)) => first.line.file == second.line.file && first.line.number == second.line.number,
+    _                           => false,
+    }
+}
+
+pub(crate) fn warning_at(file: &Path, origin: Option<&SectionOrigin>, message: String) -> Diagnostic
+{
+match origin
+    {
+    Some(origin) =>
+        {
+        let text = origin.line.text.trim();
+        let column = origin.line.text.chars().count() - origin.line.text.trim_start().chars().count();
+        Diagnostic::warning(&origin.line.file, origin.line.number, message).with_span(&origin.line.text, column, text.chars().count())
+        },
+    None => Diagnostic::warning(file, 0, message),
+    }
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for duplicates   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  use std::path::PathBuf;
+  use crate::modules::template::SourceLine;
+  use crate::modules::expansion::{Scope, parse_nodes, render_nodes};
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** duplicates_are_reported_with_their_origin()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that sections evolved from a header without placeholder, and repeated keys, are reported   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn duplicates_are_reported_with_their_origin()
+  {
+  let file = PathBuf::from("t.arise");
+  let lines: Vec<SourceLine> = ["[Rainmeter]", "<:for drive in ['C', 'D']:>", "  [Meter_Drive]", "X=<:drive:>", "x=1", "<:end:>"].iter().enumerate()
+                               .map(|(index, text)| SourceLine { file: file.clone(), number: index + 1, text: text.to_string() })
+                               .collect();
+  let mut scope = Scope::new();
+  let skin = IniDocument::parse(&render_nodes(&parse_nodes(&lines).unwrap(), &mut scope).unwrap());
+
+  let warnings = duplicate_definitions(&file, &skin, &scope.take_origins());
+  assert_eq!(warnings.len(), 2);
+  assert_eq!(warnings[0].message, "section [Meter_Drive] is evolved 2 times, Rainmeter only uses the first one");
+  assert_eq!(warnings[0].line, 3);
+  assert!(warnings[0].help.as_deref().unwrap().ends_with("come from t.arise:3 (drive 'C'), t.arise:3 (drive 'D')"));
+  assert_eq!(warnings[1].message, "key 'X' is repeated in section [Meter_Drive]");
+  assert_eq!(warnings[1].help.as_deref(), Some("Rainmeter reads only one of them, remove the others; the values are 'C', '1'; the same in [Meter_Drive]"));
+
+  assert!(duplicate_definitions(&file, &IniDocument::parse("[A]\nX=1\n[B]\nX=1\n"), &[]).is_empty());
+  }
+
+} // End of: mod test
@@ -0,0 +1,1103 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** expansion.rs 🦀   
+//! **`DESCRIPTION:`** evolves the lines of a template section into skin lines: replaces the placeholders with the values   
+//! of the current item of the multiplier list   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | <:for x in list:> loops   
+//! 0.3      | 2026-10-19 | Clunion   | separator comments between the items   
+//! 0.4      | 2026-10-19 | Clunion   | filters like <:drive|lower:>   
+//! 0.5      | 2026-10-19 | Clunion   | <:call macro(...):>, see macros.rs   
+//! 0.6      | 2026-10-19 | Clunion   | unknown placeholders are errors, with a did-you-mean hint   
+//! 0.7      | 2026-10-19 | Clunion   | control assignments of type list are known as lists   
+//! 0.8      | 2026-10-19 | Clunion   | the origin of every evolved section is remembered, for diagnostics   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! Names known inside a multiplied section, here for the 3rd item of <:drive:> := @data("drives.csv"):   
+//!     <:drive:>          E               the item itself   
+//!     <:drive.label:>    Backup          a field of the item   
+//!     <:y offset:>       40              an offset variable (step 20 multiplied by #index)   
+//!     <:#index:>         2               position of the item, counting from 0   
+//!     <:#number:>        3               position of the item, counting from 1   
+//!     <:#count:>         24              number of items in the multiplier list   
+//!     <:RAINMETER_EXE:>  C:\...          a control assignment   
+//!     <:drive|lower:>    e               any of these with filters, see filters.rs   
+//!   
+//! Loops inside a section, here generating MeasureName, MeasureName2 and MeasureName3:   
+//!     <:for m in ['Read', 'Write', 'ReadWrite']:>   
+//!     MeasureName<:#loop.suffix:>=Measure_Drive_<:drive:>_<:m:>   
+//!     <:end:>   
+//! Inside the loop <:m:> is the current element, <:#loop.index:>, <:#loop.number:> and <:#loop.count:> count   
+//! like their section counterparts, <:#loop.suffix:> is empty for the first element, then 2, 3, ...   
+//! Looping over a multiplier list (<:for d in drive:>) makes the fields known as well: <:d.label:>   
+//!```
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::collections::BTreeMap;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::template::{AriseTemplate, Multiplier, SourceLine, KEY_NAME_BEGIN, KEY_NAME_END, CONTROL_SEPARATOR, SEPARATOR_WIDTH, placeholder_name};
+use crate::modules::expression::{Expr, Value, parse_expression, evaluate};
+use crate::modules::data_source::DataRecord;
+use crate::modules::diagnostics::{Diagnostic, closest_name};
+use crate::modules::filters::{FilterError, Placeholder, parse_placeholder, apply_filters};
+use crate::modules::types::{Type, list_items};
+use crate::modules::ini::IniSection;
+use crate::modules::macros::{MacroCall, MacroDefinition, DIRECTIVE_CALL, DIRECTIVE_MACRO, DIRECTIVE_ENDMACRO, parse_call, render_call};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const META_INDEX  : &str = "#index";
+pub(crate) const META_NUMBER : &str = "#number";
+pub(crate) const META_COUNT  : &str = "#count";
+
+pub(crate) const DIRECTIVE_IF   : &str = "if";
+pub(crate) const DIRECTIVE_ELIF : &str = "elif";
+pub(crate) const DIRECTIVE_ELSE : &str = "else";
+pub(crate) const DIRECTIVE_END  : &str = "end";
+pub(crate) const DIRECTIVE_FOR  : &str = "for";
+pub(crate) const KEYWORD_IN     : &str = "in";
+
+pub(crate) const LOOP_INDEX  : &str = "#loop.index";
+pub(crate) const LOOP_NUMBER : &str = "#loop.number";
+pub(crate) const LOOP_COUNT  : &str = "#loop.count";
+pub(crate) const LOOP_SUFFIX : &str = "#loop.suffix";
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// A part of a template section: a plain line or a block of lines controlled by a directive.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node
+{
+    Line(SourceLine),
+    If(Conditional),
+    For(Loop),
+    Call(MacroCall),
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// The names known while evolving a section, innermost frame last.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Scope
+{
+    frames: Vec<BTreeMap<String, String>>,
+    lists:  BTreeMap<String, Vec<DataRecord>>,       // the multiplier lists, for loops over them
+    macros: BTreeMap<String, MacroDefinition>,
+    calls:  Vec<String>,                             // the macros being evolved at the moment, outermost first
+    origins: Vec<SectionOrigin>,                     // the sections evolved so far, in the order of the skin
+}
+
+/// Where an evolved section comes from: the template line of its header, and the items it was evolved for.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SectionOrigin
+{
+    pub(crate) name:  String,
+    pub(crate) line:  SourceLine,
+    pub(crate) items: Vec<String>,       // like "drive 'C'", the multiplier item first, then the elements of loops
+}
+
+/// A `<:if ...:>` block with its `<:elif ...:>` and `<:else:>` branches.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Conditional
+{
+    pub(crate) branches:  Vec<Branch>,
+    pub(crate) otherwise: Option<Vec<Node>>,
+}
+
+/// One conditional branch: evolved if its condition is the first one being true.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Branch
+{
+    pub(crate) condition: Expr,
+    pub(crate) line:      SourceLine,
+    pub(crate) body:      Vec<Node>,
+}
+
+/// A `<:for variable in list:>` block, evolved once for every element of the list.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Loop
+{
+    pub(crate) variable: String,
+    pub(crate) list:     Expr,
+    pub(crate) line:     SourceLine,
+    pub(crate) body:     Vec<Node>,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl Scope
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  new   
+/// **`TYPE:       `**  method of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` Scope        `** a scope with one empty frame   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn new() -> Scope
+    {
+        Scope { frames: vec![BTreeMap::new()], ..Scope::default() }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  push_frame / pop_frame   
+/// **`TYPE:       `**  methods of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Opens a new innermost frame, whose names hide the names of the outer frames, or closes it again.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn push_frame(&mut self)
+    {
+        self.frames.push(BTreeMap::new());
+    }
+
+pub(crate) fn pop_frame(&mut self)
+    {
+        if self.frames.len() > 1 { self.frames.pop(); }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  set   
+/// **`TYPE:       `**  method of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** name to define in the innermost frame   
+/// **`            `** **` value        `** value of the name   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn set(&mut self, name: &str, value: &str)
+    {
+        if let Some(frame) = self.frames.last_mut() { frame.insert(name.to_string(), value.to_string()); }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  get   
+/// **`TYPE:       `**  method of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** name to look up, from the innermost frame outwards   
+/// **`RETURNS:    `** **` Option       `** the value, None if the name is unknown   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn get(&self, name: &str) -> Option<&str>
+    {
+        self.frames.iter().rev().find_map(|frame| frame.get(name)).map(|value| value.as_str())
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  set_list / get_list   
+/// **`TYPE:       `**  methods of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Makes a whole multiplier list known by its name, or looks it up. Lists are known in all frames.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn set_list(&mut self, name: &str, items: &[DataRecord])
+    {
+        self.lists.insert(name.to_string(), items.to_vec());
+    }
+
+pub(crate) fn get_list(&self, name: &str) -> Option<&[DataRecord]>
+    {
+        self.lists.get(name).map(|items| items.as_slice())
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  names   
+/// **`TYPE:       `**  method of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` Vec          `** the names of all frames and all lists, to look for similar ones   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn names(&self) -> Vec<&str>
+    {
+        self.frames.iter().flat_map(|frame| frame.keys()).chain(self.lists.keys()).map(|name| name.as_str()).collect()
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  set_macro / get_macro   
+/// **`TYPE:       `**  methods of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Makes a macro known by its name, or looks it up. Macros are known in all frames.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn set_macro(&mut self, definition: &MacroDefinition)
+    {
+        self.macros.insert(definition.name.clone(), definition.clone());
+    }
+
+pub(crate) fn get_macro(&self, name: &str) -> Option<&MacroDefinition>
+    {
+        self.macros.get(name)
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  outermost   
+/// **`TYPE:       `**  method of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` Scope        `** a copy with only the outermost frame, the lists, the macros and the calls   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn outermost(&self) -> Scope
+    {
+        Scope { frames: self.frames.iter().take(1).cloned().collect(), lists: self.lists.clone(), macros: self.macros.clone(), calls: self.calls.clone(), origins: Vec::new() }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  push_call / calls   
+/// **`TYPE:       `**  methods of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Remembers the macro being evolved in this scope, or gives all of them, to detect macros calling themselves.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn push_call(&mut self, name: &str)
+    {
+        self.calls.push(name.to_string());
+    }
+
+pub(crate) fn calls(&self) -> &[String]
+    {
+        &self.calls
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  push_origin / origin_count / add_origin_item / take_origins   
+/// **`TYPE:       `**  methods of Scope   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Remembers the origin of an evolved section. The item a part of the skin is evolved for is added to all origins   
+/// from `from` on, in front of the items of inner loops.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn push_origin(&mut self, origin: SectionOrigin)
+    {
+        self.origins.push(origin);
+    }
+
+pub(crate) fn origin_count(&self) -> usize
+    {
+        self.origins.len()
+    }
+
+pub(crate) fn add_origin_item(&mut self, from: usize, item: &str)
+    {
+        for origin in self.origins.iter_mut().skip(from) { origin.items.insert(0, item.to_string()); }
+    }
+
+pub(crate) fn take_origins(&mut self) -> Vec<SectionOrigin>
+    {
+        std::mem::take(&mut self.origins)
+    }
+
+} // End of impl: Scope
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  control_scope   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` template       `** the template, whose control assignments are to be made known   
+/// **`RETURNS:    `** **` Scope          `** a scope containing all control assignments, multiplier lists and macros   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | macros   
+/// 1.2     | 2026-10-19 | Clunion   | control assignments of type list   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn control_scope(template: &AriseTemplate) -> Scope
+{
+let mut scope = Scope::new();
+for assignment in &template.controls
+    {
+    scope.set(&assignment.name, &assignment.value);
+    if let (Type::List(_), Some(items)) = (&assignment.value_type, list_items(&assignment.value))
+        {
+        let items: Vec<DataRecord> = items.iter().map(|item| DataRecord::single(&assignment.name, item)).collect();
+        scope.set_list(&assignment.name, &items);
+        }
+    }
+for multiplier in &template.multipliers
+    {
+    scope.set_list(&multiplier.name, &multiplier.items);
+    }
+for definition in &template.macros
+    {
+    scope.set_macro(definition);
+    }
+scope
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  bind_item   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` scope          `** the scope to extend, gets a new innermost frame   
+/// **`            `** **` template       `** the template, for its offset variables   
+/// **`            `** **` multiplier     `** the multiplier list being iterated   
+/// **`            `** **` index          `** position of the current item in the list, counting from 0   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Makes the current item, its fields, the offset variables and the iteration metadata known. The caller has to   
+/// remove the frame again by calling `scope.pop_frame()` after evolving the item.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn bind_item(scope: &mut Scope, template: &AriseTemplate, multiplier: &Multiplier, index: usize)
+{
+scope.push_frame();
+
+if let Some(item) = multiplier.items.get(index)
+    {
+    scope.set(&multiplier.name, item.primary(&multiplier.name));
+    for (field, value) in &item.fields
+        {
+        scope.set(&format!("{}.{}", multiplier.name, field), value);
+        }
+    }
+
+for offset in &template.offsets
+    {
+    scope.set(&offset.name, &(offset.step * index as i64).to_string());
+    }
+
+scope.set(META_INDEX,  &index.to_string());
+scope.set(META_NUMBER, &(index + 1).to_string());
+scope.set(META_COUNT,  &multiplier.items.len().to_string());
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  item_separator   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` template       `** the template, for its ARISE_SEPARATOR control assignment   
+/// **`            `** **` multiplier     `** the multiplier list being iterated   
+/// **`            `** **` scope          `** the names known for the current item   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the comment line put in front of the item, like `;-< C >-----...`, or "")   
+/// **`            `** **`     or -->     `** - Error, for wrong filters in ARISE_SEPARATOR   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Without ARISE_SEPARATOR the comment shows the item itself, an empty ARISE_SEPARATOR switches the comments off.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn item_separator(template: &AriseTemplate, multiplier: &Multiplier, scope: &Scope) -> Result<String, Diagnostic>
+{
+let assignment = template.controls.iter().rev().find(|assignment| assignment.name == CONTROL_SEPARATOR);
+let pattern = match assignment
+    {
+    Some(assignment) => assignment.value.clone(),
+    None             => format!(";-< {}{}{} >", KEY_NAME_BEGIN, multiplier.name, KEY_NAME_END),
+    };
+if pattern.is_empty() { return Ok(String::new()); }
+
+let mut separator = match substitute_placeholders(&pattern, scope)
+    {
+    Ok(separator) => separator,
+    Err(why)      =>
+        {
+        // the value is not the whole line, so the span can't be shown:
+        let line = assignment.map(|assignment| assignment.line.clone()).unwrap_or_default();
+        return Err(Diagnostic::error(&line.file, line.number, format!("{} in {}", why.message, CONTROL_SEPARATOR)));
+        },
+    };
+let width = separator.chars().count();
+if width < SEPARATOR_WIDTH { separator.push_str(&"-".repeat(SEPARATOR_WIDTH - width)); }
+separator.push('\n');
+Ok(separator)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  substitute_placeholders   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a line of a template section   
+/// **`            `** **` scope          `** the names known at this point   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the line with all placeholders replaced by their filtered values)   
+/// **`            `** **`     or -->     `** - Error, for unknown names and wrong filters, positioned in the text   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// A quoted text is written as it is, `<:'<:':>` is the way to write a literal `<:`.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | filters   
+/// 1.2     | 2026-10-19 | Clunion   | quoted texts   
+/// 1.3     | 2026-10-19 | Clunion   | unknown names are errors instead of being kept   
+/// 1.4     | 2026-10-19 | Clunion   | expressions   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn substitute_placeholders(text: &str, scope: &Scope) -> Result<String, FilterError>
+{
+scan_placeholders(text, |placeholder, column, length|
+    {
+    if let Some(expression) = &placeholder.expression
+        {
+        return evaluate(expression, scope).map(|value| value.to_string()).map_err(|message| FilterError { message, help: None, column, length });
+        }
+    let literal = ['"', '\''].iter().find_map(|quote| placeholder.name.strip_prefix(*quote)?.strip_suffix(*quote));
+    match literal.or_else(|| scope.get(&placeholder.name))
+        {
+        Some(value) => apply_filters(value, &placeholder.filters),
+        None        =>
+            {
+            let help = match closest_name(&placeholder.name, scope.names())
+                {
+                Some(similar) => Some(format!("did you mean '{}{}{}'?", KEY_NAME_BEGIN, similar, KEY_NAME_END)),
+                None          => Some("define it as a control variable, a multiplier list or an offset variable, or quote it to write it as it is".to_string()),
+                };
+            Err(FilterError { message: format!("unknown placeholder '{}'", placeholder.name), help, column, length })
+            },
+        }
+    })
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  placeholders   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a line of a template section   
+/// **`RETURNS:    `** **` Result -->     `** - OK(all placeholders of the line with their filters, without the quoted texts)   
+/// **`            `** **`     or -->     `** - Error, for wrong filters, positioned in the text   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Checks the filters without looking up the names, so they are reported even if the line is never evolved.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn placeholders(text: &str) -> Result<Vec<Placeholder>, FilterError>
+{
+let mut found: Vec<Placeholder> = Vec::new();
+scan_placeholders(text, |placeholder, _, _|
+    {
+    if !placeholder.name.starts_with(['"', '\'']) { found.push(placeholder); }
+    Ok(String::new())
+    })?;
+Ok(found)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  scan_placeholders   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a line of a template section   
+/// **`            `** **` replace        `** gives the replacement of a placeholder, from its column and length in chars   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the line with all placeholders replaced)   
+/// **`            `** **`     or -->     `** - Error, of the filters or of replace   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, taken out of substitute_placeholders   
+/// ___________________________________________________________________________________________________________________________
+fn scan_placeholders(text: &str, mut replace: impl FnMut(Placeholder, usize, usize) -> Result<String, FilterError>) -> Result<String, FilterError>
+{
+let mut result = String::with_capacity(text.len());
+let mut rest   = text;
+let mut column = 0;         // chars of text before rest
+
+while let Some(begin) = rest.find(KEY_NAME_BEGIN)
+    {
+    let after_begin = &rest[begin + KEY_NAME_BEGIN.len() ..];
+    let end = match after_begin.find(KEY_NAME_END)
+        {
+        Some(end) => end,
+        None      => break,
+        };
+
+    result.push_str(&rest[.. begin]);
+    let consumed = begin + KEY_NAME_BEGIN.len() + end + KEY_NAME_END.len();
+    let start    = column + rest[.. begin].chars().count();
+    let placeholder = parse_placeholder(&after_begin[.. end], start + KEY_NAME_BEGIN.chars().count())?;
+    result.push_str(&replace(placeholder, start, rest[begin .. consumed].chars().count())?);
+    column += rest[.. consumed].chars().count();
+    rest = &rest[consumed ..];
+    }
+
+result.push_str(rest);
+Ok(result)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  filter_diagnostic   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` line           `** the template line containing the placeholder   
+/// **`            `** **` why            `** the error of the filter   
+/// **`            `** **` shift          `** chars removed from the start of the line before substituting   
+/// **`RETURNS:    `** **` Diagnostic     `** the error, pointing to the filter in the line   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn filter_diagnostic(line: &SourceLine, why: FilterError, shift: usize) -> Diagnostic
+{
+let diagnostic = Diagnostic::error(&line.file, line.number, why.message).with_span(&line.text, why.column + shift, why.length);
+match why.help
+    {
+    Some(help) => diagnostic.with_help(help),
+    None       => diagnostic,
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_nodes   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` lines          `** the lines of a template section   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the lines and directive blocks of the section)   
+/// **`            `** **`     or -->     `** - Error, for unbalanced directives or invalid conditions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Directives have to stand on a line of their own, like `<:if drive.removable == 1:>`.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, conditional blocks   
+/// 1.1     | 2026-10-19 | Clunion   | loops   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_nodes(lines: &[SourceLine]) -> Result<Vec<Node>, Diagnostic>
+{
+let mut position = 0;
+let nodes = parse_node_list(lines, &mut position, None)?;
+
+// parse_node_list only stops early at a directive continuing or closing a block, which has no block to belong to:
+if let Some(line) = lines.get(position)
+    {
+    let keyword = directive(&line.text).map(|(keyword, _)| keyword).unwrap_or_default();
+    let opening = if keyword == DIRECTIVE_END { format!("{}{} ...{} or {}{} ...{}", KEY_NAME_BEGIN, DIRECTIVE_IF, KEY_NAME_END, KEY_NAME_BEGIN, DIRECTIVE_FOR, KEY_NAME_END) }
+                  else                        { format!("{}{} ...{}", KEY_NAME_BEGIN, DIRECTIVE_IF, KEY_NAME_END) };
+    return Err(Diagnostic::error(&line.file, line.number, format!("{}{}{} without a matching {}", KEY_NAME_BEGIN, keyword, KEY_NAME_END, opening))
+                          .with_help(format!("every block has to start with {}{} condition{} or {}{} x in list{}", KEY_NAME_BEGIN, DIRECTIVE_IF, KEY_NAME_END, KEY_NAME_BEGIN, DIRECTIVE_FOR, KEY_NAME_END)));
+    }
+Ok(nodes)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_node_list   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` lines          `** the lines of a template section   
+/// **`            `** **` position       `** index of the next line to parse, advanced while parsing   
+/// **`            `** **` opened_by      `** the directive line opening the block being parsed, None at section level   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the nodes up to the directive continuing or closing the block)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn parse_node_list(lines: &[SourceLine], position: &mut usize, opened_by: Option<&SourceLine>) -> Result<Vec<Node>, Diagnostic>
+{
+let mut nodes = Vec::new();
+
+while let Some(line) = lines.get(*position)
+    {
+    match directive(&line.text)
+        {
+        Some((DIRECTIVE_IF, condition)) =>
+            {
+            *position += 1;
+            nodes.push(Node::If(parse_conditional(lines, position, line, condition)?));
+            },
+        Some((DIRECTIVE_FOR, head)) =>
+            {
+            *position += 1;
+            nodes.push(Node::For(parse_loop(lines, position, line, head)?));
+            },
+        Some((DIRECTIVE_CALL, head)) =>
+            {
+            nodes.push(Node::Call(parse_call(line, head)?));
+            *position += 1;
+            },
+        Some((DIRECTIVE_MACRO, _)) | Some((DIRECTIVE_ENDMACRO, _)) =>
+            {
+            return Err(Diagnostic::error(&line.file, line.number, format!("'{}' inside a block", line.text.trim()))
+                                  .with_help("macros are defined outside of the blocks, and can't be nested"));
+            },
+        Some((DIRECTIVE_ELIF, _)) | Some((DIRECTIVE_ELSE, _)) | Some((DIRECTIVE_END, _)) => return Ok(nodes),
+        _ =>
+            {
+            // checks the filters now, so they are reported even if the line is never evolved:
+            placeholders(&line.text).map_err(|why| filter_diagnostic(line, why, 0))?;
+            nodes.push(Node::Line(line.clone()));
+            *position += 1;
+            },
+        }
+    }
+
+match opened_by
+    {
+    None       => Ok(nodes),
+    Some(open) => Err(Diagnostic::error(&open.file, open.number, format!("'{}' is not closed, missing {}{}{}", open.text.trim(), KEY_NAME_BEGIN, DIRECTIVE_END, KEY_NAME_END))),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_conditional   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` lines          `** the lines of a template section   
+/// **`            `** **` position       `** index of the first line after `<:if ...:>`, advanced while parsing   
+/// **`            `** **` if_line        `** the line containing `<:if ...:>`   
+/// **`            `** **` condition      `** the condition text of the if directive   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the conditional block, up to and including its `<:end:>`)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn parse_conditional(lines: &[SourceLine], position: &mut usize, if_line: &SourceLine, condition: &str) -> Result<Conditional, Diagnostic>
+{
+let mut conditional = Conditional { branches: Vec::new(), otherwise: None };
+let mut condition    = parse_condition(if_line, condition)?;
+let mut branch_line  = if_line;
+
+loop
+    {
+    let body = parse_node_list(lines, position, Some(if_line))?;
+    match conditional.otherwise
+        {
+        None    => conditional.branches.push(Branch { condition: condition.clone(), line: branch_line.clone(), body }),
+        Some(_) => conditional.otherwise = Some(body),
+        }
+
+    // parse_node_list returned at a directive belonging to this block (or failed before):
+    let line = &lines[*position];
+    *position += 1;
+    match directive(&line.text)
+        {
+        Some((DIRECTIVE_ELIF, next)) =>
+            {
+            if conditional.otherwise.is_some()
+                {
+                return Err(Diagnostic::error(&line.file, line.number, format!("{}{}{} after {}{}{}", KEY_NAME_BEGIN, DIRECTIVE_ELIF, KEY_NAME_END, KEY_NAME_BEGIN, DIRECTIVE_ELSE, KEY_NAME_END))
+                                      .with_help(format!("the {}{}{} branch has to be the last one of the block started in line {}", KEY_NAME_BEGIN, DIRECTIVE_ELSE, KEY_NAME_END, if_line.number)));
+                }
+            condition   = parse_condition(line, next)?;
+            branch_line = line;
+            },
+        Some((DIRECTIVE_ELSE, rest)) =>
+            {
+            if !rest.is_empty()
+                {
+                return Err(Diagnostic::error(&line.file, line.number, format!("{}{}{} takes no condition", KEY_NAME_BEGIN, DIRECTIVE_ELSE, KEY_NAME_END))
+                                      .with_help(format!("use {}{} {}{} for another condition", KEY_NAME_BEGIN, DIRECTIVE_ELIF, rest, KEY_NAME_END)));
+                }
+            if conditional.otherwise.is_some()
+                {
+                return Err(Diagnostic::error(&line.file, line.number, format!("second {}{}{} in the block started in line {}", KEY_NAME_BEGIN, DIRECTIVE_ELSE, KEY_NAME_END, if_line.number)));
+                }
+            conditional.otherwise = Some(Vec::new());
+            },
+        _ => return Ok(conditional),    // <:end:>
+        }
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_loop   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` lines          `** the lines of a template section   
+/// **`            `** **` position       `** index of the first line after `<:for ...:>`, advanced while parsing   
+/// **`            `** **` for_line       `** the line containing `<:for ...:>`   
+/// **`            `** **` head           `** the text after the for keyword, like `m in ['Read', 'Write']`   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the loop, up to and including its `<:end:>`)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn parse_loop(lines: &[SourceLine], position: &mut usize, for_line: &SourceLine, head: &str) -> Result<Loop, Diagnostic>
+{
+let usage = format!("write it like {}{} m in ['Read', 'Write']{}", KEY_NAME_BEGIN, DIRECTIVE_FOR, KEY_NAME_END);
+let (variable, list) = match head.split_once(char::is_whitespace)
+    {
+    Some((variable, rest)) => match rest.trim_start().strip_prefix(KEYWORD_IN)
+        {
+        Some(list) if list.starts_with(char::is_whitespace) => (variable, list.trim()),
+        _                                                   => return Err(Diagnostic::error(&for_line.file, for_line.number, format!("'{}' is missing '{}'", for_line.text.trim(), KEYWORD_IN)).with_help(usage)),
+        },
+    None => return Err(Diagnostic::error(&for_line.file, for_line.number, format!("'{}' needs a variable and a list", for_line.text.trim())).with_help(usage)),
+    };
+
+if !variable.starts_with(|c: char| c.is_alphabetic() || c == '_') || !variable.chars().all(|c| c.is_alphanumeric() || c == '_')
+    {
+    return Err(Diagnostic::error(&for_line.file, for_line.number, format!("'{}' is not a valid loop variable", variable))
+                          .with_help("a loop variable consists of letters, digits and '_', and starts with a letter"));
+    }
+let list = parse_expression(list).map_err(|why| Diagnostic::error(&for_line.file, for_line.number, format!("invalid list '{}': {}", list, why.message))
+                                                         .with_help(format!("the problem is at: '{}'", list.chars().skip(why.position).collect::<String>())))?;
+
+let body = parse_node_list(lines, position, Some(for_line))?;
+
+// parse_node_list returned at a directive continuing or closing a block:
+let line = &lines[*position];
+*position += 1;
+match directive(&line.text)
+    {
+    Some((DIRECTIVE_END, _)) => Ok(Loop { variable: variable.to_string(), list, line: for_line.clone(), body }),
+    Some((keyword, _))       => Err(Diagnostic::error(&line.file, line.number, format!("{}{}{} does not belong to the {}{}{} in line {}", KEY_NAME_BEGIN, keyword, KEY_NAME_END, KEY_NAME_BEGIN, DIRECTIVE_FOR, KEY_NAME_END, for_line.number))
+                                          .with_help(format!("close the loop with {}{}{} first", KEY_NAME_BEGIN, DIRECTIVE_END, KEY_NAME_END))),
+    None                     => unreachable!("parse_node_list only returns at directives"),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_condition   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` line           `** the directive line, for diagnostics   
+/// **`            `** **` text           `** the condition text   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the parsed condition)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn parse_condition(line: &SourceLine, text: &str) -> Result<Expr, Diagnostic>
+{
+if text.is_empty()
+    {
+    return Err(Diagnostic::error(&line.file, line.number, format!("'{}' needs a condition", line.text.trim())));
+    }
+parse_expression(text).map_err(|why| Diagnostic::error(&line.file, line.number, format!("invalid condition '{}': {}", text, why.message))
+                                                 .with_help(format!("the problem is at: '{}'", text.chars().skip(why.position).collect::<String>())))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  directive   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a line of a template section   
+/// **`RETURNS:    `** **` Option         `** the directive keyword and the rest of the directive, None for other lines   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn directive(text: &str) -> Option<(&'static str, &str)>
+{
+let inner = placeholder_name(text.trim())?;
+let (keyword, rest) = match inner.find(char::is_whitespace)
+    {
+    Some(pos) => (&inner[.. pos], inner[pos ..].trim()),
+    None      => (inner, ""),
+    };
+let keyword = [DIRECTIVE_IF, DIRECTIVE_ELIF, DIRECTIVE_ELSE, DIRECTIVE_END, DIRECTIVE_FOR, DIRECTIVE_CALL, DIRECTIVE_MACRO, DIRECTIVE_ENDMACRO].iter().find(|known| **known == keyword)?;
+Some((keyword, rest))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  render_nodes   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` nodes          `** the parsed lines and directive blocks of a template section   
+/// **`            `** **` scope          `** the names known at this point   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the evolved skin text)   
+/// **`            `** **`     or -->     `** - Error, if a condition can't be evaluated   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The indentation of the template lines is removed, Rainmeter ini-files are written without it.   
+/// Of every conditional block only the first branch with a true condition (or the else branch) is evolved.   
+/// The body of a loop is evolved once per element, with the loop variables in a frame of its own.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, replaces render_lines   
+/// 1.1     | 2026-10-19 | Clunion   | loops   
+/// 1.2     | 2026-10-19 | Clunion   | macro calls   
+/// 1.3     | 2026-10-19 | Clunion   | remembers the origins of the section headers   
+/// 1.4     | 2026-10-19 | Clunion   | section headers read like in templates, with their base   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn render_nodes(nodes: &[Node], scope: &mut Scope) -> Result<String, Diagnostic>
+{
+let mut result = String::new();
+for node in nodes
+    {
+    match node
+        {
+        Node::Line(line) =>
+            {
+            let text  = line.text.trim_start();
+            let shift = line.text.chars().count() - text.chars().count();
+            let evolved = substitute_placeholders(text, scope).map_err(|why| filter_diagnostic(line, why, shift))?;
+            if let Some(section) = IniSection::parse_template_header(&evolved)
+                {
+                scope.push_origin(SectionOrigin { name: section.name, line: line.clone(), items: Vec::new() });
+                }
+            result.push_str(&evolved);
+            result.push('\n');
+            },
+        Node::If(conditional) =>
+            {
+            let mut chosen = conditional.otherwise.as_ref();
+            for branch in &conditional.branches
+                {
+                let value = evaluate(&branch.condition, scope)
+                            .map_err(|why| Diagnostic::error(&branch.line.file, branch.line.number, format!("couldn't evaluate condition '{}': {}", branch.line.text.trim(), why)))?;
+                if value.is_true() { chosen = Some(&branch.body); break; }
+                }
+            if let Some(body) = chosen { result.push_str(&render_nodes(body, scope)?); }
+            },
+        Node::For(repeat) => result.push_str(&render_loop(repeat, scope)?),
+        Node::Call(call)  => result.push_str(&render_call(call, scope)?),
+        }
+    }
+Ok(result)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  render_loop   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` repeat         `** the parsed loop   
+/// **`            `** **` scope          `** the names known at this point, gets a frame for the loop variables per element   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the evolved skin text of all elements)   
+/// **`            `** **`     or -->     `** - Error, if the list can't be evaluated or is no list   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// A loop over the name of a multiplier list iterates its items, making their fields known too.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | the element is added to the origins of the sections   
+/// ___________________________________________________________________________________________________________________________
+fn render_loop(repeat: &Loop, scope: &mut Scope) -> Result<String, Diagnostic>
+{
+let line = &repeat.line;
+let elements: Vec<DataRecord> = match &repeat.list
+    {
+    Expr::Name(name) if scope.get_list(name).is_some() =>
+        {
+        // the fields are bound under the loop variable, the item itself is the field named like the list:
+        scope.get_list(name).unwrap_or_default().iter()
+             .map(|item| {
+                  let mut fields = vec![(repeat.variable.clone(), item.primary(name).to_string())];
+                  fields.extend(item.fields.iter().filter(|(field, _)| field != name).cloned());
+                  DataRecord { fields }
+                  })
+             .collect()
+        },
+    list =>
+        {
+        match evaluate(list, scope).map_err(|why| Diagnostic::error(&line.file, line.number, format!("couldn't evaluate the list of '{}': {}", line.text.trim(), why)))?
+            {
+            Value::List(values) => values.iter().map(|value| DataRecord::single(&repeat.variable, &value.to_string())).collect(),
+            other               => return Err(Diagnostic::error(&line.file, line.number, format!("'{}' is not a list, but '{}'", line.text.trim(), other))
+                                                         .with_help("lists are written like ['Read', 'Write'] or 1..3, or are the name of a multiplier list")),
+            }
+        },
+    };
+
+let mut result = String::new();
+for (index, element) in elements.iter().enumerate()
+    {
+    scope.push_frame();
+    for (field, value) in &element.fields
+        {
+        if field == &repeat.variable { scope.set(field, value); }
+        else                         { scope.set(&format!("{}.{}", repeat.variable, field), value); }
+        }
+    scope.set(LOOP_INDEX,  &index.to_string());
+    scope.set(LOOP_NUMBER, &(index + 1).to_string());
+    scope.set(LOOP_COUNT,  &elements.len().to_string());
+    scope.set(LOOP_SUFFIX, &if index == 0 { String::new() } else { (index + 1).to_string() });
+    let from = scope.origin_count();
+    let rendered = render_nodes(&repeat.body, scope);
+    scope.add_origin_item(from, &format!("{} '{}'", repeat.variable, element.primary(&repeat.variable)));
+    scope.pop_frame();
+    result.push_str(&rendered?);
+    }
+Ok(result)
+}
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for expansion   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  use crate::modules::template::{ControlAssignment, OffsetVariable};
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** item_names_are_replaced()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the replacement of item, field, offset and metadata placeholders, unknown ones are kept   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn item_names_are_replaced()
+  {
+  let multiplier = Multiplier
+      {
+      name:  "drive".to_string(),
+      items: vec![DataRecord::single("drive", "C"), DataRecord { fields: vec![("drive".to_string(), "D".to_string()), ("label".to_string(), "Data".to_string())] }],
+      ..Multiplier::default()
+      };
+  let template = AriseTemplate { offsets: vec![OffsetVariable { name: "y offset".to_string(), step: 20, ..OffsetVariable::default() }], ..AriseTemplate::default() };
+
+  let mut scope = control_scope(&template);
+  bind_item(&mut scope, &template, &multiplier, 1);
+  assert_eq!(substitute_placeholders("[Meter_<:drive:>] Y=<: y offset :> ; <:drive.label:> <:#number:>/<:#count:>", &scope),
+             Ok("[Meter_D] Y=20 ; Data 2/2".to_string()));
+  let unknown = substitute_placeholders("Drive=<:drve:>:", &scope).unwrap_err();
+  assert_eq!((unknown.message.as_str(), unknown.help.as_deref(), unknown.column, unknown.length), ("unknown placeholder 'drve'", Some("did you mean '<:drive:>'?"), 6, 8));
+  let names: Vec<String> = placeholders("<:drive|lower:> <:'<:':> <: y offset :>").unwrap().into_iter().map(|placeholder| placeholder.name).collect();
+  assert_eq!(names, vec!["drive", "y offset"]);
+  assert_eq!(substitute_placeholders("File=<:drive|lower:>_<:#number|pad(2):>.png", &scope), Ok("File=d_02.png".to_string()));
+  assert_eq!(substitute_placeholders("Lua=<:'<:':>x:> <:\"a|b\"|upper:>", &scope), Ok("Lua=<:x:> A|B".to_string()));
+  assert_eq!(item_separator(&template, &multiplier, &scope), Ok(format!(";-< D >{}\n", "-".repeat(47))));
+  let custom = AriseTemplate { controls: vec![ControlAssignment { name: "ARISE_SEPARATOR".to_string(), value: ";-< <:drive:>: >".to_string(), ..ControlAssignment::default() }], ..template.clone() };
+  assert!(item_separator(&custom, &multiplier, &scope).unwrap().starts_with(";-< D: >---"));
+  scope.pop_frame();
+  assert_eq!(scope.get("drive"), None);
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** conditional_blocks()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that only the first true branch of nested conditional blocks is evolved   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn conditional_blocks()
+  {
+  let nodes = parse_nodes(&lines("<:if drive == 'C':>\n  system\n  <:if defined(LABEL):>\n  <:LABEL:>\n  <:end:>\n<:elif drive == 'D':>\ndata\n<:else:>\nother\n<:end:>\nalways")).unwrap();
+  let mut scope = Scope::new();
+
+  scope.set("drive", "C");
+  assert_eq!(render_nodes(&nodes, &mut scope).unwrap(), "system\nalways\n");
+  scope.set("LABEL", "Sys");
+  assert_eq!(render_nodes(&nodes, &mut scope).unwrap(), "system\nSys\nalways\n");
+  scope.set("drive", "D");
+  assert_eq!(render_nodes(&nodes, &mut scope).unwrap(), "data\nalways\n");
+  scope.set("drive", "E");
+  assert_eq!(render_nodes(&nodes, &mut scope).unwrap(), "other\nalways\n");
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** unbalanced_blocks_are_reported()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the diagnostics for missing, stray and misplaced directives   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn unbalanced_blocks_are_reported()
+  {
+  let message = |text: &str| parse_nodes(&lines(text)).unwrap_err().to_string();
+
+  assert!(message("a\n<:if x:>\nb").contains("'<:if x:>' is not closed, missing <:end:>\n  --> Test.arise:2"));
+  assert!(message("a\n<:end:>").contains("<:end:> without a matching <:if ...:> or <:for ...:>\n  --> Test.arise:2"));
+  assert!(message("<:if x:>\n<:else:>\n<:elif y:>\n<:end:>").contains("<:elif:> after <:else:>\n  --> Test.arise:3"));
+  assert!(message("<:if x == :>\n<:end:>").contains("invalid condition 'x =='"));
+  assert!(message("<:for m in [1, 2]:>\n<:else:>\n<:end:>").contains("<:else:> does not belong to the <:for:> in line 1\n  --> Test.arise:2"));
+  assert!(message("<:for m [1, 2]:>\n<:end:>").contains("is missing 'in'"));
+  assert!(message("a\n  X=<:drive|lowr:>").contains("unknown filter 'lowr'\n  --> Test.arise:2:13\n   |\n2  |  X=<:drive|lowr:>\n   |            ^^^^"));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** loops()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks nested loops over lists, ranges and multiplier lists, and the loop-local variables   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn loops()
+  {
+  let nodes = parse_nodes(&lines("<:for m in ['Read', 'Write']:>\nMeasureName<:#loop.suffix:>=M_<:drive:>_<:m:>\n<:end:>")).unwrap();
+  let mut scope = Scope::new();
+  scope.set("drive", "C");
+  assert_eq!(render_nodes(&nodes, &mut scope).unwrap(), "MeasureName=M_C_Read\nMeasureName2=M_C_Write\n");
+  let nodes = parse_nodes(&lines("<:for m in ['Read']:>\n<:end:>\n<:m:>")).unwrap();
+  assert!(render_nodes(&nodes, &mut scope).unwrap_err().to_string().contains("unknown placeholder 'm'"));
+
+  let nodes = parse_nodes(&lines("<:for i in 1..2:>\n<:for d in drive:>\n<:i:><:d:><:d.label:><:#loop.number:>/<:#loop.count:>\n<:end:>\n<:end:>")).unwrap();
+  scope.set_list("drive", &[DataRecord { fields: vec![("drive".to_string(), "C".to_string()), ("label".to_string(), "System".to_string())] },
+                          DataRecord { fields: vec![("drive".to_string(), "D".to_string()), ("label".to_string(), "Data".to_string())] }]);
+  assert_eq!(render_nodes(&nodes, &mut scope).unwrap(), "1CSystem1/2\n1DData2/2\n2CSystem1/2\n2DData2/2\n");
+
+  let nodes = parse_nodes(&lines("<:for m in drive.label:>\n<:end:>")).unwrap();
+  scope.set("drive.label", "Data");
+  assert!(render_nodes(&nodes, &mut scope).unwrap_err().to_string().contains("is not a list, but 'Data'"));
+  }
+
+  fn lines(text: &str) -> Vec<SourceLine>
+  {
+  text.lines().enumerate().map(|(index, line)| SourceLine { file: "Test.arise".into(), number: index + 1, text: line.to_string() }).collect()
+  }
+
+} // End of: mod test
@@ -0,0 +1,787 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** expression.rs 🦀   
+//! **`DESCRIPTION:`** the small expression language used in the directives of arise templates, like `<:if expr:>`   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | lists [a, b, c] and ranges 1..3   
+//! 0.3      | 2026-10-19 | Clunion   | colors like #00A0FF and the color functions of colors.rs   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! drive.removable == 1   
+//! #index > 0 && #index < #count - 1   
+//! not defined(FOOTER_TEXT) or FOOTER_TEXT == ""   
+//! (#number % 2 == 0) || drive == 'C'   
+//! ['Read', 'Write', 'ReadWrite']                  <-- a list, as used by <:for x in list:>   
+//! 1 .. #count                                     <-- whole numbers, including both ends   
+//! mix(ACCENT, #FF0000, 0.5)                       <-- a color function with a hex color   
+//!```
+//! Names are looked up in the current scope: multiplier variables and their fields, iteration metadata (`#index`,   
+//! `#number`, `#count`) and control assignments. Text which looks like a number is compared as a number.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::fmt;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::expansion::Scope;
+use crate::modules::colors::{Color, COLOR_FUNCTIONS, GRADIENT_FUNCTIONS, HEX_PREFIX, call_color_function, call_gradient};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const FUNCTION_DEFINED : &str = "defined";
+pub(crate) const MAX_RANGE_LENGTH : i64  = 10_000;     // a range like 1..N must not run away with a typo
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// The value of an evaluated expression.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value
+{
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    List(Vec<Value>),
+    Color(Color),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnaryOp
+{
+    Not,
+    Negate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinaryOp
+{
+    Or,
+    And,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+/// A parsed expression.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr
+{
+    Literal(Value),
+    Name(String),
+    Call(String, Vec<Expr>),
+    List(Vec<Expr>),
+    Range(Box<Expr>, Box<Expr>),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token
+{
+    Number(f64),
+    Text(String),
+    Name(String),
+    Color(Color),
+    Symbol(&'static str),
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// An error found while parsing or evaluating an expression, `position` is the char offset in the expression text.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExprError
+{
+    pub(crate) message:  String,
+    pub(crate) position: usize,
+}
+
+struct Parser
+{
+    tokens:   Vec<(Token, usize)>,   // token and its char offset in the expression text
+    position: usize,
+    length:   usize,                 // length of the expression text, reported for errors at the end
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl Value
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  is_true   
+/// **`TYPE:       `**  method of Value   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` bool         `** the truth of the value: false, 0, "", "0", "false" and [] are false, all else is true   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn is_true(&self) -> bool
+    {
+        match self
+            {
+            Value::Bool(b)   => *b,
+            Value::Number(n) => *n != 0.0,
+            Value::Text(s)   => { let s = s.trim(); !(s.is_empty() || s == "0" || s.eq_ignore_ascii_case("false")) },
+            Value::List(l)   => !l.is_empty(),
+            Value::Color(_)  => true,
+            }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  as_number   
+/// **`TYPE:       `**  method of Value   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` Option       `** the value as number, None if it is a text which does not look like a number   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn as_number(&self) -> Option<f64>
+    {
+        match self
+            {
+            Value::Number(n) => Some(*n),
+            Value::Bool(b)   => Some(if *b {1.0} else {0.0}),
+            Value::Text(s)   => s.trim().parse::<f64>().ok(),
+            Value::List(_)   => None,
+            Value::Color(_)  => None,
+            }
+    }
+
+} // End of impl: Value
+
+
+impl fmt::Display for Value
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+            {
+            Value::Text(s)                                           => write!(f, "{}", s),
+            Value::Bool(b)                                           => write!(f, "{}", if *b {1} else {0}),
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < 1e15   => write!(f, "{}", *n as i64),
+            Value::Number(n)                                         => write!(f, "{}", n),
+            Value::List(l)                                           =>
+                {
+                let items: Vec<String> = l.iter().map(|item| item.to_string()).collect();
+                write!(f, "{}", items.join(","))
+                },
+            Value::Color(color)                                      => write!(f, "{}", color),
+            }
+    }
+}
+
+
+impl Parser
+{
+    fn peek(&self) -> Option<&Token>
+    {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn offset(&self) -> usize
+    {
+        self.tokens.get(self.position).map(|(_, offset)| *offset).unwrap_or(self.length)
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool
+    {
+        if self.peek() == Some(&Token::Symbol(symbol_literal(symbol))) { self.position += 1; true } else { false }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool
+    {
+        if matches!(self.peek(), Some(Token::Name(name)) if name == keyword) { self.position += 1; true } else { false }
+    }
+
+    fn error(&self, message: impl Into<String>) -> ExprError
+    {
+        ExprError { message: message.into(), position: self.offset() }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ExprError>
+    {
+        let mut left = self.parse_and()?;
+        while self.eat_symbol("||") || self.eat_keyword("or")
+            {
+            let right = self.parse_and()?;
+            left = Expr::Binary(BinaryOp::Or, Box::new(left), Box::new(right));
+            }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ExprError>
+    {
+        let mut left = self.parse_not()?;
+        while self.eat_symbol("&&") || self.eat_keyword("and")
+            {
+            let right = self.parse_not()?;
+            left = Expr::Binary(BinaryOp::And, Box::new(left), Box::new(right));
+            }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, ExprError>
+    {
+        if self.eat_symbol("!") || self.eat_keyword("not")
+            {
+            return Ok(Expr::Unary(UnaryOp::Not, Box::new(self.parse_not()?)));
+            }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, ExprError>
+    {
+        let left = self.parse_range()?;
+        let op = match self.peek()
+            {
+            Some(Token::Symbol("==")) => BinaryOp::Equal,
+            Some(Token::Symbol("!=")) => BinaryOp::NotEqual,
+            Some(Token::Symbol("<"))  => BinaryOp::Less,
+            Some(Token::Symbol("<=")) => BinaryOp::LessEqual,
+            Some(Token::Symbol(">"))  => BinaryOp::Greater,
+            Some(Token::Symbol(">=")) => BinaryOp::GreaterEqual,
+            _                         => return Ok(left),
+            };
+        self.position += 1;
+        let right = self.parse_range()?;
+        Ok(Expr::Binary(op, Box::new(left), Box::new(right)))
+    }
+
+    fn parse_range(&mut self) -> Result<Expr, ExprError>
+    {
+        let first = self.parse_additive()?;
+        if !self.eat_symbol("..") { return Ok(first); }
+        let last = self.parse_additive()?;
+        Ok(Expr::Range(Box::new(first), Box::new(last)))
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, ExprError>
+    {
+        let mut left = self.parse_multiplicative()?;
+        loop
+            {
+            let op = match self.peek()
+                {
+                Some(Token::Symbol("+")) => BinaryOp::Add,
+                Some(Token::Symbol("-")) => BinaryOp::Subtract,
+                _                        => return Ok(left),
+                };
+            self.position += 1;
+            let right = self.parse_multiplicative()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+            }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, ExprError>
+    {
+        let mut left = self.parse_unary()?;
+        loop
+            {
+            let op = match self.peek()
+                {
+                Some(Token::Symbol("*")) => BinaryOp::Multiply,
+                Some(Token::Symbol("/")) => BinaryOp::Divide,
+                Some(Token::Symbol("%")) => BinaryOp::Remainder,
+                _                        => return Ok(left),
+                };
+            self.position += 1;
+            let right = self.parse_unary()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+            }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ExprError>
+    {
+        if self.eat_symbol("-")
+            {
+            return Ok(Expr::Unary(UnaryOp::Negate, Box::new(self.parse_unary()?)));
+            }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ExprError>
+    {
+        let token = match self.peek()
+            {
+            Some(token) => token.clone(),
+            None        => return Err(self.error("unexpected end of expression")),
+            };
+        self.position += 1;
+
+        match token
+            {
+            Token::Number(n)            => Ok(Expr::Literal(Value::Number(n))),
+            Token::Text(s)              => Ok(Expr::Literal(Value::Text(s))),
+            Token::Color(color)         => Ok(Expr::Literal(Value::Color(color))),
+            Token::Name(name) if name == "true"  => Ok(Expr::Literal(Value::Bool(true))),
+            Token::Name(name) if name == "false" => Ok(Expr::Literal(Value::Bool(false))),
+            Token::Name(name)           =>
+                {
+                if !self.eat_symbol("(") { return Ok(Expr::Name(name)); }
+                let mut arguments = Vec::new();
+                if !self.eat_symbol(")")
+                    {
+                    loop
+                        {
+                        arguments.push(self.parse_or()?);
+                        if self.eat_symbol(")") { break; }
+                        if !self.eat_symbol(",") { return Err(self.error(format!("expected ',' or ')' in the arguments of '{}'", name))); }
+                        }
+                    }
+                Ok(Expr::Call(name, arguments))
+                },
+            Token::Symbol("[")          =>
+                {
+                let mut items = Vec::new();
+                if !self.eat_symbol("]")
+                    {
+                    loop
+                        {
+                        items.push(self.parse_or()?);
+                        if self.eat_symbol("]") { break; }
+                        if !self.eat_symbol(",") { return Err(self.error("expected ',' or ']' in the list")); }
+                        }
+                    }
+                Ok(Expr::List(items))
+                },
+            Token::Symbol("(")          =>
+                {
+                let inner = self.parse_or()?;
+                if !self.eat_symbol(")") { return Err(self.error("missing ')'")); }
+                Ok(inner)
+                },
+            Token::Symbol(symbol)       => { self.position -= 1; Err(self.error(format!("unexpected '{}'", symbol))) },
+            }
+    }
+
+} // End of impl: Parser
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_expression   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** the expression text, like `drive.removable == 1`   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the parsed expression)   
+/// **`            `** **`     or -->     `** - Error(message and position)   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_expression(text: &str) -> Result<Expr, ExprError>
+{
+let tokens = tokenize(text)?;
+let mut parser = Parser { tokens, position: 0, length: text.chars().count() };
+
+if parser.peek().is_none() { return Err(parser.error("empty expression")); }
+let expr = parser.parse_or()?;
+match parser.peek()
+    {
+    None        => Ok(expr),
+    Some(_)     => Err(parser.error("unexpected text after the end of the expression")),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  evaluate   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` expr           `** a parsed expression   
+/// **`            `** **` scope          `** the names known at this point   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the value)   
+/// **`            `** **`     or -->     `** - Error(message), for unknown names or operations which make no sense   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn evaluate(expr: &Expr, scope: &Scope) -> Result<Value, String>
+{
+match expr
+    {
+    Expr::Literal(value) => Ok(value.clone()),
+    Expr::Name(name)     => match scope.get(name)
+        {
+        Some(value) => Ok(Value::Text(value.to_string())),
+        None        => Err(format!("unknown name '{}'", name)),
+        },
+    Expr::Call(name, arguments) => evaluate_call(name, arguments, scope),
+    Expr::List(items)           => Ok(Value::List(items.iter().map(|item| evaluate(item, scope)).collect::<Result<Vec<Value>, String>>()?)),
+    Expr::Range(first, last)    =>
+        {
+        let (first, last) = (evaluate(first, scope)?, evaluate(last, scope)?);
+        let (from, to) = match (first.as_number(), last.as_number())
+            {
+            (Some(from), Some(to)) if from.fract() == 0.0 && to.fract() == 0.0 => (from as i64, to as i64),
+            _ => return Err(format!("a range needs whole numbers, got '{}..{}'", first, last)),
+            };
+        if to - from >= MAX_RANGE_LENGTH { return Err(format!("the range '{}..{}' is too long, at most {} items are allowed", from, to, MAX_RANGE_LENGTH)); }
+        Ok(Value::List((from ..= to).map(|n| Value::Number(n as f64)).collect()))
+        },
+    Expr::Unary(UnaryOp::Not, inner)    => Ok(Value::Bool(!evaluate(inner, scope)?.is_true())),
+    Expr::Unary(UnaryOp::Negate, inner) =>
+        {
+        let value = evaluate(inner, scope)?;
+        match value.as_number()
+            {
+            Some(n) => Ok(Value::Number(-n)),
+            None    => Err(format!("can't negate the text '{}'", value)),
+            }
+        },
+    Expr::Binary(BinaryOp::Or, left, right)  => Ok(Value::Bool(evaluate(left, scope)?.is_true() || evaluate(right, scope)?.is_true())),
+    Expr::Binary(BinaryOp::And, left, right) => Ok(Value::Bool(evaluate(left, scope)?.is_true() && evaluate(right, scope)?.is_true())),
+    Expr::Binary(op, left, right)            => evaluate_binary(*op, evaluate(left, scope)?, evaluate(right, scope)?),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  evaluate_call   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name           `** name of the called function   
+/// **`            `** **` arguments      `** the (unevaluated) arguments   
+/// **`            `** **` scope          `** the names known at this point   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the value)   
+/// **`            `** **`     or -->     `** - Error(message)   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, only defined(name)   
+/// 1.1     | 2026-10-19 | Clunion   | color functions   
+/// 1.2     | 2026-10-19 | Clunion   | gradients   
+/// ___________________________________________________________________________________________________________________________
+fn evaluate_call(name: &str, arguments: &[Expr], scope: &Scope) -> Result<Value, String>
+{
+match (name, arguments)
+    {
+    (FUNCTION_DEFINED, [Expr::Name(tested)]) => Ok(Value::Bool(scope.get(tested).is_some() || scope.get_list(tested).is_some())),
+    (FUNCTION_DEFINED, _)                    => Err(format!("'{}' expects exactly one name, like {}(FOOTER_TEXT)", FUNCTION_DEFINED, FUNCTION_DEFINED)),
+    _ if COLOR_FUNCTIONS.iter().any(|(known, _)| *known == name) =>
+        {
+        let values = arguments.iter().map(|argument| evaluate(argument, scope)).collect::<Result<Vec<Value>, String>>()?;
+        call_color_function(name, &values)
+        },
+    _ if GRADIENT_FUNCTIONS.contains(&name) =>
+        {
+        let values = arguments.iter().map(|argument| evaluate(argument, scope)).collect::<Result<Vec<Value>, String>>()?;
+        call_gradient(name, &values)
+        },
+    _                                        => Err(format!("unknown function '{}'", name)),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  evaluate_binary   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` op             `** the operator (all but the logical ones, which short-cut)   
+/// **`            `** **` left, right    `** the evaluated operands   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the value)   
+/// **`            `** **`     or -->     `** - Error(message)   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Comparisons are numeric if both sides look like numbers, otherwise the texts are compared.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn evaluate_binary(op: BinaryOp, left: Value, right: Value) -> Result<Value, String>
+{
+let numbers = match (left.as_number(), right.as_number())
+    {
+    (Some(l), Some(r)) => Some((l, r)),
+    _                  => None,
+    };
+
+let ordering = match numbers
+    {
+    Some((l, r)) => l.partial_cmp(&r),
+    None         => Some(left.to_string().cmp(&right.to_string())),
+    };
+
+match op
+    {
+    BinaryOp::Equal        => Ok(Value::Bool(ordering == Some(std::cmp::Ordering::Equal))),
+    BinaryOp::NotEqual     => Ok(Value::Bool(ordering != Some(std::cmp::Ordering::Equal))),
+    BinaryOp::Less         => Ok(Value::Bool(ordering == Some(std::cmp::Ordering::Less))),
+    BinaryOp::LessEqual    => Ok(Value::Bool(matches!(ordering, Some(std::cmp::Ordering::Less) | Some(std::cmp::Ordering::Equal)))),
+    BinaryOp::Greater      => Ok(Value::Bool(ordering == Some(std::cmp::Ordering::Greater))),
+    BinaryOp::GreaterEqual => Ok(Value::Bool(matches!(ordering, Some(std::cmp::Ordering::Greater) | Some(std::cmp::Ordering::Equal)))),
+    _                      =>
+        {
+        let (l, r) = match numbers
+            {
+            Some(pair) => pair,
+            None       => return Err(format!("arithmetic needs numbers, got '{}' and '{}'", left, right)),
+            };
+        match op
+            {
+            BinaryOp::Add       => Ok(Value::Number(l + r)),
+            BinaryOp::Subtract  => Ok(Value::Number(l - r)),
+            BinaryOp::Multiply  => Ok(Value::Number(l * r)),
+            BinaryOp::Divide    if r == 0.0 => Err("division by zero".to_string()),
+            BinaryOp::Divide    => Ok(Value::Number(l / r)),
+            BinaryOp::Remainder if r == 0.0 => Err("division by zero".to_string()),
+            BinaryOp::Remainder => Ok(Value::Number(l % r)),
+            _                   => unreachable!("logical and comparison operators are handled above"),
+            }
+        },
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  tokenize   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** the expression text   
+/// **`RETURNS:    `** **` Result -->     `** - OK(tokens with their char offsets)   
+/// **`            `** **`     or -->     `** - Error(message and position)   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Names may contain letters, digits, '_' and '.', and may start with '#' (iteration metadata like `#index`).   
+/// Texts are enclosed in single or double quotes, '#' followed by 6 or 8 hex digits is a color.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | hex colors   
+/// ___________________________________________________________________________________________________________________________
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, ExprError>
+{
+let chars: Vec<char> = text.chars().collect();
+let mut tokens = Vec::new();
+let mut pos = 0;
+
+while pos < chars.len()
+    {
+    let c = chars[pos];
+    let start = pos;
+
+    if c.is_whitespace() { pos += 1; continue; }
+
+    if c.is_ascii_digit()
+        {
+        while pos < chars.len() && chars[pos].is_ascii_digit() { pos += 1; }
+        if pos + 1 < chars.len() && chars[pos] == '.' && chars[pos + 1].is_ascii_digit()    // "1..3" is a range, not a number
+            {
+            pos += 1;
+            while pos < chars.len() && chars[pos].is_ascii_digit() { pos += 1; }
+            }
+        let literal: String = chars[start .. pos].iter().collect();
+        match literal.parse::<f64>()
+            {
+            Ok(n)  => tokens.push((Token::Number(n), start)),
+            Err(_) => return Err(ExprError { message: format!("invalid number '{}'", literal), position: start }),
+            }
+        continue;
+        }
+
+    if c == HEX_PREFIX
+        {
+        let length = chars[pos + 1 ..].iter().take_while(|next| next.is_alphanumeric() || **next == '_').count();
+        let word: String = chars[pos + 1 .. pos + 1 + length].iter().collect();
+        if let Some(color) = Color::parse(&word)
+            {
+            tokens.push((Token::Color(color), start));
+            pos += 1 + length;
+            continue;
+            }
+        }
+
+    if c.is_alphabetic() || c == '_' || c == '#'
+        {
+        pos += 1;
+        while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_' || is_field_dot(&chars, pos)) { pos += 1; }
+        tokens.push((Token::Name(chars[start .. pos].iter().collect()), start));
+        continue;
+        }
+
+    if c == '"' || c == '\''
+        {
+        pos += 1;
+        while pos < chars.len() && chars[pos] != c { pos += 1; }
+        if pos >= chars.len() { return Err(ExprError { message: "text is not closed, missing quote".to_string(), position: start }); }
+        tokens.push((Token::Text(chars[start + 1 .. pos].iter().collect()), start));
+        pos += 1;
+        continue;
+        }
+
+    let two: String = chars[pos .. (pos + 2).min(chars.len())].iter().collect();
+    if ["==", "!=", "<=", ">=", "&&", "||", ".."].contains(&two.as_str())
+        {
+        tokens.push((Token::Symbol(symbol_literal(&two)), start));
+        pos += 2;
+        continue;
+        }
+    if "+-*/%<>!(),[]".contains(c)
+        {
+        tokens.push((Token::Symbol(symbol_literal(&c.to_string())), start));
+        pos += 1;
+        continue;
+        }
+
+    return Err(ExprError { message: format!("unexpected character '{}'", c), position: start });
+    }
+Ok(tokens)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  is_field_dot   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` chars          `** the chars of the expression text   
+/// **`            `** **` pos            `** position to check   
+/// **`RETURNS:    `** **` bool           `** true if there is a '.' which separates a name and a field, like in `drive.label`   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn is_field_dot(chars: &[char], pos: usize) -> bool
+{
+chars[pos] == '.' && chars.get(pos + 1).is_some_and(|next| next.is_alphanumeric() || *next == '_')
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  symbol_literal   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` symbol         `** an operator or punctuation text   
+/// **`RETURNS:    `** **` &'static str   `** the same symbol as static text, "" for unknown symbols   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn symbol_literal(symbol: &str) -> &'static str
+{
+const SYMBOLS: [&str; 20] = ["==", "!=", "<=", ">=", "&&", "||", "..", "+", "-", "*", "/", "%", "<", ">", "!", "(", ")", ",", "[", "]"];
+SYMBOLS.iter().find(|known| **known == symbol).copied().unwrap_or("")
+}
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for expression   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  fn eval(text: &str, scope: &Scope) -> Result<Value, String>
+  {
+  evaluate(&parse_expression(text).map_err(|e| e.message)?, scope)
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** conditions_on_items()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks comparisons, logic, arithmetic and defined() against names of the scope   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn conditions_on_items()
+  {
+  let mut scope = Scope::new();
+  scope.set("drive",           "D");
+  scope.set("drive.removable", "1");
+  scope.set("#index",          "1");
+  scope.set("#count",          "3");
+
+  assert_eq!(eval("drive.removable == 1",                 &scope), Ok(Value::Bool(true)));
+  assert_eq!(eval("drive == 'C' or drive == \"D\"",        &scope), Ok(Value::Bool(true)));
+  assert_eq!(eval("#index < #count - 1 && !(#index == 0)", &scope), Ok(Value::Bool(true)));
+  assert_eq!(eval("not defined(FOOTER)",                  &scope), Ok(Value::Bool(true)));
+  assert_eq!(eval("(#index + 1) * 10 % 7",                &scope), Ok(Value::Number(6.0)));
+  assert_eq!(eval("drve == 'C'",                          &scope), Err("unknown name 'drve'".to_string()));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** lists_and_ranges()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks list literals and ranges, also that ranges are not mistaken for numbers or fields   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn lists_and_ranges()
+  {
+  let mut scope = Scope::new();
+  scope.set("#count", "3");
+
+  assert_eq!(eval("['a', 1.5]", &scope), Ok(Value::List(vec![Value::Text("a".to_string()), Value::Number(1.5)])));
+  assert_eq!(eval("1..#count",  &scope), Ok(Value::List(vec![Value::Number(1.0), Value::Number(2.0), Value::Number(3.0)])));
+  assert_eq!(eval("0 .. 1",     &scope).map(|v| v.to_string()), Ok("0,1".to_string()));
+  assert!(eval("1..2.5", &scope).is_err());
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** colors()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks hex color literals, which are not mistaken for metadata names, and the color functions   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn colors()
+  {
+  let mut scope = Scope::new();
+  scope.set("ACCENT", "0,160,255");
+  scope.set("#count", "3");
+
+  assert_eq!(eval("#00a0ff", &scope).map(|v| v.to_string()), Ok("00A0FF".to_string()));
+  assert_eq!(eval("mix(ACCENT, #FF0000, 1 / 2)", &scope).map(|v| v.to_string()), Ok("128,80,128".to_string()));
+  assert_eq!(eval("to_hex(alpha(ACCENT, #count))", &scope).map(|v| v.to_string()), Ok("00A0FF03".to_string()));
+  assert_eq!(eval("lighten(#count, 1)", &scope), Err("argument 1 of 'lighten' has to be a color, got '3'".to_string()));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** syntax_errors_have_positions()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that syntax errors report the char offset of the problem   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn syntax_errors_have_positions()
+  {
+  assert_eq!(parse_expression("(a == 1").unwrap_err().position,  7);
+  assert_eq!(parse_expression("a == == 1").unwrap_err().position, 5);
+  assert_eq!(parse_expression("a $ 1").unwrap_err().position,     2);
+  }
+
+} // End of: mod test
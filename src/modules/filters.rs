@@ -0,0 +1,454 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** filters.rs 🦀   
+//! **`DESCRIPTION:`** pipe filters inside placeholders, which change the value before it is written into the skin   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | expressions like <:= lighten(ACCENT, 20):>   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! <:drive|lower:>                 c                   lower case   
+//! <:drive|upper:>                 C                   upper case   
+//! <:label|trim:>                  Data                without leading and trailing spaces   
+//! <:label|quote:>                 "Data"              enclosed in double quotes   
+//! <:#number|pad(2):>              03                  filled up from the left with '0' to 2 chars   
+//! <:#number|pad(4, " "):>            3                filled up from the left with the given char   
+//! <:label|replace(" ", "_"):>     My_Data             every " " replaced by "_"   
+//! <:size|fixed(1):>               12.5                a number with 1 decimal   
+//! <:label|upper|trim:>            DATA                filters are applied from left to right   
+//!```
+//! A placeholder starting with '=' is an expression instead, written as its value. It takes no filters:   
+//!```text
+//! <:= lighten(ACCENT, 20):>       102,198,255         see expression.rs and colors.rs   
+//! <:= #number * 20:>              60   
+//!```
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::expression::{Expr, Value, parse_expression};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const FILTER_SEPARATOR : char = '|';
+pub(crate) const EXPRESSION_MARK  : char = '=';      // <:= expression:>
+
+pub(crate) const FILTER_LOWER   : &str = "lower";
+pub(crate) const FILTER_UPPER   : &str = "upper";
+pub(crate) const FILTER_TRIM    : &str = "trim";
+pub(crate) const FILTER_QUOTE   : &str = "quote";
+pub(crate) const FILTER_PAD     : &str = "pad";
+pub(crate) const FILTER_REPLACE : &str = "replace";
+pub(crate) const FILTER_FIXED   : &str = "fixed";
+
+/// Every filter with the kinds of its arguments and how many of them are required.   
+const FILTERS: [(&str, &[ArgumentKind], usize); 7] =
+    [
+    (FILTER_LOWER,   &[],                                       0),
+    (FILTER_UPPER,   &[],                                       0),
+    (FILTER_TRIM,    &[],                                       0),
+    (FILTER_QUOTE,   &[],                                       0),
+    (FILTER_PAD,     &[ArgumentKind::Count, ArgumentKind::Char], 1),
+    (FILTER_REPLACE, &[ArgumentKind::Text, ArgumentKind::Text],  2),
+    (FILTER_FIXED,   &[ArgumentKind::Count],                     1),
+    ];
+
+const MAX_COUNT : i64 = 100;    // more decimals or a wider padding is surely a typo
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgumentKind
+{
+    Count,      // a whole number from 0 to MAX_COUNT
+    Text,       // a quoted text
+    Char,       // a quoted text of exactly one char
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// One filter of a placeholder, like `pad(2)`, with its position in the source text.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FilterCall
+{
+    pub(crate) name:      String,
+    pub(crate) arguments: Vec<Value>,
+    pub(crate) column:    usize,        // char offset of the filter in the source text
+    pub(crate) length:    usize,        // length of the filter in chars
+}
+
+/// The content of a placeholder: the name to look up and the filters to apply, like `drive|lower`.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Placeholder
+{
+    pub(crate) name:       String,
+    pub(crate) filters:    Vec<FilterCall>,
+    pub(crate) expression: Option<Expr>,      // for <:= expression:>, evaluated instead of looking up the name
+}
+
+/// An unknown filter, a wrong argument or a value a filter can't handle, `column` and `length` mark the problem.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FilterError
+{
+    pub(crate) message: String,
+    pub(crate) help:    Option<String>,
+    pub(crate) column:  usize,
+    pub(crate) length:  usize,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl ArgumentKind
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  describe   
+/// **`TYPE:       `**  method of ArgumentKind   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` &str         `** what an argument of this kind looks like, for error messages   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn describe(self) -> &'static str
+    {
+        match self
+            {
+            ArgumentKind::Count => "a whole number",
+            ArgumentKind::Text  => "a quoted text",
+            ArgumentKind::Char  => "a quoted single char",
+            }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  accepts   
+/// **`TYPE:       `**  method of ArgumentKind   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` value        `** the value of an argument   
+/// **`RETURNS:    `** **` bool         `** true if the value is an argument of this kind   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn accepts(self, value: &Value) -> bool
+    {
+        match (self, value)
+            {
+            (ArgumentKind::Count, Value::Number(n)) => n.fract() == 0.0 && *n >= 0.0 && *n <= MAX_COUNT as f64,
+            (ArgumentKind::Text,  Value::Text(_))   => true,
+            (ArgumentKind::Char,  Value::Text(s))   => s.chars().count() == 1,
+            _                                       => false,
+            }
+    }
+
+} // End of impl: ArgumentKind
+
+
+impl FilterCall
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  error   
+/// **`TYPE:       `**  method of FilterCall   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` message      `** text describing the problem   
+/// **`RETURNS:    `** **` FilterError  `** an error marking this filter in the source text   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn error(&self, message: String) -> FilterError
+    {
+        FilterError { message, help: None, column: self.column, length: self.length }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  count / text   
+/// **`TYPE:       `**  methods of FilterCall   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The argument at the given position, already checked by parse_placeholder. Missing optional arguments give None.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn count(&self, position: usize) -> Option<usize>
+    {
+        self.arguments.get(position).and_then(Value::as_number).map(|n| n as usize)
+    }
+
+fn text(&self, position: usize) -> Option<String>
+    {
+        self.arguments.get(position).map(|value| value.to_string())
+    }
+
+} // End of impl: FilterCall
+
+
+impl FilterError
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  with_help   
+/// **`TYPE:       `**  method of FilterError   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` help         `** hint on how to fix the problem   
+/// **`RETURNS:    `** **` FilterError  `** the error, extended by the hint   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn with_help(mut self, help: impl Into<String>) -> FilterError
+    {
+        self.help = Some(help.into());
+        self
+    }
+
+} // End of impl: FilterError
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_placeholder   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** the text between `<:` and `:>`, like `label|replace(" ", "_")|upper`   
+/// **`            `** **` column         `** char offset of the text in its source line, added to the error positions   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the name and the checked filters)   
+/// **`            `** **`     or -->     `** - Error, for unknown filters and wrong arguments   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | expressions   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_placeholder(text: &str, column: usize) -> Result<Placeholder, FilterError>
+{
+if let Some(source) = text.trim_start().strip_prefix(EXPRESSION_MARK)
+    {
+    let offset = column + text.chars().count() - source.chars().count();
+    return match parse_expression(source)
+        {
+        Ok(expression) => Ok(Placeholder { name: text.trim().to_string(), filters: Vec::new(), expression: Some(expression) }),
+        Err(why)       => Err(FilterError { message: why.message, help: None, column: offset + why.position, length: 1 }),
+        };
+    }
+
+let mut parts = split_filters(text).into_iter();
+let name = parts.next().map(|(_, part)| part.trim().to_string()).unwrap_or_default();
+
+let mut filters = Vec::new();
+for (offset, part) in parts
+    {
+    let leading = part.chars().take_while(|c| c.is_whitespace()).count();
+    let trimmed = part.trim();
+    let call    = FilterCall { name: String::new(), arguments: Vec::new(), column: column + offset + leading, length: trimmed.chars().count() };
+
+    let (name, arguments) = match parse_expression(trimmed)
+        {
+        Ok(Expr::Name(name))            => (name, Vec::new()),
+        Ok(Expr::Call(name, arguments)) => (name, arguments),
+        _                               => return Err(call.error(format!("'{}' is no filter", trimmed))
+                                                      .with_help("write filters like <:name|lower:> or <:name|pad(2):>")),
+        };
+    let call = FilterCall { name, ..call };
+
+    let (_, kinds, required) = match FILTERS.iter().find(|(known, _, _)| *known == call.name)
+        {
+        Some(filter) => filter,
+        None         => return Err(call.error(format!("unknown filter '{}'", call.name))
+                                       .with_help(format!("known filters: {}", FILTERS.iter().map(|(known, _, _)| *known).collect::<Vec<_>>().join(", ")))),
+        };
+    if arguments.len() < *required || arguments.len() > kinds.len()
+        {
+        let expected = if required == &kinds.len() { required.to_string() } else { format!("{} to {}", required, kinds.len()) };
+        return Err(call.error(format!("filter '{}' expects {} arguments, got {}", call.name, expected, arguments.len())));
+        }
+
+    let mut values = Vec::new();
+    for (position, (argument, kind)) in arguments.iter().zip(kinds.iter()).enumerate()
+        {
+        match argument
+            {
+            Expr::Literal(value) if kind.accepts(value) => values.push(value.clone()),
+            _                                           => return Err(call.error(format!("argument {} of filter '{}' has to be {}", position + 1, call.name, kind.describe()))),
+            }
+        }
+    filters.push(FilterCall { arguments: values, ..call });
+    }
+
+Ok(Placeholder { name, filters, expression: None })
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  apply_filters   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` value          `** the value of the placeholder name   
+/// **`            `** **` filters        `** the filters, as checked by parse_placeholder   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the filtered value)   
+/// **`            `** **`     or -->     `** - Error, if a filter can't handle the value, like fixed() with a text   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn apply_filters(value: &str, filters: &[FilterCall]) -> Result<String, FilterError>
+{
+let mut value = value.to_string();
+for filter in filters
+    {
+    value = match filter.name.as_str()
+        {
+        FILTER_LOWER   => value.to_lowercase(),
+        FILTER_UPPER   => value.to_uppercase(),
+        FILTER_TRIM    => value.trim().to_string(),
+        FILTER_QUOTE   => format!("\"{}\"", value),
+        FILTER_PAD     =>
+            {
+            let width = filter.count(0).unwrap_or(0);
+            let fill  = filter.text(1).unwrap_or_else(|| "0".to_string());
+            let count = value.chars().count();
+            if count < width { format!("{}{}", fill.repeat(width - count), value) } else { value }
+            },
+        FILTER_REPLACE =>
+            {
+            let (from, to) = (filter.text(0).unwrap_or_default(), filter.text(1).unwrap_or_default());
+            if from.is_empty() { value } else { value.replace(&from, &to) }
+            },
+        FILTER_FIXED   =>
+            {
+            let number = match value.trim().parse::<f64>()
+                {
+                Ok(number) => number,
+                Err(_)     => return Err(filter.error(format!("filter '{}' needs a number, got '{}'", filter.name, value))),
+                };
+            format!("{:.*}", filter.count(0).unwrap_or(0), number)
+            },
+        other          => return Err(filter.error(format!("unknown filter '{}'", other))),
+        };
+    }
+Ok(value)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  split_filters   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** the text between `<:` and `:>`   
+/// **`RETURNS:    `** **` Vec            `** the parts between the '|', each with its char offset in the text   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// A '|' inside quotes belongs to an argument, like in `replace("|", "/")`.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn split_filters(text: &str) -> Vec<(usize, String)>
+{
+let mut parts = Vec::new();
+let mut part  = String::new();
+let mut start = 0;
+let mut quote: Option<char> = None;
+
+for (offset, c) in text.chars().enumerate()
+    {
+    match quote
+        {
+        Some(open) if c == open                    => quote = None,
+        None if c == '"' || c == '\''              => quote = Some(c),
+        None if c == FILTER_SEPARATOR              =>
+            {
+            parts.push((start, std::mem::take(&mut part)));
+            start = offset + 1;
+            continue;
+            },
+        _                                          => {},
+        }
+    part.push(c);
+    }
+parts.push((start, part));
+parts
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for filters   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  fn filtered(text: &str, value: &str) -> Result<String, FilterError>
+  {
+  apply_filters(value, &parse_placeholder(text, 0)?.filters)
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** filters_are_chained()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks every filter and the chaining from left to right   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn filters_are_chained()
+  {
+  assert_eq!(parse_placeholder(" drive | lower ", 0).unwrap().name, "drive");
+  assert_eq!(filtered("drive|lower",                  "C"),          Ok("c".to_string()));
+  assert_eq!(filtered("i|pad(2)",                     "3"),          Ok("03".to_string()));
+  assert_eq!(filtered("i|pad(3, ' ')",                "7"),          Ok("  7".to_string()));
+  assert_eq!(filtered("name|replace(\" \",\"_\")",    "My Data"),    Ok("My_Data".to_string()));
+  assert_eq!(filtered("name|replace(\"|\", \"/\")",   "a|b"),        Ok("a/b".to_string()));
+  assert_eq!(filtered("v|fixed(1)",                   "12.46"),      Ok("12.5".to_string()));
+  assert_eq!(filtered("label|upper|trim|quote",       " Data "),     Ok("\"DATA\"".to_string()));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** errors_mark_the_filter()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that unknown filters and wrong arguments are reported with the position of the filter   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn errors_mark_the_filter()
+  {
+  let error = parse_placeholder("drive| lowr", 10).unwrap_err();
+  assert_eq!((error.message.as_str(), error.column, error.length), ("unknown filter 'lowr'", 17, 4));
+
+  let error = parse_placeholder("i|pad(\"2\")", 0).unwrap_err();
+  assert_eq!((error.message.as_str(), error.column, error.length), ("argument 1 of filter 'pad' has to be a whole number", 2, 8));
+
+  assert_eq!(parse_placeholder("i|replace(\"a\")", 0).unwrap_err().message, "filter 'replace' expects 2 arguments, got 1");
+  assert_eq!(filtered("v|fixed(1)", "C").unwrap_err().message,            "filter 'fixed' needs a number, got 'C'");
+  }
+
+} // End of: mod test
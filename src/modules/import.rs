@@ -0,0 +1,834 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** import.rs 🦀   
+//! **`DESCRIPTION:`** infers an arise template from an existing, hand written Rainmeter skin: sections repeated with a   
+//! varying token are collapsed into multiplied blocks, all other lines go into header, footer and blocks as they are   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | copies compared by their sections, the comments around them repeat as far as they can   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! arise import Skins/DriveMonitor/DriveMonitor.ini   
+//!
+//! [Measure_Drive_C_Total]                          <:drive:> := 'CDE'   
+//! Measure=FreeDiskSpace                            ...   
+//! Drive=C:                          -->            <block drive_measures repeat=drive>   
+//! [Measure_Drive_D_Total]                          [Measure_Drive_<:drive:>_Total]   
+//! Measure=FreeDiskSpace                            Measure=FreeDiskSpace   
+//! Drive=D:                                         Drive=<:drive:>:   
+//! ...                                              <block end>   
+//!```
+//! The file is split into units: a section from its header to its last key. Runs of at least three copies of the   
+//! same units, differing only in words or numbers, become a block multiplied by a list of the first varying token.   
+//! The comments and blank lines between the copies go into the block as far as they are the same in all of them,   
+//! a comment only in front of the first copy, like `; --- Skin Measures-Start ---`, stays in front of the block. Other varying tokens are arithmetic progressions like `<:= 10 + #index * 20:>`, or further fields   
+//! of the items, kept in a data file next to the template. The template is evolved before it is written, so the   
+//! import only succeeds if it gives the original file again, byte for byte.   
+//! ___________________________________________________________________________________________________________________________
+//!
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::config::{AriseConfig, ARISE_FILE_EXTENSION};
+use crate::modules::core_logic::{evolve_skin, exists_file};
+use crate::modules::diagnostics::Diagnostic;
+use crate::modules::expansion::{META_INDEX, META_NUMBER};
+use crate::modules::ini_syntax::{Encoding, LineEnding, SyntaxKind, SyntaxTree};
+use crate::modules::template::*;
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+const MIN_COPIES   : usize = 3;             // fewer copies are kept as they are
+const MAX_FAMILIES : usize = 4;             // copies differing in more tokens are most likely no copies at all
+const DEFAULT_LIST : &str  = "item";
+
+// delimiters tried in turn, if the skin contains the ones before:
+const PLACEHOLDER_DELIMITERS : [(&str, &str); 3] = [(KEY_NAME_BEGIN, KEY_NAME_END), ("{{", "}}"), ("<%", "%>")];
+const MULTILINE_DELIMITERS   : [(&str, &str); 3] = [(COMMENT_MULTILINE_BEGIN, COMMENT_MULTILINE_END), ("(*", "*)"), ("{#", "#}")];
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenClass
+{
+    Word,       // letters, a word of a camel case name
+    Number,     // digits
+    Other,      // everything else, has to be the same in all copies
+}
+
+/// What a varying token is written as in the template.   
+#[derive(Debug, Clone, PartialEq)]
+enum Binding
+{
+    Item(usize),                // the item of a multiplier list
+    Field(usize, String),       // a further field of the items of a multiplier list
+    Expression(String),         // an expression of the index of the item
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+#[derive(Debug, Clone, PartialEq)]
+struct Token
+{
+    class: TokenClass,
+    text:  String,
+}
+
+/// Copies of `size` units, starting at unit `start`.   
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Group
+{
+    start:  usize,
+    size:   usize,
+    copies: usize,
+}
+
+/// The lines of the units, and where the copies of a group are in them.   
+struct Layout<'a>
+{
+    lines:  &'a [String],
+    starts: Vec<usize>,                             // the line of the header of each unit
+    ends:   Vec<usize>,                             // behind the line of the last key of each unit
+}
+
+/// The lines of a block, from `begin` to behind `end`, and the tokens of each copy.   
+#[derive(Debug, Clone, PartialEq)]
+struct Placement
+{
+    begin:  usize,
+    end:    usize,
+    copies: Vec<Vec<Token>>,
+}
+
+/// A multiplier list of the template, with the further fields of its items.   
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ImportList
+{
+    name:   String,
+    items:  Vec<String>,
+    fields: Vec<(String, Vec<String>)>,
+}
+
+/// The inferred template and the data files it needs.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ImportedSkin
+{
+    pub(crate) template:   String,
+    pub(crate) data_files: Vec<(String, String)>,      // file name (next to the template) and contents
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+impl Layout<'_>
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  place   
+/// **`TYPE:       `**  method of Layout   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` group_p       `** the copies   
+/// **`            `** **` floor_p       `** the first line the block may take, behind the block in front of it   
+/// **`RETURNS:    `** **` Option -->    `** - Some(the lines of the block and the tokens of its copies)   
+/// **`            `** **`       -->    `** - None, if the copies with their lines between differ in more than words or numbers   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Every copy takes the same last lines of the gap in front of it, like a separator comment, and the same first lines   
+/// of the gap behind it, as many as are the same in all copies; the rest of the gap in front of the first copy and   
+/// behind the last one stays outside.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn place(&self, group_p: &Group, floor_p: usize) -> Option<Placement>
+    {
+        let first = |copy: usize| group_p.start + copy * group_p.size;
+        let last  = |copy: usize| first(copy) + group_p.size - 1;
+        let next  = self.starts.get(last(group_p.copies - 1) + 1).copied().unwrap_or(self.lines.len());
+        let gap_before = |copy: usize| -> &[String]
+            {
+            let begin = if copy > 0 { self.ends[last(copy - 1)] } else if group_p.start > 0 { self.ends[group_p.start - 1].max(floor_p) } else { floor_p };
+            &self.lines[begin .. self.starts[first(copy)]]
+            };
+        let after = &self.lines[self.ends[last(group_p.copies - 1)] .. next];
+        let shape = |lines: &[String]| tokenize(&(lines.join("\n") + "\n"));
+        let alike = |first: &[String], second: &[String]| first.len() == second.len() && same_shape(&shape(first), &shape(second));
+
+        let most = (0 .. group_p.copies).map(|copy| gap_before(copy).len()).min().unwrap_or(0);
+        for taken in (0 ..= most).rev()
+            {
+            let leading = |copy: usize| { let gap = gap_before(copy); &gap[gap.len() - taken ..] };
+            let trailing = |copy: usize| { let gap = gap_before(copy + 1); &gap[.. gap.len() - taken] };
+            let behind = if group_p.copies > 1 { trailing(0) } else { &after[.. 0] };
+            if !(1 .. group_p.copies).all(|copy| alike(leading(0), leading(copy))) { continue; }
+            if !(1 .. group_p.copies - 1).all(|copy| alike(behind, trailing(copy)))  { continue; }
+            if after.len() < behind.len() || !alike(behind, &after[.. behind.len()]) { continue; }
+
+            let copies: Vec<Vec<Token>> = (0 .. group_p.copies).map(|copy| shape(&self.lines[self.starts[first(copy)] - taken .. self.ends[last(copy)] + behind.len()])).collect();
+            if copies.iter().all(|copy| same_shape(&copies[0], copy))
+                {
+                return Some(Placement { begin: self.starts[first(0)] - taken, end: self.ends[last(group_p.copies - 1)] + behind.len(), copies });
+                }
+            }
+        None
+    }
+
+} // End of impl: Layout
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  import_skin   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` conf_p        `** the configuration, the template is written into its input directory   
+/// **`            `** **` ini_file_p    `** the existing Rainmeter skin   
+/// **`RETURNS:    `** **` Result -->    `** - OK(path of the written arise file)   
+/// **`            `** **`     or -->    `** - Error, if the skin can't be read or the template doesn't evolve into it   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Writes `<skin>.arise` and its data files, but only after checking that the template evolves into the skin again.   
+/// Existing files are not overwritten.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn import_skin(conf_p: &AriseConfig, ini_file_p: &Path) -> Result<PathBuf, Box<dyn Error>>
+{
+let bytes = fs::read(ini_file_p).map_err(|why| Diagnostic::error(ini_file_p, 0, format!("couldn't read the skin: {}", why)))?;
+let tree  = SyntaxTree::decode(&bytes).map_err(|why| Diagnostic::error(ini_file_p, 0, why))?;
+
+let stem = ini_file_p.file_stem().and_then(|stem| stem.to_str()).unwrap_or(DEFAULT_LIST).to_string();
+let imported = infer_template(ini_file_p, &tree, &stem)?;
+
+let arise_file = conf_p.inp_pathpart.join(&stem).with_extension(ARISE_FILE_EXTENSION);
+let mut written: Vec<PathBuf> = Vec::new();
+for file in std::iter::once(arise_file.clone()).chain(imported.data_files.iter().map(|(name, _)| conf_p.inp_pathpart.join(name)))
+    {
+    if exists_file(&file)
+        {
+        return Err(Diagnostic::error(&file, 0, "the file exists already").with_help("remove or rename it, the import doesn't overwrite files").into());
+        }
+    }
+
+// the data files are needed to evolve the template:
+for (name, contents) in &imported.data_files
+    {
+    let file = conf_p.inp_pathpart.join(name);
+    fs::write(&file, contents)?;
+    written.push(file);
+    }
+
+let evolved = evolve_skin(&arise_file, &imported.template);
+let checked = evolved.and_then(|evolved| compare_skins(ini_file_p, &bytes, &evolved).map_err(|why| why.into()));
+if let Err(why) = checked
+    {
+    for file in &written { let _ = fs::remove_file(file); }
+    return Err(why);
+    }
+
+fs::write(&arise_file, &imported.template)?;
+info!("imported '{}' into '{}', evolving it gives the skin again", ini_file_p.display(), arise_file.display());
+Ok(arise_file)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  infer_template   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file_p        `** the skin, named in diagnostics   
+/// **`            `** **` tree_p        `** the lines of the skin   
+/// **`            `** **` stem_p        `** base name of the skin, for the names of the data files   
+/// **`RETURNS:    `** **` Result -->    `** - OK(the template text and its data files)   
+/// **`            `** **`     or -->    `** - Error, for skins the template can't give again, like mixed line endings   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | units start at the section headers   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn infer_template(file_p: &Path, tree_p: &SyntaxTree, stem_p: &str) -> Result<ImportedSkin, Diagnostic>
+{
+let line_ending = check_line_endings(file_p, tree_p)?;
+let lines: Vec<String> = tree_p.lines().map(|line| line.kind.to_string()).collect();
+let text = lines.join("\n");
+
+let Some(placeholder) = PLACEHOLDER_DELIMITERS.iter().find(|(begin, end)| !text.contains(begin) && !text.contains(end)) else
+    {
+    return Err(Diagnostic::error(file_p, 0, "the skin contains all delimiters arise knows for placeholders"));
+    };
+let Some(multiline) = MULTILINE_DELIMITERS.iter().find(|(begin, end)| !text.contains(begin) && !text.contains(end)) else
+    {
+    return Err(Diagnostic::error(file_p, 0, "the skin contains all delimiters arise knows for multiline comments"));
+    };
+
+// a unit is a section from its header to its last key, the comments and blank lines around it are compared later:
+let mut layout = Layout { lines: &lines, starts: Vec::new(), ends: Vec::new() };
+let mut position = tree_p.preamble.len();
+for section in &tree_p.sections
+    {
+    let last_key = section.lines.iter().rposition(|line| matches!(line.kind, SyntaxKind::Key { .. })).map(|index| index + 1).unwrap_or(0);
+    layout.starts.push(position);
+    layout.ends.push(position + last_key + 1);
+    position += 1 + section.lines.len();
+    }
+let units: Vec<Vec<Token>> = layout.starts.iter().zip(&layout.ends).map(|(start, end)| tokenize(&(lines[*start .. *end].join("\n") + "\n"))).collect();
+
+let mut groups = Vec::new();
+let valid = |group: &Group| layout.place(group, 0).is_some_and(|placement| families(&placement.copies).is_some());
+find_groups(&units, 0, units.len(), &valid, &mut groups);
+groups.sort_by_key(|group| group.start);
+
+// the template, literal lines and multiplied blocks in the order of the skin:
+let mut lists: Vec<ImportList> = Vec::new();
+let mut pieces: Vec<(Option<String>, String)> = Vec::new();        // the multiplier list of a block, and its lines
+let mut literal_start = 0;
+for group in &groups
+    {
+    // a group behind another may get less of the gap between them:
+    let Some(placement) = layout.place(group, literal_start).filter(|placement| families(&placement.copies).is_some()) else { continue; };
+    let (list, bindings, tokens) = bind_group(&placement.copies, &mut lists);
+    let begin = placement.begin;
+    if literal_start < begin { pieces.push((None, template_lines(&lines[literal_start .. begin], placeholder))); }
+    let body: String = tokens.iter().zip(&bindings).map(|(token, binding)| match binding
+        {
+        Some(binding) => render_binding(binding, &lists, placeholder),
+        None          => token.text.clone(),
+        }).collect();
+    let body: Vec<String> = body.lines().map(|line| line.to_string()).collect();
+    pieces.push((Some(lists[list].name.clone()), template_lines(&body, placeholder)));
+    literal_start = placement.end;
+    }
+if literal_start < lines.len() { pieces.push((None, template_lines(&lines[literal_start ..], placeholder))); }
+
+let mut template = String::new();
+let mut syntax_options = Vec::new();
+if *placeholder != PLACEHOLDER_DELIMITERS[0] { syntax_options.push(format!("{}=\"{} {}\"", SYNTAX_PLACEHOLDER, placeholder.0, placeholder.1)); }
+if *multiline   != MULTILINE_DELIMITERS[0]   { syntax_options.push(format!("{}=\"{} {}\"", SYNTAX_MULTILINE,   multiline.0,   multiline.1)); }
+if !syntax_options.is_empty() { template.push_str(&format!("{}{} {}{}\n", KEY_NAME_BEGIN, DIRECTIVE_SYNTAX, syntax_options.join(" "), KEY_NAME_END)); }
+
+template.push_str(&format!("{} imported from '{}.ini' by arise import, evolves into the same file again\n", COMMENT_SINGLELINE, stem_p));
+template.push_str(&format!("{} {} {}\n", CONTROL_FRAME, OPERATOR_ASSIGN, FRAME_NONE));
+template.push_str(&format!("{} {} \"\"\n", CONTROL_SEPARATOR, OPERATOR_ASSIGN));
+if line_ending == LineEnding::CrLf { template.push_str(&format!("{} {} crlf\n", CONTROL_LINE_ENDING, OPERATOR_ASSIGN)); }
+match tree_p.encoding
+    {
+    Encoding::Utf8    => {},
+    Encoding::Utf8Bom => template.push_str(&format!("{} {} utf-8-bom\n", CONTROL_ENCODING, OPERATOR_ASSIGN)),
+    Encoding::Utf16Le => template.push_str(&format!("{} {} utf-16\n",    CONTROL_ENCODING, OPERATOR_ASSIGN)),
+    }
+
+let mut data_files = Vec::new();
+if !lists.is_empty()
+    {
+    template.push_str(&format!("\n{}\n", MULTIPLIER_LIST_BEGIN));
+    for list in &lists
+        {
+        let inline = list.fields.is_empty() && list.items.iter().all(|item| item.chars().count() == 1 && item != "'");
+        let value = if inline
+            {
+            format!("'{}'", list.items.concat())
+            }
+        else
+            {
+            let name = format!("{}_{}.csv", stem_p, list.name);
+            let mut csv = std::iter::once(&list.name).chain(list.fields.iter().map(|(field, _)| field)).cloned().collect::<Vec<_>>().join(",");
+            csv.push('\n');
+            for (index, item) in list.items.iter().enumerate()
+                {
+                let row: Vec<&str> = std::iter::once(item.as_str()).chain(list.fields.iter().map(|(_, values)| values[index].as_str())).collect();
+                csv.push_str(&row.join(","));
+                csv.push('\n');
+                }
+            let value = format!("@data(\"{}\")", name);
+            data_files.push((name, csv));
+            value
+            };
+        template.push_str(&format!("    {}{}{} {} {}\n", placeholder.0, list.name, placeholder.1, OPERATOR_ASSIGN, value));
+        }
+    template.push_str(&format!("{}\n", MULTIPLIER_LIST_END));
+    }
+
+let mut block_names: Vec<String> = Vec::new();
+let last = pieces.len().saturating_sub(1);
+for (index, (repeat, body)) in pieces.iter().enumerate()
+    {
+    let (begin, end) = match repeat
+        {
+        Some(list) =>
+            {
+            let name = unique_name(&format!("{}_{}", list, block_kind(body)), &block_names);
+            block_names.push(name.clone());
+            (format!("{} {} {}={}>", BLOCK_BEGIN, name, BLOCK_OPTION_REPEAT, list), BLOCK_END.to_string())
+            },
+        None if index == 0    => (SECTION_HEADER_BEGIN.to_string(), SECTION_HEADER_END.to_string()),
+        None if index == last => (SECTION_FOOTER_BEGIN.to_string(), SECTION_FOOTER_END.to_string()),
+        None                  =>
+            {
+            let name = unique_name("part", &block_names);
+            block_names.push(name.clone());
+            (format!("{} {}>", BLOCK_BEGIN, name), BLOCK_END.to_string())
+            },
+        };
+    template.push_str(&format!("\n{}\n{}{}\n", begin, body, end));
+    }
+
+Ok(ImportedSkin { template, data_files })
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  check_line_endings   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`RETURNS:    `** **` Result -->    `** - OK(the line ending of all lines)   
+/// **`            `** **`     or -->    `** - Error, for mixed line endings or a missing line break at the end   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// An evolved skin has the same line ending in all lines, and a line break at its end.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn check_line_endings(file_p: &Path, tree_p: &SyntaxTree) -> Result<LineEnding, Diagnostic>
+{
+let Some(first) = tree_p.lines().next() else { return Err(Diagnostic::error(file_p, 0, "the skin is empty, there is nothing to import")); };
+let name = |ending: LineEnding| if ending == LineEnding::CrLf { "CR LF" } else { "LF" };
+for line in tree_p.lines()
+    {
+    if line.ending == LineEnding::None
+        {
+        return Err(Diagnostic::error(file_p, line.number, "the last line has no line break")
+                              .with_help("add a line break at the end of the file, every evolved line ends with one"));
+        }
+    if line.ending != first.ending
+        {
+        return Err(Diagnostic::error(file_p, line.number, format!("the line ends with {}, but the first one with {}", name(line.ending), name(first.ending)))
+                              .with_help("all evolved lines end the same way, convert the file to one line ending first"));
+        }
+    }
+Ok(first.ending)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  compare_skins   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file_p        `** the skin, named in diagnostics   
+/// **`            `** **` original_p    `** the bytes of the skin   
+/// **`            `** **` evolved_p     `** the bytes of the evolved template   
+/// **`RETURNS:    `** **` Result -->    `** - OK, if both are the same   
+/// **`            `** **`     or -->    `** - Error, naming the first line which differs   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn compare_skins(file_p: &Path, original_p: &[u8], evolved_p: &[u8]) -> Result<(), Diagnostic>
+{
+if original_p == evolved_p { return Ok(()); }
+
+let original: Vec<String> = SyntaxTree::decode(original_p).map(|tree| tree.lines().map(|line| line.kind.to_string()).collect()).unwrap_or_default();
+let evolved:  Vec<String> = SyntaxTree::decode(evolved_p).map(|tree| tree.lines().map(|line| line.kind.to_string()).collect()).unwrap_or_default();
+let index = original.iter().zip(&evolved).position(|(original, evolved)| original != evolved).unwrap_or(original.len().min(evolved.len()));
+Err(Diagnostic::error(file_p, index + 1, "the inferred template doesn't evolve into the skin again, nothing is written")
+               .with_help(format!("the line is '{}', but evolves into '{}'",
+                                  original.get(index).map(String::as_str).unwrap_or("<end of file>"),
+                                  evolved.get(index).map(String::as_str).unwrap_or("<end of file>"))))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  tokenize   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text          `** the lines of a unit   
+/// **`RETURNS:    `** **` Vec           `** words, numbers and the text between them, all together are the text again   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Camel case names are split into their words, so the `C` of `DriveC` is a token of its own.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn tokenize(text: &str) -> Vec<Token>
+{
+let mut tokens: Vec<Token> = Vec::new();
+let mut previous: Option<char> = None;
+for c in text.chars()
+    {
+    let class = if c.is_alphabetic() { TokenClass::Word } else if c.is_ascii_digit() { TokenClass::Number } else { TokenClass::Other };
+    let camel = class == TokenClass::Word && c.is_uppercase() && previous.is_some_and(|previous| previous.is_lowercase());
+    match tokens.last_mut()
+        {
+        Some(last) if last.class == class && !camel => last.text.push(c),
+        _                                           => tokens.push(Token { class, text: c.to_string() }),
+        }
+    previous = Some(c);
+    }
+tokens
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  find_groups   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` units_p       `** the tokens of all units   
+/// **`            `** **` begin_p       `** the first unit to look at   
+/// **`            `** **` end_p         `** behind the last unit to look at   
+/// **`            `** **` valid_p       `** tells if copies of the same units can be a block, with the lines between them   
+/// **`            `** **` groups_p      `** gets the groups found   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Takes the group covering the most units, then looks for further groups in front of and behind it.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | validity of a group decided by the caller   
+/// ___________________________________________________________________________________________________________________________
+fn find_groups(units_p: &[Vec<Token>], begin_p: usize, end_p: usize, valid_p: &dyn Fn(&Group) -> bool, groups_p: &mut Vec<Group>)
+{
+let mut best: Option<Group> = None;
+for size in 1 ..= (end_p - begin_p) / MIN_COPIES
+    {
+    for start in begin_p ..= end_p - size * MIN_COPIES
+        {
+        let mut copies = 1;
+        while start + (copies + 1) * size <= end_p && (0 .. size).all(|unit| same_shape(&units_p[start + unit], &units_p[start + copies * size + unit]))
+            {
+            copies += 1;
+            }
+        let group = Group { start, size, copies };
+        if copies >= MIN_COPIES && best.is_none_or(|best| size * copies > best.size * best.copies) && valid_p(&group)
+            {
+            best = Some(group);
+            }
+        }
+    }
+
+let Some(group) = best else { return; };
+groups_p.push(group);
+find_groups(units_p, begin_p, group.start, valid_p, groups_p);
+find_groups(units_p, group.start + group.size * group.copies, end_p, valid_p, groups_p);
+}
+
+fn same_shape(first: &[Token], second: &[Token]) -> bool
+{
+first.len() == second.len() && first.iter().zip(second).all(|(first, second)|
+    first.class == second.class && (first.class != TokenClass::Other || first.text == second.text))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  families   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` copies_p      `** the tokens of the copies   
+/// **`RETURNS:    `** **` Option -->    `** - Some(the tokens of the first copy, and for each of them the number of its family,   
+/// **`            `** **`               `**   None for tokens the same in all copies; the values of the families across the copies)   
+/// **`            `** **`       -->    `** - None, if the copies don't differ, differ too much, or the first family repeats items   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Varying tokens with the same values in all copies belong to the same family, they get the same placeholder.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | the tokens of the copies with the lines around them, instead of units   
+/// ___________________________________________________________________________________________________________________________
+#[allow(clippy::type_complexity)]
+fn families(copies_p: &[Vec<Token>]) -> Option<(Vec<Token>, Vec<Option<usize>>, Vec<Vec<String>>)>
+{
+let tokens: Vec<Token> = copies_p.first()?.clone();
+
+let mut members: Vec<Option<usize>> = Vec::new();
+let mut values:  Vec<Vec<String>>  = Vec::new();
+let copies = copies_p;
+for position in 0 .. tokens.len()
+    {
+    let sequence: Vec<String> = copies.iter().map(|copy| copy[position].text.clone()).collect();
+    if sequence.iter().all(|value| *value == sequence[0]) { members.push(None); continue; }
+    match values.iter().position(|known| *known == sequence)
+        {
+        Some(family) => members.push(Some(family)),
+        None         => { members.push(Some(values.len())); values.push(sequence); },
+        }
+    }
+
+let first = values.first()?;
+let distinct = first.iter().enumerate().all(|(index, value)| !first[.. index].contains(value));
+if values.len() > MAX_FAMILIES || !distinct { return None; }
+Some((tokens, members, values))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  bind_group   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` copies_p      `** the tokens of the copies   
+/// **`            `** **` lists_p       `** the multiplier lists, gets a new one, or new fields of a list with the same items   
+/// **`RETURNS:    `** **` (.., .., ..)  `** the multiplier list, the binding of every token of the first copy (None for the   
+/// **`            `** **`               `** constant ones) and the tokens   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The first family which is no arithmetic progression gives the items of the multiplier list.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | the tokens of the copies, instead of units   
+/// ___________________________________________________________________________________________________________________________
+fn bind_group(copies_p: &[Vec<Token>], lists_p: &mut Vec<ImportList>) -> (usize, Vec<Option<Binding>>, Vec<Token>)
+{
+let (tokens, members, values) = families(copies_p).unwrap_or_default();     // checked by the caller
+// named by the word in front of it, like 'Drive' in 'Drive=C:' or '[Measure_Drive_C]':
+let name_of = |family: usize|
+    {
+    let word = (0 .. tokens.len()).filter(|position| members[*position] == Some(family)).find_map(|position|
+        {
+        let mut before = position.checked_sub(1)?;
+        if tokens[before].class == TokenClass::Other && tokens[before].text.chars().count() == 1 { before = before.checked_sub(1)?; }
+        let token = &tokens[before];
+        (members[before].is_none() && token.class == TokenClass::Word).then(|| token.text.to_lowercase())
+        });
+    let name: String = word.unwrap_or_default().chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    if name.is_empty() { DEFAULT_LIST.to_string() } else { name }
+    };
+
+let expressions: Vec<Option<String>> = values.iter().map(|sequence| progression(sequence)).collect();
+let primary = expressions.iter().position(Option::is_none).unwrap_or(0);
+
+let list = match lists_p.iter().position(|list| list.items == values[primary])
+    {
+    Some(list) => list,
+    None       =>
+        {
+        let known: Vec<String> = lists_p.iter().map(|list| list.name.clone()).collect();
+        lists_p.push(ImportList { name: unique_name(&name_of(primary), &known), items: values[primary].clone(), fields: Vec::new() });
+        lists_p.len() - 1
+        },
+    };
+
+let bindings: Vec<Binding> = values.iter().enumerate().map(|(family, sequence)|
+    {
+    if family == primary { return Binding::Item(list); }
+    if let Some(expression) = &expressions[family] { return Binding::Expression(expression.clone()); }
+    let fields = &mut lists_p[list].fields;
+    if let Some((field, _)) = fields.iter().find(|(_, known)| known == sequence) { return Binding::Field(list, field.clone()); }
+    let mut known: Vec<String> = fields.iter().map(|(field, _)| field.clone()).collect();
+    known.push(lists_p[list].name.clone());
+    let field = unique_name(&name_of(family), &known);
+    lists_p[list].fields.push((field.clone(), sequence.clone()));
+    Binding::Field(list, field)
+    }).collect();
+
+(list, members.iter().map(|member| member.map(|family| bindings[family].clone())).collect(), tokens)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  progression   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` values_p      `** the values of a family, one per copy   
+/// **`RETURNS:    `** **` Option -->    `** - Some(the expression giving the values from the index of the item)   
+/// **`            `** **`       -->    `** - None, if the values are no integers with the same distance   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn progression(values_p: &[String]) -> Option<String>
+{
+let numbers: Vec<i64> = values_p.iter().map(|value| value.parse::<i64>().ok().filter(|number| number.to_string() == *value)).collect::<Option<_>>()?;
+let step = numbers.get(1)? - numbers[0];
+if numbers.windows(2).any(|pair| pair[1] - pair[0] != step) { return None; }
+
+let base = numbers[0];
+Some(match (base, step)
+    {
+    (0, 1) => META_INDEX.to_string(),
+    (1, 1) => META_NUMBER.to_string(),
+    (0, _) => format!("= {} * {}", META_INDEX, step),
+    (_, _) if step < 0 => format!("= {} - {} * {}", base, META_INDEX, -step),
+    (_, _) => format!("= {} + {} * {}", base, META_INDEX, step),
+    })
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  render_binding / template_lines / block_kind / unique_name   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Writing the template: placeholders, lines of the skin written as they are, names of blocks and lists.   
+/// Arise ignores the indentation of template lines, so it is written as a quoted text like `<:'    ':>[Meter]`,   
+/// as are lines which would look like the begin or end of a block.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn render_binding(binding_p: &Binding, lists_p: &[ImportList], placeholder_p: &(&str, &str)) -> String
+{
+let inner = match binding_p
+    {
+    Binding::Item(list)         => lists_p[*list].name.clone(),
+    Binding::Field(list, field) => format!("{}.{}", lists_p[*list].name, field),
+    Binding::Expression(text)   => text.clone(),
+    };
+format!("{}{}{}", placeholder_p.0, inner, placeholder_p.1)
+}
+
+fn template_lines(lines_p: &[String], placeholder_p: &(&str, &str)) -> String
+{
+let mut text = String::new();
+for line in lines_p
+    {
+    let content = line.trim_start();
+    let mut quoted = line[.. line.len() - content.len()].to_string();
+    if content.starts_with('<') { quoted.push('<'); }
+    if quoted.is_empty() { text.push_str(line); }
+    else                 { text.push_str(&format!("{}'{}'{}{}", placeholder_p.0, quoted, placeholder_p.1, &line[quoted.len() ..])); }
+    text.push('\n');
+    }
+text
+}
+
+fn block_kind(body_p: &str) -> &'static str
+{
+let sections: Vec<&str> = body_p.lines().map(str::trim).filter(|line| line.starts_with('[')).collect();
+if      !sections.is_empty() && sections.iter().all(|section| section.starts_with("[Measure")) { "measures" }
+else if !sections.is_empty() && sections.iter().all(|section| section.starts_with("[Meter"))   { "meters" }
+else                                                                                            { "sections" }
+}
+
+fn unique_name(name_p: &str, known_p: &[String]) -> String
+{
+let mut name = name_p.to_string();
+let mut number = 1;
+while known_p.contains(&name)
+    {
+    number += 1;
+    name = format!("{}{}", name_p, number);
+    }
+name
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for import   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  fn import(text: &str) -> Result<ImportedSkin, Diagnostic>
+  {
+  infer_template(Path::new("Drives.ini"), &SyntaxTree::parse(text), "Drives")
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** drives_are_collapsed_into_a_block()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that copies of sections become a multiplied block, which evolves into the skin again   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn drives_are_collapsed_into_a_block()
+  {
+  let mut skin = String::from("; Drives\r\n[Rainmeter]\r\nUpdate=1000\r\n\r\n");
+  for (index, drive) in "CDEF".chars().enumerate()
+      {
+      skin.push_str(&format!("; drive {}\r\n[Measure_Drive_{}_Total]\r\nMeasure=FreeDiskSpace\r\nDrive={}:\r\n\r\n", drive, drive, drive));
+      skin.push_str(&format!("[Meter_Drive_{}]\r\n  Meter=String\r\n  Y={}\r\n\r\n", drive, 10 + index * 20));
+      }
+  skin.push_str("[MeterEnd]\r\nMeter=Image\r\n");
+
+  let imported = import(&skin).unwrap();
+  assert!(imported.data_files.is_empty());
+  assert!(imported.template.contains("<:drive:> := 'CDEF'"));
+  assert!(imported.template.contains("<block drive_sections repeat=drive>\n\n; drive <:drive:>\n[Measure_Drive_<:drive:>_Total]\n"));
+  assert!(imported.template.contains("<:'  ':>Y=<:= 10 + #index * 20:>\n"));
+  assert!(imported.template.contains("ARISE_LINE_ENDING := crlf\n"));
+  assert_eq!(evolve_skin(Path::new("Drives.arise"), &imported.template).unwrap(), skin.as_bytes());
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** fields_go_into_a_data_file()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that further varying tokens become fields of the items, and other delimiters if needed   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn fields_go_into_a_data_file()
+  {
+  let skin = "[MeterDiskSys]\nText=System <:\n[MeterDiskData]\nText=Data <:\n[MeterDiskGames]\nText=Games <:\n";
+  let imported = import(skin).unwrap();
+  assert!(imported.template.starts_with("<:syntax placeholder=\"{{ }}\":>\n"));
+  assert!(imported.template.contains("{{disk}} := @data(\"Drives_disk.csv\")"));
+  assert!(imported.template.contains("<block disk_meters repeat=disk>\n[MeterDisk{{disk}}]\nText={{disk.text}} <:\n<block end>"));
+  assert_eq!(imported.data_files, vec![("Drives_disk.csv".to_string(), "disk,text\nSys,System\nData,Data\nGames,Games\n".to_string())]);
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** mixed_line_endings_are_reported()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that skins an evolved template can't give again are reported   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn mixed_line_endings_are_reported()
+  {
+  assert_eq!(import("[Rainmeter]\r\nUpdate=1000\n").unwrap_err().line, 2);
+  assert_eq!(import("[Rainmeter]\nUpdate=1000").unwrap_err().line, 2);
+  assert_eq!(import("").unwrap_err().line, 0);
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** evolved_storagemon_is_imported_again()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that the evolved StorageMon skin gives one drive list over its measures and its meters, with   
+  /// **`            `** the separator comments inside the blocks   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn evolved_storagemon_is_imported_again()
+  {
+  let arise_file = Path::new("input/StorageMon.arise");
+  let skin = evolve_skin(arise_file, &fs::read_to_string(arise_file).unwrap()).unwrap();
+  let imported = infer_template(Path::new("StorageMon.ini"), &SyntaxTree::decode(&skin).unwrap(), "StorageMon").unwrap();
+
+  assert!(imported.data_files.is_empty());
+  assert!(imported.template.contains("<multiplier list begin>\n    <:drive:> := 'CDEFGHIJKLMNOPQRSTUVWXYZ'\n<multiplier list end>"));
+  let blocks: Vec<&str> = imported.template.lines().filter(|line| line.starts_with("<block ") && *line != "<block end>").collect();
+  assert_eq!(blocks, ["<block drive_measures repeat=drive>", "<block part>", "<block drive_meters repeat=drive>"]);
+  assert!(imported.template.contains("<block drive_measures repeat=drive>\n;-< <:drive:>: >---"));
+  assert!(imported.template.contains("<block drive_meters repeat=drive>\n;-< <:drive:>: >---"));
+  assert!(imported.template.contains("<header end>") && !imported.template.contains("[Measure_Drive_C_Total]"));
+  assert_eq!(evolve_skin(Path::new("StorageMon.arise"), &imported.template).unwrap(), skin);
+  }
+
+} // End of: mod test
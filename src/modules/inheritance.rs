@@ -0,0 +1,341 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** inheritance.rs 🦀   
+//! **`DESCRIPTION:`** inheritance of Rainmeter sections: the keys of a base section are copied into the sections   
+//! derived from it, the base section itself is not written into the skin   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | works on the INI document of ini.rs   
+//! 0.3      | 2026-10-19 | Clunion   | a section inherits from the nearest base in front of it, bases in multiplied blocks   
+//! 0.4      | 2026-10-19 | Clunion   | warnings for copies of a base replaced before anything inherits from them   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! [DriveBase]                                 <-- used as a base only, so it is not written into the skin   
+//! Measure=FreeDiskSpace   
+//! Drive=<:drive:>:   
+//! IgnoreRemovable=0   
+//!   
+//! [Measure_Drive_<:drive:>_Free : DriveBase]  <-- gets Measure, Drive and IgnoreRemovable of DriveBase   
+//! UpdateDivider=#DriveSpace_UpdateDivider#   
+//! IgnoreRemovable=1                           <-- replaces the inherited key   
+//!```
+//! Unlike Rainmeter's MeterStyle this works for all keys and for measures as well. A base may be derived from   
+//! another base. The inherited keys come first, keys of the derived section replace inherited keys of the same name.   
+//! A base in a multiplied block is evolved once per item, every section inherits from the nearest copy in front of it.   
+//! The ':' needs a space on both sides, so names like `[Drive C:]` stay names; existing skins never have bases.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use std::path::Path;
+
+use crate::modules::ini::{IniDocument, IniSection, IniKey, IniLine};
+use crate::modules::expansion::SectionOrigin;
+use crate::modules::duplicates::{locate_origins, describe, warning_at};
+use crate::modules::diagnostics::Diagnostic;
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const BASE_SEPARATOR : char = ':';
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+//___ none ___
+
+//___ METHODS: ________________________________________________________________________________________________________________
+//___ none ___
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  resolve_inheritance   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` skin           `** the evolved skin, with sections like `[Name : Base]`   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the skin with the inherited keys copied and without the base sections)   
+/// **`            `** **`     or -->     `** - Error, for unknown base sections and sections inheriting from themselves   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The comments and blank lines behind the last key of a base section are kept, they are in front of the next section.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | works on the INI document instead of the text   
+/// 1.2     | 2026-10-19 | Clunion   | the nearest base instead of the first one   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn resolve_inheritance(mut skin: IniDocument) -> Result<IniDocument, String>
+{
+if skin.sections.iter().all(|section| section.base.is_none()) { return Ok(skin); }
+let sections = std::mem::take(&mut skin.sections);
+let bases: Vec<String> = sections.iter().filter_map(|section| section.base.as_ref().map(|base| base.to_lowercase())).collect();
+
+for (index, section) in sections.iter().enumerate()
+    {
+    if bases.contains(&section.name.to_lowercase())
+        {
+        let trailing = section.lines[section.key_count() ..].iter().cloned();
+        match skin.sections.last_mut()
+            {
+            Some(previous) => previous.lines.extend(trailing),
+            None           => skin.preamble.extend(trailing),
+            }
+        continue;
+        }
+
+    let mut resolved = section.clone();
+    if section.base.is_some()
+        {
+        let own: Vec<String> = section.keys().map(|key| key.name.to_lowercase()).collect();
+        let mut lines: Vec<IniLine> = inherited_keys(&sections, index)?.into_iter()
+                                      .filter(|key| !own.contains(&key.name.to_lowercase()))
+                                      .map(IniLine::Key)
+                                      .collect();
+        lines.append(&mut resolved.lines);
+        resolved.lines = lines;
+        resolved.base  = None;
+        resolved.drop_header();
+        }
+    skin.sections.push(resolved);
+    }
+
+debug!("resolved the inheritance of {} sections from {} base sections", sections.iter().filter(|section| section.base.is_some()).count(), bases.len());
+Ok(skin)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  conflicting_bases   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file           `** the arise file, named for sections without a known origin   
+/// **`            `** **` skin           `** the evolved skin, before its inheritance is resolved   
+/// **`            `** **` origins        `** where the sections come from, in the order they were evolved   
+/// **`RETURNS:    `** **` Vec            `** a warning for every copy of a base which is replaced by another copy with other keys   
+/// **`            `** **`                `** before any section inherits from it   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Base sections are not written into the skin, so the duplicate check can't see them. Repeated copies of a base are   
+/// fine as long as each one is used, like a base in a multiplied block with its derived sections behind it.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn conflicting_bases(file: &Path, skin: &IniDocument, origins: &[SectionOrigin]) -> Vec<Diagnostic>
+{
+let sections = &skin.sections;
+let located  = locate_origins(skin, origins);
+let bases: Vec<String> = sections.iter().filter_map(|section| section.base.as_ref().map(|base| base.to_lowercase())).collect();
+let inherits = |position: usize, name: &str| sections[position].base.as_deref().is_some_and(|base| base.eq_ignore_ascii_case(name));
+
+let mut warnings = Vec::new();
+for (index, section) in sections.iter().enumerate()
+    {
+    if !bases.contains(&section.name.to_lowercase()) { continue; }
+    let next = match (index + 1 .. sections.len()).find(|other| sections[*other].name.eq_ignore_ascii_case(&section.name))
+        {
+        Some(next) => next,
+        None       => continue,
+        };
+    let first = !(0 .. index).any(|other| sections[other].name.eq_ignore_ascii_case(&section.name));
+    if (index + 1 .. next).any(|other| inherits(other, &section.name)) || (first && (0 .. index).any(|other| inherits(other, &section.name))) { continue; }
+
+    let keys = |base: &IniSection| base.keys().map(|key| (key.name.to_lowercase(), key.value.clone())).collect::<Vec<(String, String)>>();
+    if keys(section) == keys(&sections[next]) { continue; }
+    warnings.push(warning_at(file, located[index], format!("base section [{}] is evolved again before any section inherits from it", section.name))
+                  .with_help(format!("the keys of the copy from {} are never used, the copy from {} replaces it; put the sections derived from it behind it",
+                                     describe(located[index]), describe(located[next]))));
+    }
+
+debug!("checked the base sections of {} sections, {} warning(s)", sections.len(), warnings.len());
+warnings
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  inherited_keys   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` sections       `** all sections of the skin   
+/// **`            `** **` index          `** the derived section   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the keys of all bases, the outermost base first, overridden ones removed)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | keys of the INI document instead of lines   
+/// 1.2     | 2026-10-19 | Clunion   | every base is looked up from the position of the section derived from it   
+/// ___________________________________________________________________________________________________________________________
+fn inherited_keys(sections: &[IniSection], index: usize) -> Result<Vec<IniKey>, String>
+{
+let section = &sections[index];
+let mut chain: Vec<&IniSection> = Vec::new();
+let mut position = index;
+while let Some(base) = &sections[position].base
+    {
+    position = match base_position(sections, position, base)
+        {
+        Some(found) => found,
+        None        => return Err(format!("section [{}] inherits from the unknown section [{}]", sections[position].name, base)),
+        };
+    let current = &sections[position];
+    if current.name.eq_ignore_ascii_case(&section.name) || chain.iter().any(|known| known.name.eq_ignore_ascii_case(&current.name))
+        {
+        let mut names: Vec<&str> = vec![section.name.as_str()];
+        names.extend(chain.iter().map(|known| known.name.as_str()));
+        names.push(current.name.as_str());
+        return Err(format!("section [{}] inherits from itself: {}", section.name, names.join(" : ")));
+        }
+    chain.push(current);
+    }
+
+let mut keys: Vec<IniKey> = Vec::new();
+for base in chain.iter().rev()
+    {
+    for key in base.keys()
+        {
+        keys.retain(|known| !known.name.eq_ignore_ascii_case(&key.name));
+        keys.push(key.clone());
+        }
+    }
+Ok(keys)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  base_position   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` sections       `** all sections of the skin   
+/// **`            `** **` position       `** the section inheriting from the base   
+/// **`            `** **` name           `** the name of the base   
+/// **`RETURNS:    `** **` Option         `** the nearest section of the name in front of the position, or else the first one   
+/// **`            `** **`                `** behind it; None if there is no section of that name   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// A base in a multiplied block is evolved once per item, so the copy in front of a section is the one of its own item.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn base_position(sections: &[IniSection], position: usize, name: &str) -> Option<usize>
+{
+let named = |other: &usize| sections[*other].name.eq_ignore_ascii_case(name);
+(0 ..= position).rev().find(named).or_else(|| (position + 1 .. sections.len()).find(named))
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for inheritance   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+  use std::path::Path;
+  use crate::modules::core_logic::evolve_skin;
+
+  fn evolved(skin: &str) -> IniDocument
+  {
+  let mut document = IniDocument::default();
+  document.push_template_text(skin);
+  document
+  }
+
+  fn resolve(skin: &str) -> Result<String, String>
+  {
+  resolve_inheritance(evolved(skin)).map(|skin| skin.to_string())
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** keys_are_inherited()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks copying, overriding, chained bases and that base sections are removed with their keys only   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn keys_are_inherited()
+  {
+  let skin = "[Common]\nGroup=All\n\n[DriveBase : Common]\nMeasure=FreeDiskSpace\nIgnoreRemovable=0\n; --- end ---\n\n\
+              [Measure_C_Free : DriveBase]\nignoreremovable=1\nUpdateDivider=5\n\n[Meter]\nX=1\n";
+  assert_eq!(resolve(skin),
+             Ok("\n; --- end ---\n\n[Measure_C_Free]\nGroup=All\nMeasure=FreeDiskSpace\nignoreremovable=1\nUpdateDivider=5\n\n[Meter]\nX=1\n".to_string()));
+  assert_eq!(resolve("[A]\n  X = 1\n"), Ok("[A]\n  X = 1\n".to_string()));
+  assert_eq!(resolve("[Drive C:]\nX=1\n[B:A]\n[C :D]\n[E : Drive C:]\n"), Ok("[B:A]\n[C :D]\n[E]\nX=1\n".to_string()));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** bases_are_evolved_per_item()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that a base in a multiplied block gives every item the keys of its own copy   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn bases_are_evolved_per_item()
+  {
+  let template = "<multiplier list begin>\n<:drive:> := 'CD'\n<multiplier list end>\nARISE_FRAME := none\n\
+                  <measures begin>\n[DriveBase]\nMeasure=FreeDiskSpace\nDrive=<:drive:>:\n\n[Measure_<:drive:>_Total : DriveBase]\nTotal=1\n<measures end>\n";
+  let skin = String::from_utf8(evolve_skin(Path::new("Drives.arise"), template).unwrap()).unwrap();
+  assert!(skin.contains("\n[Measure_C_Total]\nMeasure=FreeDiskSpace\nDrive=C:\nTotal=1\n"));
+  assert!(skin.contains("\n[Measure_D_Total]\nMeasure=FreeDiskSpace\nDrive=D:\nTotal=1\n"));
+  assert!(!skin.contains("[DriveBase]"));
+  assert_eq!(resolve("[Total : Base]\n[Base]\nX=1\n[Base]\nX=2\n[Free : Base]\n"), Ok("[Total]\nX=1\n[Free]\nX=2\n".to_string()));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** inheritance_errors()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks unknown base sections and cycles   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn inheritance_errors()
+  {
+  assert_eq!(resolve("[A : Base]\nX=1\n"), Err("section [A] inherits from the unknown section [Base]".to_string()));
+  assert_eq!(resolve("[A : B]\n[B : C]\n[C : B]\n"), Err("section [A] inherits from itself: A : B : C : B".to_string()));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** conflicting_bases_are_reported()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that only copies of a base replaced before their use with other keys are reported   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn conflicting_bases_are_reported()
+  {
+  let conflicts = |skin: &str| -> Vec<String> { conflicting_bases(Path::new("t.arise"), &evolved(skin), &[]).into_iter().map(|warning| warning.message).collect() };
+  assert_eq!(conflicts("[Base]\nX=1\n[Base]\nX=2\n[A : Base]\n"), vec!["base section [Base] is evolved again before any section inherits from it".to_string()]);
+  assert!(conflicts("[Base]\nX=1\n[A : Base]\n[Base]\nX=2\n[B : Base]\n").is_empty());
+  assert!(conflicts("[A : Base]\n[Base]\nX=1\n[Base]\nX=2\n[B : Base]\n").is_empty());
+  assert!(conflicts("[Base]\nX=1\n[Base]\nx=1\n[A : Base]\n").is_empty());
+  assert!(conflicts("[Meter]\nX=1\n[Meter]\nX=2\n").is_empty());
+  }
+
+} // End of: mod test
@@ -0,0 +1,374 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** ini.rs 🦀   
+//! **`DESCRIPTION:`** the Rainmeter INI document a skin is evolved into: ordered sections with ordered keys, comments   
+//! and blank lines kept as trivia, written as text only at the end   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | renaming keys   
+//! 0.3      | 2026-10-19 | Clunion   | bases only in evolved template sections and only with spaces around ':'   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! ; -- Metainfo-Text --                       <-- preamble: the lines in front of the first section   
+//!   
+//! [Measure_Drive_C_Free : DriveBase]          <-- section, with the name and the base it inherits from (templates only)   
+//!     Measure=FreeDiskSpace                   <-- key, name and value   
+//! ; free space only                           <-- comment, trivia of the section   
+//!                                             <-- blank line, trivia as well   
+//!```
+//! Every line keeps the text it was evolved as, so writing a document nobody changed gives the same text again.   
+//! Keys added or changed later are written as `Name=Value`, sections without their text as `[Name]`.   
+//! A base is only read from the sections of a template, and only with spaces around the ':'. In existing skins   
+//! and in names like `[Drive C:]` the ':' belongs to the name.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::fmt;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::inheritance::BASE_SEPARATOR;
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const COMMENT_PREFIX : char = ';';
+pub(crate) const KEY_SEPARATOR  : char = '=';
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// A line inside a section, or in front of the first one.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum IniLine
+{
+    Key(IniKey),
+    Comment(String),    // the whole line, starting with ';' after the indentation
+    Blank(String),      // nothing but whitespace
+    Other(String),      // anything else, ignored by Rainmeter
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// A `Name=Value` line.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IniKey
+{
+    pub(crate) name:  String,
+    pub(crate) value: String,
+    text:             Option<String>,   // the line as evolved, with its indentation and spaces around '='
+}
+
+/// A section: the header `[Name]` or `[Name : Base]` and all lines up to the next header.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IniSection
+{
+    pub(crate) name:  String,
+    pub(crate) base:  Option<String>,
+    pub(crate) lines: Vec<IniLine>,
+    header:           Option<String>,   // the header line as evolved
+}
+
+/// A whole skin.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct IniDocument
+{
+    pub(crate) preamble: Vec<IniLine>,
+    pub(crate) sections: Vec<IniSection>,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl IniLine
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  parse   
+/// **`TYPE:       `**  method of IniLine   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text         `** a line, no section header   
+/// **`RETURNS:    `** **` IniLine      `** the key, comment, blank or other line   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse(text: &str) -> IniLine
+    {
+        let trimmed = text.trim_start();
+        if trimmed.is_empty()                    { return IniLine::Blank(text.to_string()); }
+        if trimmed.starts_with(COMMENT_PREFIX)   { return IniLine::Comment(text.to_string()); }
+        match trimmed.split_once(KEY_SEPARATOR)
+            {
+            Some((name, value)) if !name.trim().is_empty() && !trimmed.starts_with('[') =>
+                IniLine::Key(IniKey { name: name.trim().to_string(), value: value.trim().to_string(), text: Some(text.to_string()) }),
+            _ => IniLine::Other(text.to_string()),
+            }
+    }
+
+} // End of impl: IniLine
+
+
+impl IniKey
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  rename   
+/// **`TYPE:       `**  method of IniKey   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** the new name of the key   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The line keeps its indentation and the spaces around '='.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn rename(&mut self, name: &str)
+    {
+        if let Some(text) = &mut self.text
+            {
+            if let Some(start) = text.find(self.name.as_str()) { text.replace_range(start .. start + self.name.len(), name); }
+            }
+        self.name = name.to_string();
+    }
+
+} // End of impl: IniKey
+
+
+impl IniSection
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  parse_header / parse_template_header   
+/// **`TYPE:       `**  methods of IniSection   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text         `** a line   
+/// **`RETURNS:    `** **` Option       `** an empty section, None if the line is no section header   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// For Rainmeter everything between the brackets is the name. A section evolved from a template may name its base   
+/// behind a ':' with spaces on both sides, `[Measure_C_Free : DriveBase]`.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, split_sections of inheritance.rs   
+/// 1.1     | 2026-10-19 | Clunion   | the base only in template sections and only with spaces around ':'   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_header(text: &str) -> Option<IniSection>
+    {
+        let header = text.trim().strip_prefix('[')?.strip_suffix(']')?;
+        Some(IniSection { name: header.trim().to_string(), base: None, lines: Vec::new(), header: Some(text.to_string()) })
+    }
+
+pub(crate) fn parse_template_header(text: &str) -> Option<IniSection>
+    {
+        let mut section = IniSection::parse_header(text)?;
+        let header = section.name.clone();
+        let separator = header.char_indices().find(|(at, c)| *c == BASE_SEPARATOR
+                                                         && header[.. *at].ends_with(char::is_whitespace)
+                                                         && header[at + 1 ..].starts_with(char::is_whitespace));
+        if let Some((at, _)) = separator
+            {
+            section.name = header[.. at].trim().to_string();
+            section.base = Some(header[at + 1 ..].trim().to_string());
+            }
+        Some(section)
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  keys / key_count / drop_header   
+/// **`TYPE:       `**  methods of IniSection   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The keys in their order, the number of lines up to the last key (the lines behind it are in front of the next   
+/// section rather than part of this one), and forgetting the header text, after the name or base was changed.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn keys(&self) -> impl Iterator<Item = &IniKey>
+    {
+        self.lines.iter().filter_map(|line| match line { IniLine::Key(key) => Some(key), _ => None })
+    }
+
+pub(crate) fn key_count(&self) -> usize
+    {
+        self.lines.iter().rposition(|line| matches!(line, IniLine::Key(_))).map_or(0, |last| last + 1)
+    }
+
+pub(crate) fn drop_header(&mut self)
+    {
+        self.header = None;
+    }
+
+} // End of impl: IniSection
+
+
+impl IniDocument
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  parse   
+/// **`TYPE:       `**  method of IniDocument   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text         `** evolved Rainmeter-ini-code   
+/// **`RETURNS:    `** **` IniDocument  `** the document of all its lines   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse(text: &str) -> IniDocument
+    {
+        let mut document = IniDocument::default();
+        document.push_text(text);
+        document
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  push_line / push_text / push_template_text   
+/// **`TYPE:       `**  methods of IniDocument   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text         `** one line, or any number of lines   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Appends to the last section, a section header starts a new one. Only the lines evolved from the sections of a   
+/// template may have section headers with a base.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | push_template_text   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn push_line(&mut self, text: &str)
+    {
+        self.push(text, IniSection::parse_header(text));
+    }
+
+pub(crate) fn push_text(&mut self, text: &str)
+    {
+        for line in text.lines() { self.push_line(line); }
+    }
+
+pub(crate) fn push_template_text(&mut self, text: &str)
+    {
+        for line in text.lines() { self.push(line, IniSection::parse_template_header(line)); }
+    }
+
+fn push(&mut self, text: &str, header: Option<IniSection>)
+    {
+        if let Some(section) = header
+            {
+            self.sections.push(section);
+            return;
+            }
+        let line = IniLine::parse(text);
+        match self.sections.last_mut()
+            {
+            Some(section) => section.lines.push(line),
+            None          => self.preamble.push(line),
+            }
+    }
+
+} // End of impl: IniDocument
+
+
+impl fmt::Display for IniLine
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+            {
+            IniLine::Key(IniKey { text: Some(text), .. })   => write!(f, "{}", text),
+            IniLine::Key(IniKey { name, value, text: None }) => write!(f, "{}{}{}", name, KEY_SEPARATOR, value),
+            IniLine::Comment(text) | IniLine::Blank(text) | IniLine::Other(text) => write!(f, "{}", text),
+            }
+    }
+}
+
+impl fmt::Display for IniSection
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match (&self.header, &self.base)
+            {
+            (Some(header), _)  => writeln!(f, "{}", header)?,
+            (None, Some(base)) => writeln!(f, "[{} {} {}]", self.name, BASE_SEPARATOR, base)?,
+            (None, None)       => writeln!(f, "[{}]", self.name)?,
+            }
+        for line in &self.lines { writeln!(f, "{}", line)?; }
+        Ok(())
+    }
+}
+
+impl fmt::Display for IniDocument
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        for line in &self.preamble    { writeln!(f, "{}", line)?; }
+        for section in &self.sections { write!(f, "{}", section)?; }
+        Ok(())
+    }
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for ini   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** documents_are_built_and_written()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the structure of a document, and that its text is kept until something is changed   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn documents_are_built_and_written()
+  {
+  let text = "; -- Metainfo-Text --\n\n[Rainmeter]\n    Update = 1000\n; ok\n\n[Measure_C : DriveBase]\nDrive=C:\nnot a key\n";
+  let mut document = IniDocument::parse(text);
+  assert_eq!(document.preamble, vec![IniLine::Comment("; -- Metainfo-Text --".to_string()), IniLine::Blank(String::new())]);
+  assert_eq!(document.sections.iter().map(|section| section.name.as_str()).collect::<Vec<&str>>(), vec!["Rainmeter", "Measure_C : DriveBase"]);
+  assert_eq!(document.sections[1].base, None);
+  assert_eq!(document.sections[0].keys().map(|key| (key.name.as_str(), key.value.as_str())).collect::<Vec<_>>(), vec![("Update", "1000")]);
+  assert_eq!(document.sections[0].key_count(), 1);
+  assert_eq!(document.sections[1].lines[1], IniLine::Other("not a key".to_string()));
+  assert_eq!(document.to_string(), text);
+
+  let mut evolved = IniDocument::default();
+  evolved.push_template_text("[Measure_C : DriveBase]\n[Drive C:]\n[U:C]\n[ : Base]\n");
+  assert_eq!(evolved.sections.iter().map(|section| (section.name.as_str(), section.base.as_deref())).collect::<Vec<_>>(),
+             vec![("Measure_C", Some("DriveBase")), ("Drive C:", None), ("U:C", None), (": Base", None)]);
+
+  document.sections[1].drop_header();
+  document.sections[1].lines[0] = IniLine::Key(IniKey { name: "Drive".to_string(), value: "D:".to_string(), text: None });
+  document.push_line("[Meter]");
+  assert!(document.to_string().ends_with("[Measure_C : DriveBase]\nDrive=D:\nnot a key\n[Meter]\n"));
+  }
+
+} // End of: mod test
@@ -0,0 +1,394 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+#![allow(dead_code)]                // not used by evolving, but by the import of existing skins
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** ini_syntax.rs 🦀   
+//! **`DESCRIPTION:`** a lossless parser for existing Rainmeter ini-files: the concrete syntax tree keeps every   
+//! character, so it is written back byte for byte   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//!     [Rainmeter]␍␊                   <-- Section: indent "    ", name "Rainmeter", CR LF   
+//! Update  =  1000  ␊                  <-- Key: name "Update", separator "  =  ", value "1000", trailing "  ", LF   
+//! DynamicWindowSize=␊                 <-- Key without a value   
+//! Update=500␊                         <-- a duplicate key, kept as it is   
+//!   ; the meters                      <-- Comment: indent "  ", the comment, no line ending at the end of the file   
+//!```
+//! Lines are classified like Rainmeter does: a trimmed line in brackets is a section header, a line starting with ';'   
+//! a comment, a line with '=' a key, everything else is text Rainmeter ignores. Files may be UTF-8, with or without   
+//! BOM, or UTF-16 LE with BOM, as Rainmeter writes them.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::fmt;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::ini::{IniDocument, COMMENT_PREFIX, KEY_SEPARATOR};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+const UTF16_LE_BOM : [u8; 2] = [0xFF, 0xFE];
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// The encoding of an ini-file, kept to write it back the same way.   
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Encoding
+{
+    #[default]
+    Utf8,
+    Utf8Bom,
+    Utf16Le,        // always with BOM
+}
+
+/// The end of a line: LF, CR LF, or none for the last line of a file without a final line break.   
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum LineEnding
+{
+    #[default]
+    Lf,
+    CrLf,
+    None,
+}
+
+/// A line, split into the parts Rainmeter sees and the whitespace around them.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SyntaxKind
+{
+    Section { indent: String, name: String, trailing: String },                                     // name: everything between the brackets
+    Key     { indent: String, name: String, separator: String, value: String, trailing: String },    // separator: '=' with the whitespace around it
+    Comment { indent: String, text: String },                                                        // text: starting with ';'
+    Blank   { whitespace: String },
+    Text    { text: String },                                                                        // ignored by Rainmeter
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// A line of the file with its line number (counting from 1).   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SyntaxLine
+{
+    pub(crate) number: usize,
+    pub(crate) kind:   SyntaxKind,
+    pub(crate) ending: LineEnding,
+}
+
+/// A section header and the lines up to the next one.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SyntaxSection
+{
+    pub(crate) header: SyntaxLine,
+    pub(crate) lines:  Vec<SyntaxLine>,
+}
+
+/// A whole ini-file.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SyntaxTree
+{
+    pub(crate) encoding: Encoding,
+    pub(crate) preamble: Vec<SyntaxLine>,      // the lines in front of the first section
+    pub(crate) sections: Vec<SyntaxSection>,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl LineEnding
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  as_str   
+/// **`TYPE:       `**  method of LineEnding   
+/// ___________________________________________________________________________________________________________________________
+/// **`RETURNS:    `** **` &str         `** the characters of the line ending   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn as_str(&self) -> &'static str
+    {
+        match self
+            {
+            LineEnding::Lf   => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::None => "",
+            }
+    }
+
+} // End of impl: LineEnding
+
+
+impl SyntaxLine
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  parse   
+/// **`TYPE:       `**  method of SyntaxLine   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` number       `** the line number, counting from 1   
+/// **`            `** **` text         `** the line without its line ending   
+/// **`            `** **` ending       `** the line ending   
+/// **`RETURNS:    `** **` SyntaxLine   `** the classified line, all parts together are the text again   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse(number: usize, text: &str, ending: LineEnding) -> SyntaxLine
+    {
+        let content = text.trim_start();
+        let indent  = text[.. text.len() - content.len()].to_string();
+        let body    = content.trim_end();
+        let trailing = content[body.len() ..].to_string();
+
+        let kind = if body.is_empty()
+            {
+            SyntaxKind::Blank { whitespace: text.to_string() }
+            }
+        else if body.starts_with('[') && body.ends_with(']') && body.len() > 2
+            {
+            SyntaxKind::Section { indent, name: body[1 .. body.len() - 1].to_string(), trailing }
+            }
+        else if body.starts_with(COMMENT_PREFIX)
+            {
+            SyntaxKind::Comment { indent, text: content.to_string() }
+            }
+        else
+            {
+            match body.split_once(KEY_SEPARATOR)
+                {
+                Some((name, value)) if !name.trim().is_empty() =>
+                    {
+                    let key   = name.trim_end();
+                    let value_start = value.len() - value.trim_start().len();
+                    let separator = format!("{}{}{}", &name[key.len() ..], KEY_SEPARATOR, &value[.. value_start]);
+                    SyntaxKind::Key { indent, name: key.to_string(), separator, value: value[value_start ..].to_string(), trailing }
+                    },
+                _ => SyntaxKind::Text { text: text.to_string() },
+                }
+            };
+        SyntaxLine { number, kind, ending }
+    }
+
+} // End of impl: SyntaxLine
+
+
+impl SyntaxTree
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  parse   
+/// **`TYPE:       `**  method of SyntaxTree   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text         `** the text of an ini-file, a BOM included   
+/// **`RETURNS:    `** **` SyntaxTree   `** the tree of all lines, Display gives the text again   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse(text: &str) -> SyntaxTree
+    {
+        let (encoding, text) = match text.strip_prefix('\u{FEFF}')
+            {
+            Some(rest) => (Encoding::Utf8Bom, rest),
+            None       => (Encoding::Utf8, text),
+            };
+
+        let mut tree = SyntaxTree { encoding, ..SyntaxTree::default() };
+        for (index, line) in text.split_inclusive('\n').enumerate()
+            {
+            let (line, ending) = if let Some(line) = line.strip_suffix("\r\n") { (line, LineEnding::CrLf) }
+                                 else if let Some(line) = line.strip_suffix('\n') { (line, LineEnding::Lf) }
+                                 else { (line, LineEnding::None) };
+            let line = SyntaxLine::parse(index + 1, line, ending);
+            match (&line.kind, tree.sections.last_mut())
+                {
+                (SyntaxKind::Section { .. }, _) => tree.sections.push(SyntaxSection { header: line, lines: Vec::new() }),
+                (_, Some(section))              => section.lines.push(line),
+                (_, None)                       => tree.preamble.push(line),
+                }
+            }
+        tree
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  decode / encode   
+/// **`TYPE:       `**  methods of SyntaxTree   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` bytes        `** the contents of an ini-file   
+/// **`RETURNS:    `** **` Result -->   `** - OK(the tree, with the encoding of the file)   
+/// **`            `** **`     or -->   `** - Error(message), for files neither UTF-8 nor UTF-16 LE   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// `encode` gives the bytes of the file again, in the encoding it was read in.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn decode(bytes: &[u8]) -> Result<SyntaxTree, String>
+    {
+        if let Some(rest) = bytes.strip_prefix(&UTF16_LE_BOM)
+            {
+            if rest.len() % 2 != 0 { return Err("the file is UTF-16, but has an odd number of bytes".to_string()); }
+            let units: Vec<u16> = rest.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+            let text = String::from_utf16(&units).map_err(|why| format!("the file is no valid UTF-16: {}", why))?;
+            return Ok(SyntaxTree { encoding: Encoding::Utf16Le, ..SyntaxTree::parse(&text) });
+            }
+        let text = std::str::from_utf8(bytes).map_err(|why| format!("the file is neither UTF-8 nor UTF-16 with BOM: {}", why))?;
+        Ok(SyntaxTree::parse(text))
+    }
+
+pub(crate) fn encode(&self) -> Vec<u8>
+    {
+        encode_text(&self.to_string(), self.encoding)
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  lines / to_document   
+/// **`TYPE:       `**  methods of SyntaxTree   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// All lines in the order of the file, and the INI document of them (without encoding and line endings), to work on   
+/// the sections and keys like on an evolved skin.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn lines(&self) -> impl Iterator<Item = &SyntaxLine>
+    {
+        self.preamble.iter().chain(self.sections.iter().flat_map(|section| std::iter::once(&section.header).chain(section.lines.iter())))
+    }
+
+pub(crate) fn to_document(&self) -> IniDocument
+    {
+        let mut document = IniDocument::default();
+        for line in self.lines() { document.push_line(&line.kind.to_string()); }
+        document
+    }
+
+} // End of impl: SyntaxTree
+
+
+impl fmt::Display for SyntaxKind
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+            {
+            SyntaxKind::Section { indent, name, trailing }                  => write!(f, "{}[{}]{}", indent, name, trailing),
+            SyntaxKind::Key { indent, name, separator, value, trailing }    => write!(f, "{}{}{}{}{}", indent, name, separator, value, trailing),
+            SyntaxKind::Comment { indent, text }                            => write!(f, "{}{}", indent, text),
+            SyntaxKind::Blank { whitespace }                                => write!(f, "{}", whitespace),
+            SyntaxKind::Text { text }                                       => write!(f, "{}", text),
+            }
+    }
+}
+
+impl fmt::Display for SyntaxTree
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        if self.encoding != Encoding::Utf8 { write!(f, "\u{FEFF}")?; }
+        for line in self.lines() { write!(f, "{}{}", line.kind, line.ending.as_str())?; }
+        Ok(())
+    }
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  encode_text   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** the text of an ini-file, with or without a BOM   
+/// **`            `** **` encoding       `** the encoding to write it in   
+/// **`RETURNS:    `** **` Vec            `** the bytes of the file, with a BOM unless the encoding is plain UTF-8   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, split from SyntaxTree::encode for evolved skins   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn encode_text(text: &str, encoding: Encoding) -> Vec<u8>
+{
+let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+match encoding
+    {
+    Encoding::Utf8    => text.as_bytes().to_vec(),
+    Encoding::Utf8Bom => format!("\u{FEFF}{}", text).into_bytes(),
+    Encoding::Utf16Le => UTF16_LE_BOM.iter().copied().chain(text.encode_utf16().flat_map(|unit| unit.to_le_bytes())).collect(),
+    }
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for ini_syntax   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** files_are_read_losslessly()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the classification of lines, and that odd files are written back byte for byte   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn files_are_read_losslessly()
+  {
+  let text = "\u{FEFF}; skin\r\n\r\n  [ Rainmeter ]  \r\nUpdate  =  1000  \nDynamicWindowSize=\nUpdate=500\n\t\nno key here\n=1\n[]\n  ; end";
+  let tree = SyntaxTree::parse(text);
+  assert_eq!(tree.to_string(), text);
+  assert_eq!(tree.encoding, Encoding::Utf8Bom);
+  assert_eq!(tree.preamble.len(), 2);
+  assert_eq!(tree.sections.len(), 1);
+  assert_eq!(tree.sections[0].header.kind, SyntaxKind::Section { indent: "  ".to_string(), name: " Rainmeter ".to_string(), trailing: "  ".to_string() });
+  assert_eq!(tree.sections[0].lines[0].kind, SyntaxKind::Key { indent: String::new(), name: "Update".to_string(), separator: "  =  ".to_string(),
+                                                              value: "1000".to_string(), trailing: "  ".to_string() });
+  assert!(matches!(&tree.sections[0].lines[1].kind, SyntaxKind::Key { value, .. } if value.is_empty()));
+  assert!(matches!(&tree.sections[0].lines[4].kind, SyntaxKind::Text { .. }));
+  assert!(matches!(&tree.sections[0].lines[5].kind, SyntaxKind::Text { .. }));
+  assert!(matches!(&tree.sections[0].lines[6].kind, SyntaxKind::Text { .. }));
+  assert_eq!(tree.sections[0].lines[7].number, 11);
+  assert_eq!(tree.sections[0].lines[7].ending, LineEnding::None);
+
+  let document = tree.to_document();
+  assert_eq!(document.sections[0].name, "Rainmeter");
+  assert_eq!(document.sections[0].keys().map(|key| key.value.as_str()).collect::<Vec<&str>>(), vec!["1000", "", "500"]);
+
+  let mut utf16: Vec<u8> = vec![0xFF, 0xFE];
+  utf16.extend("[Meter]\r\nText=Grüße\r\n".encode_utf16().flat_map(|unit| unit.to_le_bytes()));
+  let tree = SyntaxTree::decode(&utf16).unwrap();
+  assert_eq!(tree.encoding, Encoding::Utf16Le);
+  assert_eq!(tree.encode(), utf16);
+  assert_eq!(SyntaxTree::decode(b"[A]\nX=1").unwrap().encode(), b"[A]\nX=1".to_vec());
+  assert!(SyntaxTree::decode(&[0x5B, 0xFF, 0x5D]).is_err());
+  }
+
+} // End of: mod test
@@ -0,0 +1,396 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** macros.rs 🦀   
+//! **`DESCRIPTION:`** user defined macros: groups of lines with parameters, evolved wherever they are called   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | sections evolved by macros keep their origin   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! <:macro bar(name, disk, y = 0):>                                 <-- outside of the blocks, y is optional   
+//! [Meter_<:disk:>_<:name:>]   
+//! MeasureName=Measure_Drive_<:disk:>_<:name:>   
+//! Y=<:y:>   
+//! <:endmacro:>   
+//!   
+//! <meters begin>   
+//!     <:call bar("Used", drive, 20):>   
+//!     <:call bar("Free", drive):>   
+//! <meters end>   
+//!```
+//! The arguments are evaluated where the macro is called. Inside the macro only its parameters, the control   
+//! assignments and the multiplier lists are known, so values of the current item have to be passed as arguments.   
+//! Names used inside the macro, like its loop variables, never collide with the names where it is called.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::template::{SourceLine, KEY_NAME_BEGIN, KEY_NAME_END};
+use crate::modules::expression::{Expr, parse_expression, evaluate};
+use crate::modules::expansion::{Node, Scope, parse_nodes, render_nodes};
+use crate::modules::diagnostics::Diagnostic;
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const DIRECTIVE_MACRO    : &str = "macro";
+pub(crate) const DIRECTIVE_ENDMACRO : &str = "endmacro";
+pub(crate) const DIRECTIVE_CALL     : &str = "call";
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// A parameter of a macro, with the value used if the call leaves it out.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Parameter
+{
+    pub(crate) name:    String,
+    pub(crate) default: Option<Expr>,
+}
+
+/// A `<:macro name(parameters):>` definition with its parsed lines.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MacroDefinition
+{
+    pub(crate) name:       String,
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) line:       SourceLine,
+    pub(crate) body:       Vec<Node>,
+}
+
+/// A `<:call name(arguments):>` inside a block.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MacroCall
+{
+    pub(crate) name:      String,
+    pub(crate) arguments: Vec<Expr>,
+    pub(crate) line:      SourceLine,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl MacroDefinition
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  signature   
+/// **`TYPE:       `**  method of MacroDefinition   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` String       `** the macro as it is called, like `bar(name, measure, y = ...)`, for messages   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn signature(&self) -> String
+    {
+        let parameters: Vec<String> = self.parameters.iter()
+                                      .map(|parameter| if parameter.default.is_some() { format!("{} = ...", parameter.name) } else { parameter.name.clone() })
+                                      .collect();
+        format!("{}({})", self.name, parameters.join(", "))
+    }
+
+} // End of impl: MacroDefinition
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_macro   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` line           `** the line containing `<:macro ...:>`   
+/// **`            `** **` head           `** the text after the macro keyword, like `bar(name, measure, y = 0)`   
+/// **`            `** **` body           `** the lines up to `<:endmacro:>`   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the macro with its parsed lines)   
+/// **`            `** **`     or -->     `** - Error, for an invalid name or parameter list, or invalid lines   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_macro(line: &SourceLine, head: &str, body: &[SourceLine]) -> Result<MacroDefinition, Diagnostic>
+{
+let usage = format!("write it like {}{} bar(name, measure, y = 0){}", KEY_NAME_BEGIN, DIRECTIVE_MACRO, KEY_NAME_END);
+let (name, list) = match head.split_once('(').and_then(|(name, rest)| Some((name.trim(), rest.trim_end().strip_suffix(')')?)))
+    {
+    Some((name, list)) if is_identifier(name) => (name, list),
+    _                                         => return Err(Diagnostic::error(&line.file, line.number, format!("'{}' is no valid macro definition", line.text.trim())).with_help(usage)),
+    };
+
+let mut parameters: Vec<Parameter> = Vec::new();
+for argument in split_arguments(list)
+    {
+    let (parameter, default) = match argument.split_once('=')
+        {
+        Some((parameter, default)) => (parameter.trim(), Some(default.trim())),
+        None                       => (argument.trim(), None),
+        };
+    if !is_identifier(parameter)
+        {
+        return Err(Diagnostic::error(&line.file, line.number, format!("'{}' is not a valid parameter of macro '{}'", parameter, name))
+                              .with_help("a parameter consists of letters, digits and '_', and starts with a letter"));
+        }
+    if parameters.iter().any(|known| known.name == parameter)
+        {
+        return Err(Diagnostic::error(&line.file, line.number, format!("macro '{}' has two parameters named '{}'", name, parameter)));
+        }
+    let default = match default
+        {
+        Some(text) => Some(parse_expression(text).map_err(|why| Diagnostic::error(&line.file, line.number, format!("invalid default value '{}' of parameter '{}': {}", text, parameter, why.message)))?),
+        None if parameters.iter().any(|known| known.default.is_some()) =>
+            {
+            return Err(Diagnostic::error(&line.file, line.number, format!("parameter '{}' of macro '{}' needs a default value", parameter, name))
+                                  .with_help("parameters with default values have to be the last ones"));
+            },
+        None       => None,
+        };
+    parameters.push(Parameter { name: parameter.to_string(), default });
+    }
+
+Ok(MacroDefinition { name: name.to_string(), parameters, line: line.clone(), body: parse_nodes(body)? })
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_call   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` line           `** the line containing `<:call ...:>`   
+/// **`            `** **` head           `** the text after the call keyword, like `bar("Used", "Measure_Used", 20)`   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the name of the macro and the arguments)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_call(line: &SourceLine, head: &str) -> Result<MacroCall, Diagnostic>
+{
+match parse_expression(head)
+    {
+    Ok(Expr::Call(name, arguments)) => Ok(MacroCall { name, arguments, line: line.clone() }),
+    Ok(Expr::Name(name))            => Ok(MacroCall { name, arguments: Vec::new(), line: line.clone() }),
+    Ok(_)                           => Err(Diagnostic::error(&line.file, line.number, format!("'{}' is no macro call", line.text.trim()))
+                                                  .with_help(format!("write it like {}{} bar(\"Used\", 20){}", KEY_NAME_BEGIN, DIRECTIVE_CALL, KEY_NAME_END))),
+    Err(why)                        => Err(Diagnostic::error(&line.file, line.number, format!("invalid macro call '{}': {}", head, why.message))
+                                                  .with_help(format!("the problem is at: '{}'", head.chars().skip(why.position).collect::<String>()))),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  render_call   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` call           `** the parsed call   
+/// **`            `** **` scope          `** the names known where the macro is called, for the arguments   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the evolved lines of the macro)   
+/// **`            `** **`     or -->     `** - Error, for unknown macros, wrong numbers of arguments and recursion   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The macro is evolved in a scope of its own, which knows the outermost frame of the calling scope and the parameters.   
+/// Default values are evaluated in that scope, so they may use the parameters in front of them.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | the origins of the sections of the macro are handed back to the calling scope   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn render_call(call: &MacroCall, scope: &mut Scope) -> Result<String, Diagnostic>
+{
+let line = &call.line;
+let definition = match scope.get_macro(&call.name)
+    {
+    Some(definition) => definition,
+    None             => return Err(Diagnostic::error(&line.file, line.number, format!("unknown macro '{}'", call.name))
+                                              .with_help(format!("macros are defined outside of the blocks with {}{} {}(...){}", KEY_NAME_BEGIN, DIRECTIVE_MACRO, call.name, KEY_NAME_END))),
+    };
+
+if scope.calls().contains(&call.name)
+    {
+    let mut chain = scope.calls().to_vec();
+    chain.push(call.name.clone());
+    return Err(Diagnostic::error(&line.file, line.number, format!("macro '{}' calls itself", call.name))
+                          .with_help(format!("call chain: {}", chain.join(" -> "))));
+    }
+
+let required = definition.parameters.iter().filter(|parameter| parameter.default.is_none()).count();
+if call.arguments.len() < required || call.arguments.len() > definition.parameters.len()
+    {
+    let expected = if required == definition.parameters.len() { required.to_string() } else { format!("{} to {}", required, definition.parameters.len()) };
+    return Err(Diagnostic::error(&line.file, line.number, format!("macro '{}' takes {} arguments, got {}", call.name, expected, call.arguments.len()))
+                          .with_help(format!("it is defined as {} in {}:{}", definition.signature(), definition.line.file.display(), definition.line.number)));
+    }
+
+let mut arguments = Vec::new();
+for argument in &call.arguments
+    {
+    arguments.push(evaluate(argument, scope).map_err(|why| Diagnostic::error(&line.file, line.number, format!("couldn't evaluate the arguments of '{}': {}", line.text.trim(), why)))?);
+    }
+
+let mut inner = scope.outermost();
+inner.push_frame();
+for (index, parameter) in definition.parameters.iter().enumerate()
+    {
+    let value = match (arguments.get(index), &parameter.default)
+        {
+        (Some(value), _)         => value.to_string(),
+        (None, Some(default))    => evaluate(default, &inner).map_err(|why| Diagnostic::error(&definition.line.file, definition.line.number, format!("couldn't evaluate the default value of '{}': {}", parameter.name, why)))?.to_string(),
+        (None, None)             => unreachable!("the number of arguments was checked"),
+        };
+    inner.set(&parameter.name, &value);
+    }
+inner.push_call(&call.name);
+let rendered = render_nodes(&definition.body, &mut inner);
+for origin in inner.take_origins() { scope.push_origin(origin); }
+rendered
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  is_identifier   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** name of a macro or parameter   
+/// **`RETURNS:    `** **` bool           `** true if it consists of letters, digits and '_', and starts with a letter or '_'   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn is_identifier(text: &str) -> bool
+{
+text.starts_with(|c: char| c.is_alphabetic() || c == '_') && text.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  split_arguments   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** the text between the parentheses of a macro definition   
+/// **`RETURNS:    `** **` Vec            `** the parameters, empty for an empty list   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// A ',' inside quotes or brackets belongs to a default value, like in `items = ['a', 'b']`.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn split_arguments(text: &str) -> Vec<String>
+{
+let mut parts = Vec::new();
+let mut part  = String::new();
+let mut depth = 0;
+let mut quote: Option<char> = None;
+
+for c in text.chars()
+    {
+    match quote
+        {
+        Some(open) if c == open                    => quote = None,
+        None if c == '"' || c == '\''              => quote = Some(c),
+        None if c == '(' || c == '['               => depth += 1,
+        None if c == ')' || c == ']'               => depth -= 1,
+        None if c == ',' && depth == 0             =>
+            {
+            parts.push(std::mem::take(&mut part));
+            continue;
+            },
+        _                                          => {},
+        }
+    part.push(c);
+    }
+if !part.trim().is_empty() || !parts.is_empty() { parts.push(part); }
+parts
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for macros   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  use std::path::Path;
+  use crate::modules::template::parse_template;
+  use crate::modules::expansion::control_scope;
+
+  fn evolve(text: &str) -> Result<String, String>
+  {
+  let template = parse_template(Path::new("Test.arise"), text).map_err(|why| why.to_string())?;
+  let mut scope = control_scope(&template);
+  let nodes = parse_nodes(&template.block("header").unwrap().lines).map_err(|why| why.to_string())?;
+  scope.push_frame();
+  scope.set("drive", "C");
+  render_nodes(&nodes, &mut scope).map_err(|why| why.to_string())
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** macros_are_expanded()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks arguments, default values and that the names of caller and macro are kept apart   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn macros_are_expanded()
+  {
+  let macros = "<:macro bar(name, measure, y = 10, h = y + 5):>\n[Meter_<:name:>]\nMeasureName=<:measure:>\nY=<:y:>,<:h:>\n<:for i in 1..2:>\n<:i:>\n<:end:>\n<:endmacro:>\n";
+  let result = evolve(&format!("{}<header begin>\n<:for i in [7]:>\n<:call bar(drive, 'M_Used', 20):>\n<:i:>\n<:end:>\n<:call bar('Free', 'M_Free'):>\n<header end>\n", macros));
+  assert_eq!(result, Ok("[Meter_C]\nMeasureName=M_Used\nY=20,25\n1\n2\n7\n[Meter_Free]\nMeasureName=M_Free\nY=10,15\n1\n2\n".to_string()));
+  let error = evolve("<:macro leak():>\n<:drive:>\n<:endmacro:>\n<header begin>\n<:call leak():>\n<header end>\n").unwrap_err();
+  assert!(error.contains("unknown placeholder 'drive'\n  --> Test.arise:2"));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** macro_errors()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the diagnostics for wrong numbers of arguments, unknown macros and recursion   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn macro_errors()
+  {
+  let error = evolve("<:macro bar(a, b = 1):>\n<:a:>\n<:endmacro:>\n<header begin>\n<:call bar(1, 2, 3):>\n<header end>\n").unwrap_err();
+  assert!(error.contains("macro 'bar' takes 1 to 2 arguments, got 3\n  --> Test.arise:5"));
+  assert!(error.contains("it is defined as bar(a, b = ...) in Test.arise:1"));
+
+  let error = evolve("<header begin>\n<:call foo():>\n<header end>\n").unwrap_err();
+  assert!(error.contains("unknown macro 'foo'"));
+
+  let error = evolve("<:macro a():>\n<:call b():>\n<:endmacro:>\n<:macro b():>\n<:call a():>\n<:endmacro:>\n<header begin>\n<:call a():>\n<header end>\n").unwrap_err();
+  assert!(error.contains("macro 'a' calls itself\n  --> Test.arise:5"));
+  assert!(error.contains("call chain: a -> b -> a"));
+
+  let error = evolve("<:macro a(x = 1, y):>\n<:endmacro:>\n").unwrap_err();
+  assert!(error.contains("parameter 'y' of macro 'a' needs a default value"));
+  let error = evolve("<:macro a():>\nX\n").unwrap_err();
+  assert!(error.contains("macro 'a' is not closed, missing '<:endmacro:>'\n  --> Test.arise:1"));
+  }
+
+} // End of: mod test
@@ -43,6 +43,27 @@
 pub(crate) mod config;        // <filename>
 pub(crate) mod core_logic;    // <filename>
 pub(crate) mod arise_log;     // <filename>
+pub(crate) mod diagnostics;   // <filename>
+pub(crate) mod data_source;   // <filename>
+pub(crate) mod template;      // <filename>
+pub(crate) mod expansion;     // <filename>
+pub(crate) mod expression;    // <filename>
+pub(crate) mod filters;       // <filename>
+pub(crate) mod macros;        // <filename>
+pub(crate) mod inheritance;   // <filename>
+pub(crate) mod usage;         // <filename>
+pub(crate) mod types;         // <filename>
+pub(crate) mod colors;        // <filename>
+pub(crate) mod ini;           // <filename>
+pub(crate) mod ini_syntax;    // <filename>
+pub(crate) mod import;        // <filename>
+pub(crate) mod duplicates;    // <filename>
+pub(crate) mod references;    // <filename>
+pub(crate) mod bangs;         // <filename>
+pub(crate) mod schema;        // <filename>
+pub(crate) mod options;       // <filename>
+pub(crate) mod calc;          // <filename>
+pub(crate) mod simulate;      // <filename>
 
 //___ CONSTANTS: ______________________________________________________________________________________________________________
 //___ none ___
@@ -0,0 +1,330 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** options.rs 🦀   
+//! **`DESCRIPTION:`** checks the options of the meters and measures of the evolved skin against the schema   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | keys in the casing of the Rainmeter manual, suggestions for unknown options   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! warning: invalid value 'Diagonal' of BarOrientation in section [Meter_Drive_C_DriveBar_Filled]   
+//!   --> input/StorageMon.arise:131:3   
+//!     |   
+//! 131 |  [Meter_Drive_<:drive:>_DriveBar_Filled]   
+//!     |  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^   
+//!     = help: expected is one of Horizontal, Vertical; the same in [Meter_Drive_D_DriveBar_Filled], ...   
+//!```
+//! Rainmeter ignores the case of keys, but `Barimage` reads worse than `BarImage`: keys written differently from the   
+//! manual are reported, or rewritten with `ARISE_KEY_CASE := canonical`.   
+//! Sections without `Meter` or `Measure` key (styles, `[Rainmeter]`, `[Variables]`) are not checked. Plugins the   
+//! schema does not know have options of their own, so only the general measure options of them are checked.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::path::Path;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::ini::{IniDocument, IniLine, IniSection};
+use crate::modules::expansion::SectionOrigin;
+use crate::modules::duplicates::locate_origins;
+use crate::modules::references::KeyWarnings;
+use crate::modules::schema::{OptionSpec, SCHEMA_VERSION, meter_type, measure_type, plugin, meter_type_names, measure_type_names,
+                             general_meter_options, general_measure_options};
+use crate::modules::diagnostics::{Diagnostic, closest_name};
+use crate::modules::template::{CONTROL_KEY_CASE, KEY_CASE_CANONICAL};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+//___ none ___
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// What the schema knows about the options of a section.   
+enum KnownOptions
+{
+    None,                                                   // no meter or measure, or a type given by a variable
+    Some { kind: String, options: Vec<&'static OptionSpec>, complete: bool },
+    UnknownType { key: String, message: String, help: String },
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+//___ none ___
+
+//___ METHODS: ________________________________________________________________________________________________________________
+//___ none ___
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  option_warnings   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file           `** the arise file, named for sections without a known origin   
+/// **`            `** **` skin           `** the evolved skin   
+/// **`            `** **` origins        `** where the sections come from, in the order they were evolved   
+/// **`RETURNS:    `** **` Vec            `** a warning for every unknown meter or measure type, unknown option and invalid   
+/// **`            `** **`                `** value, one per template line   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | keys differing from the manual in case, suggestions for unknown options   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn option_warnings(file: &Path, skin: &IniDocument, origins: &[SectionOrigin]) -> Vec<Diagnostic>
+{
+let located = locate_origins(skin, origins);
+let mut warnings = KeyWarnings::new();
+for (position, section) in skin.sections.iter().enumerate()
+    {
+    let (kind, options, complete) = match known_options(section)
+        {
+        KnownOptions::None                              => continue,
+        KnownOptions::Some { kind, options, complete }  => (kind, options, complete),
+        KnownOptions::UnknownType { key, message, help } =>
+            {
+            warnings.add(located[position], &key, 0, &section.name, message, help);
+            continue;
+            },
+        };
+
+    for key in section.keys()
+        {
+        match options.iter().find(|option| option.matches(&key.name))
+            {
+            Some(option) =>
+                {
+                if !option.accepts(&key.value)
+                    {
+                    warnings.add(located[position], &key.name, 0, &section.name,
+                                 format!("invalid value '{}' of {} in section [{}]", key.value.trim(), key.name, section.name),
+                                 format!("expected is {}", option.expected()));
+                    }
+                let canonical = canonical_name(option, &key.name);
+                if canonical != key.name
+                    {
+                    warnings.add(located[position], &key.name, 1, &section.name,
+                                 format!("'{}' is written '{}' in the Rainmeter manual, in section [{}]", key.name, canonical, section.name),
+                                 format!("Rainmeter ignores the case, but readers do not; rename it, or let {} := {} do it", CONTROL_KEY_CASE, KEY_CASE_CANONICAL));
+                    }
+                },
+            None if complete =>
+                {
+                let help = match closest_name(&key.name, options.iter().map(|option| option.name))
+                    {
+                    Some(closest) => format!("did you mean '{}'?", closest),
+                    None          => format!("the schema of {} knows no option of this name for {}", SCHEMA_VERSION, kind),
+                    };
+                warnings.add(located[position], &key.name, 0, &section.name,
+                             format!("unknown option '{}' for {} in section [{}]", key.name, kind, section.name), help);
+                },
+            None => {},
+            }
+        }
+    }
+
+let warnings = warnings.into_diagnostics(file);
+debug!("checked the options of {} sections against the schema of {}, {} warning(s)", skin.sections.len(), SCHEMA_VERSION, warnings.len());
+warnings
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  canonical_keys   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` skin           `** the evolved skin   
+/// **`RETURNS:    `** **` usize          `** the number of keys renamed   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Renames the keys of meters and measures to the casing of the schema, like `Barimage` to `BarImage` and   
+/// `measurename2` to `MeasureName2`. Unknown keys stay as they are.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn canonical_keys(skin: &mut IniDocument) -> usize
+{
+let mut renamed = 0;
+for section in &mut skin.sections
+    {
+    let options = match known_options(section)
+        {
+        KnownOptions::Some { options, .. } => options,
+        _                                  => continue,
+        };
+    for line in &mut section.lines
+        {
+        let IniLine::Key(key) = line else { continue; };
+        let Some(option) = options.iter().find(|option| option.matches(&key.name)) else { continue; };
+        let canonical = canonical_name(option, &key.name);
+        if canonical != key.name
+            {
+            key.rename(&canonical);
+            renamed += 1;
+            }
+        }
+    }
+debug!("{} key(s) renamed to the casing of the Rainmeter manual", renamed);
+renamed
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  canonical_name   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` option         `** the option the key is   
+/// **`            `** **` key            `** the name of the key, maybe with a number   
+/// **`RETURNS:    `** **` String         `** the name in the casing of the schema, with the number of the key   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn canonical_name(option: &OptionSpec, key: &str) -> String
+{
+format!("{}{}", option.name, &key[option.name.len() ..])
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  known_options   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` section        `** a section of the evolved skin   
+/// **`RETURNS:    `** **` KnownOptions   `** the options of its type, like `Meter=BAR`, and whether they are all known   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn known_options(section: &IniSection) -> KnownOptions
+{
+let value_of = |name: &str| section.keys().find(|key| key.name.eq_ignore_ascii_case(name)).map(|key| key.value.trim());
+let unknown  = |key: &str, what: &str, name: &str, names: Vec<&str>| KnownOptions::UnknownType
+    {
+    key:     key.to_string(),
+    message: format!("unknown {} type '{}' in section [{}]", what, name, section.name),
+    help:    match closest_name(name, names.iter().copied())
+                 {
+                 Some(closest) => format!("did you mean '{}'?", closest),
+                 None          => format!("the schema of {} knows {}", SCHEMA_VERSION, names.join(", ")),
+                 },
+    };
+
+if let Some(meter) = value_of("Meter")
+    {
+    if meter.contains('#') { return KnownOptions::None; }
+    return match meter_type(meter)
+        {
+        Some(spec) => KnownOptions::Some { kind: format!("Meter={}", meter), options: general_meter_options().chain(spec.options).collect(), complete: true },
+        None       => unknown("Meter", "meter", meter, meter_type_names().collect()),
+        };
+    }
+
+if let Some(measure) = value_of("Measure")
+    {
+    if measure.contains('#') { return KnownOptions::None; }
+    let spec = match measure_type(measure)
+        {
+        Some(spec) => spec,
+        None       => return unknown("Measure", "measure", measure, measure_type_names().collect()),
+        };
+    let mut options: Vec<&OptionSpec> = general_measure_options().chain(spec.options).collect();
+    if !spec.name.eq_ignore_ascii_case("Plugin") { return KnownOptions::Some { kind: format!("Measure={}", measure), options, complete: true }; }
+
+    let name = value_of("Plugin").unwrap_or("");
+    return match plugin(name)
+        {
+        Some(plugin) =>
+            {
+            options.extend(plugin.options);
+            KnownOptions::Some { kind: format!("Plugin={}", plugin.name), options, complete: true }
+            },
+        None => KnownOptions::Some { kind: format!("Plugin={}", name), options, complete: false },
+        };
+    }
+KnownOptions::None
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for options   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  use std::path::PathBuf;
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** options_are_checked_against_the_schema()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that unknown types, unknown options, invalid values and keys in another case are reported,   
+  /// **`            `** and that plugins the schema does not know are left alone   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn options_are_checked_against_the_schema()
+  {
+  let skin = IniDocument::parse("[StringStyle]\nWhatever=1\n\
+                                 [Meter_Bar]\nMeter=BAR\nBarimage=#@#bar.png\nBarOrientation=Diagonal\nX=6r\nW=(#Width#*2)\nWobble=1\nBarOrientaton=1\n\
+                                 [Measure_Read]\nMeasure=Plugin\nPlugin=UsageMonitor\nCategory=LogicalDisk\nCounter=Disk Read Bytes/sec\nName=C:\n\
+                                 [Measure_Other]\nMeasure=Plugin\nPlugin=SomeoneElses\nAnything=1\nDisabled=maybe\n\
+                                 [Meter_Text]\nMeter=Strng\n");
+
+  let warnings = option_warnings(&PathBuf::from("t.arise"), &skin, &[]);
+  let messages: Vec<&str> = warnings.iter().map(|warning| warning.message.as_str()).collect();
+  assert_eq!(messages, ["'Barimage' is written 'BarImage' in the Rainmeter manual, in section [Meter_Bar]",
+                        "invalid value 'Diagonal' of BarOrientation in section [Meter_Bar]",
+                        "unknown option 'Wobble' for Meter=BAR in section [Meter_Bar]",
+                        "unknown option 'BarOrientaton' for Meter=BAR in section [Meter_Bar]",
+                        "invalid value 'maybe' of Disabled in section [Measure_Other]",
+                        "unknown meter type 'Strng' in section [Meter_Text]"]);
+  assert_eq!(warnings[1].help.as_deref(), Some("expected is one of Horizontal, Vertical"));
+  assert_eq!(warnings[2].help.as_deref(), Some("the schema of Rainmeter 4.5 knows no option of this name for Meter=BAR"));
+  assert_eq!(warnings[3].help.as_deref(), Some("did you mean 'BarOrientation'?"));
+  assert_eq!(warnings[5].help.as_deref(), Some("did you mean 'String'?"));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** keys_are_renamed_to_their_canonical_case()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that known keys of meters and measures get the case of the manual, keeping their number and   
+  /// **`            `** spacing, and that other keys and sections stay as they are   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn keys_are_renamed_to_their_canonical_case()
+  {
+  let mut skin = IniDocument::parse("[Rainmeter]\nupdate=250\n[Meter_Lines]\nMeter=LINE\n  linecolor2 = 255,0,0\nmeasurename=Measure_Read\nWobble=1\n");
+  assert_eq!(canonical_keys(&mut skin), 2);
+  assert_eq!(skin.to_string(), "[Rainmeter]\nupdate=250\n[Meter_Lines]\nMeter=LINE\n  LineColor2 = 255,0,0\nMeasureName=Measure_Read\nWobble=1\n");
+  assert!(option_warnings(&PathBuf::from("t.arise"), &skin, &[]).iter().all(|warning| !warning.message.contains("is written")));
+  }
+
+} // End of: mod test
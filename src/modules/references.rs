@@ -0,0 +1,572 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** references.rs 🦀   
+//! **`DESCRIPTION:`** checks the references between the sections of the evolved skin, and finds measures no meter uses   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | index of groups and collecting warnings shared with the check of bangs   
+//! 0.3      | 2026-10-19 | Clunion   | the measures of formulas found by the parser of calc, the words of broken ones   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! warning: unknown measure 'Measure_Drive_C_Usde' in MeasureName of section [Meter_Drive_C_DriveBar_Filled]   
+//!   --> input/StorageMon.arise:131:1   
+//!     |   
+//! 131 |[Meter_Drive_<:drive:>_DriveBar_Filled]   
+//!     |^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^   
+//!     = help: did you mean 'Measure_Drive_C_Used'?; the same in [Meter_Drive_D_DriveBar_Filled], ...   
+//!```
+//! Checked are the measures named by `MeasureName`, `MeasureName2`, ..., the sections named by `MeterStyle`, the   
+//! variables `#Name#`, the section variables `[Name]` and `[Name:X]`, and the measures used in `Formula` and   
+//! `IfCondition`. Rainmeter's built-in variables like `#CRLF#`, `#@#` and `#CURRENTCONFIG#` are known.   
+//! A skin with `@Include` gets sections and variables from other files, so it is not checked.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::ini::{IniDocument, IniKey, IniSection};
+use crate::modules::expansion::SectionOrigin;
+use crate::modules::duplicates::{locate_origins, same_line, warning_at};
+use crate::modules::diagnostics::{Diagnostic, closest_name};
+use crate::modules::bangs::is_action_key;
+use crate::modules::calc::{CALC_FUNCTIONS, CALC_CONSTANTS, is_formula_key, parse_formula};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+/// Variables Rainmeter defines itself, a monitor number may follow, like `#SCREENAREAWIDTH@2#`.   
+const BUILTIN_VARIABLES: &[&str] = &["@", "CRLF", "CURRENTSECTION", "CURRENTFILE", "CURRENTPATH", "CURRENTCONFIG",
+                                     "CURRENTCONFIGX", "CURRENTCONFIGY", "CURRENTCONFIGWIDTH", "CURRENTCONFIGHEIGHT",
+                                     "ROOTCONFIG", "ROOTCONFIGPATH", "SKINSPATH", "SETTINGSPATH", "PROGRAMPATH",
+                                     "PROGRAMDRIVE", "ADDONSPATH", "PLUGINSPATH",
+                                     "WORKAREAX", "WORKAREAY", "WORKAREAWIDTH", "WORKAREAHEIGHT",
+                                     "SCREENAREAX", "SCREENAREAY", "SCREENAREAWIDTH", "SCREENAREAHEIGHT",
+                                     "PWORKAREAX", "PWORKAREAY", "PWORKAREAWIDTH", "PWORKAREAHEIGHT",
+                                     "PSCREENAREAX", "PSCREENAREAY", "PSCREENAREAWIDTH", "PSCREENAREAHEIGHT",
+                                     "VSCREENAREAX", "VSCREENAREAY", "VSCREENAREAWIDTH", "VSCREENAREAHEIGHT"];
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// What a reference has to name.   
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Target
+{
+    Measure,                    // MeasureName, Formula, IfCondition
+    Meter,                      // !ShowMeter
+    Section,                    // [Name], [Name:X]: a measure or a meter
+    Style,                      // MeterStyle, !SetOption: any section
+    Variable,                   // #Name#
+    MeterGroup,                 // !HideMeterGroup: a Group of a meter
+    MeasureGroup,               // !UpdateMeasureGroup: a Group of a measure
+    Group,                      // !SetOptionGroup: a Group of any section
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// The names defined in the skin, by their lower case name.   
+pub(crate) struct SkinIndex<'a>
+{
+    measures:       BTreeMap<String, &'a IniSection>,
+    meters:         BTreeMap<String, &'a str>,
+    sections:       BTreeMap<String, &'a str>,
+    variables:      BTreeMap<String, &'a str>,
+    meter_groups:   BTreeMap<String, &'a str>,
+    measure_groups: BTreeMap<String, &'a str>,
+    groups:         BTreeMap<String, &'a str>,
+}
+
+/// A warning about a key, for the sections evolved from the same template line.   
+struct KeyWarning<'a>
+{
+    origin:   Option<&'a SectionOrigin>,
+    key:      String,
+    nth:      usize,                // the n-th problem in the value
+    message:  String,               // of the first section
+    help:     String,
+    sections: Vec<String>,
+}
+
+/// The warnings about keys, one per template line instead of one per evolved section.   
+pub(crate) struct KeyWarnings<'a>
+{
+    warnings: Vec<KeyWarning<'a>>,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+impl<'a> SkinIndex<'a>
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  new / knows / candidates   
+/// **`TYPE:       `**  methods of SkinIndex   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Collects the measures (sections with a `Measure` key), meters (with a `Meter` key), all sections, the keys of   
+/// `[Variables]` and the groups of the `Group` keys (`Group=A | B`); whether a reference names one of them, and the   
+/// names it could have meant.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | meters and groups, for the bangs   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn new(skin: &'a IniDocument) -> SkinIndex<'a>
+    {
+        let mut index = SkinIndex { measures: BTreeMap::new(), meters: BTreeMap::new(), sections: BTreeMap::new(), variables: BTreeMap::new(),
+                                    meter_groups: BTreeMap::new(), measure_groups: BTreeMap::new(), groups: BTreeMap::new() };
+        for section in &skin.sections
+            {
+            let name     = section.name.to_lowercase();
+            let measure  = section.keys().any(|key| key.name.eq_ignore_ascii_case("Measure"));
+            let meter    = section.keys().any(|key| key.name.eq_ignore_ascii_case("Meter"));
+            index.sections.entry(name.clone()).or_insert(&section.name);
+            if measure { index.measures.entry(name.clone()).or_insert(section); }
+            if meter   { index.meters.entry(name.clone()).or_insert(&section.name); }
+            if name == "variables"
+                {
+                for key in section.keys() { index.variables.entry(key.name.to_lowercase()).or_insert(&key.name); }
+                }
+            for group in section.keys().filter(|key| key.name.eq_ignore_ascii_case("Group")).flat_map(|key| key.value.split('|')).map(str::trim).filter(|group| !group.is_empty())
+                {
+                index.groups.entry(group.to_lowercase()).or_insert(group);
+                if measure { index.measure_groups.entry(group.to_lowercase()).or_insert(group); }
+                if meter   { index.meter_groups.entry(group.to_lowercase()).or_insert(group); }
+                }
+            }
+        index
+    }
+
+pub(crate) fn knows(&self, target: Target, name: &str) -> bool
+    {
+        let name = name.to_lowercase();
+        match target
+            {
+            Target::Measure      => self.measures.contains_key(&name),
+            Target::Meter        => self.meters.contains_key(&name),
+            Target::Section      => self.measures.contains_key(&name) || self.meters.contains_key(&name),
+            Target::Style        => self.sections.contains_key(&name),
+            Target::MeterGroup   => self.meter_groups.contains_key(&name),
+            Target::MeasureGroup => self.measure_groups.contains_key(&name),
+            Target::Group        => self.groups.contains_key(&name),
+            Target::Variable     =>
+                {
+                let bare = if name.len() > 1 { name.split('@').next().unwrap_or("") } else { &name };
+                self.variables.contains_key(&name) || BUILTIN_VARIABLES.iter().any(|builtin| builtin.eq_ignore_ascii_case(bare))
+                },
+            }
+    }
+
+pub(crate) fn candidates(&self, target: Target) -> Vec<&str>
+    {
+        let measures = self.measures.values().map(|section| section.name.as_str());
+        match target
+            {
+            Target::Measure      => measures.collect(),
+            Target::Meter        => self.meters.values().copied().collect(),
+            Target::Section      => measures.chain(self.meters.values().copied()).collect(),
+            Target::Style        => self.sections.values().copied().collect(),
+            Target::MeterGroup   => self.meter_groups.values().copied().collect(),
+            Target::MeasureGroup => self.measure_groups.values().copied().collect(),
+            Target::Group        => self.groups.values().copied().collect(),
+            Target::Variable     => self.variables.values().copied().chain(BUILTIN_VARIABLES.iter().copied()).collect(),
+            }
+    }
+
+} // End of impl: SkinIndex
+
+
+impl<'a> KeyWarnings<'a>
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  new / add / into_diagnostics   
+/// **`TYPE:       `**  methods of KeyWarnings   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Adding the n-th problem of a key to the warning of the same problem of the same key from the same template line,   
+/// if there is one, so 24 evolved drives give one warning; the others are named in its help.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, taken out of cross_references   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn new() -> KeyWarnings<'a>
+    {
+        KeyWarnings { warnings: Vec::new() }
+    }
+
+pub(crate) fn add(&mut self, origin: Option<&'a SectionOrigin>, key: &str, nth: usize, section: &str, message: String, help: String)
+    {
+        let known = self.warnings.iter_mut().find(|known| known.nth == nth && known.key.eq_ignore_ascii_case(key) && same_line(known.origin, origin));
+        match known
+            {
+            Some(known) => known.sections.push(section.to_string()),
+            None        => self.warnings.push(KeyWarning { origin, key: key.to_string(), nth, message, help, sections: vec![section.to_string()] }),
+            }
+    }
+
+pub(crate) fn into_diagnostics(self, file: &Path) -> Vec<Diagnostic>
+    {
+        self.warnings.into_iter().map(|warning|
+            {
+            let mut help = warning.help;
+            if warning.sections.len() > 1
+                {
+                let others: Vec<String> = warning.sections[1 ..].iter().map(|section| format!("[{}]", section)).collect();
+                help.push_str(&format!("; the same in {}", others.join(", ")));
+                }
+            warning_at(file, warning.origin, warning.message).with_help(help)
+            }).collect()
+    }
+
+} // End of impl: KeyWarnings
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  cross_references   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` file           `** the arise file, named for sections without a known origin   
+/// **`            `** **` skin           `** the evolved skin   
+/// **`            `** **` origins        `** where the sections come from, in the order they were evolved   
+/// **`RETURNS:    `** **` Vec            `** a warning for every reference to something which does not exist, and for   
+/// **`            `** **`                `** every measure no meter uses, one per template line   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// A measure is used, if a meter refers to it, or a used measure does (like a Calc measure in its formula).   
+/// Measures with actions (`IfTrueAction`, `OnChangeAction`, ...) do their work on their own and count as used.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | warnings collected by KeyWarnings   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn cross_references(file: &Path, skin: &IniDocument, origins: &[SectionOrigin]) -> Vec<Diagnostic>
+{
+if skin.sections.iter().flat_map(|section| section.keys()).any(|key| key.name.to_lowercase().starts_with("@include"))
+    {
+    info!("the skin includes other files, its references are not checked");
+    return Vec::new();
+    }
+
+let index   = SkinIndex::new(skin);
+let located = locate_origins(skin, origins);
+
+let mut unknown = KeyWarnings::new();
+for (position, section) in skin.sections.iter().enumerate()
+    {
+    for key in section.keys()
+        {
+        for (nth, (target, name)) in references(key).into_iter().enumerate()
+            {
+            if index.knows(target, &name) { continue; }
+            unknown.add(located[position], &key.name, nth, &section.name,
+                        unknown_message(target, &name, &key.name, &section.name),
+                        unknown_help(target, closest_name(&name, index.candidates(target))));
+            }
+        }
+    }
+let mut warnings = unknown.into_diagnostics(file);
+
+// the measures the meters use, and the measures those use, and so on:
+let mut used: BTreeSet<String> = BTreeSet::new();
+let mut pending: Vec<&IniSection> = skin.sections.iter().filter(|section| index.meters.contains_key(&section.name.to_lowercase())).collect();
+while let Some(section) = pending.pop()
+    {
+    for (target, name) in section.keys().flat_map(references)
+        {
+        if target != Target::Measure && target != Target::Section { continue; }
+        if let Some(measure) = index.measures.get(&name.to_lowercase())
+            {
+            if used.insert(name.to_lowercase()) { pending.push(measure); }
+            }
+        }
+    }
+
+let mut unused: Vec<(Option<&SectionOrigin>, Vec<String>)> = Vec::new();
+for (position, section) in skin.sections.iter().enumerate()
+    {
+    let name = section.name.to_lowercase();
+    if !index.measures.get(&name).is_some_and(|measure| std::ptr::eq(*measure, section)) { continue; }
+    if used.contains(&name) || section.keys().any(|key| is_action_key(&key.name)) { continue; }
+    let origin = located[position];
+    match unused.iter_mut().find(|(known, _)| same_line(*known, origin))
+        {
+        Some((_, sections)) => sections.push(section.name.clone()),
+        None                => unused.push((origin, vec![section.name.clone()])),
+        }
+    }
+for (origin, sections) in unused
+    {
+    let mut help = "neither directly nor through another measure; remove it, or show it with MeasureName in a meter".to_string();
+    if sections.len() > 1
+        {
+        let others: Vec<String> = sections[1 ..].iter().map(|section| format!("[{}]", section)).collect();
+        help.push_str(&format!("; the same for {}", others.join(", ")));
+        }
+    warnings.push(warning_at(file, origin, format!("measure [{}] is not used by any meter", sections[0])).with_help(help));
+    }
+
+debug!("checked the references of {} sections, {} warning(s)", skin.sections.len(), warnings.len());
+warnings
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  references   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` key            `** a key of the evolved skin   
+/// **`RETURNS:    `** **` Vec            `** the names its value refers to, with what they have to name   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Names built from variables (`MeasureName=#Measure#`) are only known at runtime and are left out. The values of   
+/// `RegExp` and `Substitute` are patterns, their brackets are no section variables. The measures of a formula which   
+/// does not parse are the words in it, its syntax is reported by the check of the formulas.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | the measures of formulas from the parser of calc   
+/// ___________________________________________________________________________________________________________________________
+fn references(key: &IniKey) -> Vec<(Target, String)>
+{
+let name  = key.name.to_lowercase();
+let value = key.value.trim();
+let numbered = |prefix: &str| name.strip_prefix(prefix).is_some_and(|number| number.chars().all(|c| c.is_ascii_digit()));
+let runtime  = |text: &str| text.contains('#') || text.contains('[');
+
+let mut found = Vec::new();
+if numbered("measurename") && !value.is_empty() && !runtime(value)
+    {
+    found.push((Target::Measure, value.to_string()));
+    }
+if name == "meterstyle"
+    {
+    found.extend(value.split('|').map(str::trim).filter(|style| !style.is_empty() && !runtime(style)).map(|style| (Target::Style, style.to_string())));
+    }
+if is_formula_key(&name)
+    {
+    let measures = parse_formula(value).map(|formula| formula.names()).unwrap_or_else(|_| formula_names(value));
+    found.extend(measures.into_iter().map(|measure| (Target::Measure, measure)));
+    }
+if name != "regexp" && name != "substitute"
+    {
+    found.extend(section_variables(value).into_iter().map(|section| (Target::Section, section)));
+    }
+found.extend(variables(value).into_iter().map(|variable| (Target::Variable, variable)));
+found
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  variables / section_variables / formula_names   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The names of `#Name#`, of `[Name]`, `[Name:X]` and `[&Name]` (the innermost brackets, so `[!Bang "[Name]"]` is   
+/// found too), and the words in a formula, which are not Calc functions, with the parts in `#` and `[]` left out.   
+/// Escaped references like `#*Name*#` are no names.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn variables(value: &str) -> Vec<String>
+{
+let mut found = Vec::new();
+let mut rest  = value;
+while let Some(start) = rest.find('#')
+    {
+    let after = &rest[start + 1 ..];
+    match after.find('#')
+        {
+        Some(end) if end > 0 && after[.. end].chars().all(|c| is_name_char(c) || c == '@') =>
+            {
+            found.push(after[.. end].to_string());
+            rest = &after[end + 1 ..];
+            },
+        _ => rest = after,
+        }
+    }
+found
+}
+
+fn section_variables(value: &str) -> Vec<String>
+{
+let mut found = Vec::new();
+let mut start = None;
+for (position, c) in value.char_indices()
+    {
+    match c
+        {
+        '[' => start = Some(position + 1),
+        ']' =>
+            {
+            if let Some(begin) = start.take()
+                {
+                let inner = &value[begin .. position];
+                let inner = inner.strip_prefix('&').unwrap_or(inner);
+                let name  = inner.split(':').next().unwrap_or("");
+                if name.chars().all(is_name_char) && name.chars().any(char::is_alphabetic) { found.push(name.to_string()); }
+                }
+            },
+        _ => {},
+        }
+    }
+found
+}
+
+fn formula_names(value: &str) -> Vec<String>
+{
+let mut plain  = String::new();
+let mut depth  = 0;
+let mut hashed = false;
+for c in value.chars()
+    {
+    match c
+        {
+        '['             => depth += 1,
+        ']' if depth > 0 => depth -= 1,
+        '#'             => hashed = !hashed,
+        _ if depth == 0 && !hashed => { plain.push(c); continue; },
+        _ => {},
+        }
+    plain.push(' ');
+    }
+
+let known = |word: &str| CALC_FUNCTIONS.iter().map(|function| function.0).chain(CALC_CONSTANTS.iter().copied()).any(|known| known.eq_ignore_ascii_case(word));
+let chars: Vec<char> = plain.chars().collect();
+let mut found = Vec::new();
+let mut index = 0;
+while index < chars.len()
+    {
+    let first = chars[index];
+    let end   = index + chars[index ..].iter().take_while(|c| c.is_alphanumeric() || **c == '_' || (**c == '.' && !first.is_alphabetic())).count();
+    if end == index { index += 1; continue; }
+    let word: String = chars[index .. end].iter().collect();
+    if (first.is_alphabetic() || first == '_') && !known(&word) { found.push(word); }
+    index = end;
+    }
+found
+}
+
+fn is_name_char(c: char) -> bool
+{
+c.is_alphanumeric() || c == '_' || c == '.' || c == '-'
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  unknown_message / unknown_help   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The warning about a reference to something that does not exist, and the help with the name probably meant.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | meters and groups   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn unknown_message(target: Target, name: &str, key: &str, section: &str) -> String
+{
+let what = match target
+    {
+    Target::Measure      => format!("measure '{}'", name),
+    Target::Meter        => format!("meter '{}'", name),
+    Target::Section      => format!("measure or meter '[{}]'", name),
+    Target::Style        => format!("section '{}'", name),
+    Target::Variable     => format!("variable '#{}#'", name),
+    Target::MeterGroup   => format!("meter group '{}'", name),
+    Target::MeasureGroup => format!("measure group '{}'", name),
+    Target::Group        => format!("group '{}'", name),
+    };
+format!("unknown {} in {} of section [{}]", what, key, section)
+}
+
+pub(crate) fn unknown_help(target: Target, closest: Option<&str>) -> String
+{
+match (target, closest)
+    {
+    (Target::Variable,      Some(closest)) => format!("did you mean '#{}#'?", closest),
+    (_,                     Some(closest)) => format!("did you mean '{}'?", closest),
+    (Target::Measure,       None)          => "no section of this name has a Measure key".to_string(),
+    (Target::Meter,         None)          => "no section of this name has a Meter key".to_string(),
+    (Target::Section,       None)          => "no section of this name has a Measure or a Meter key".to_string(),
+    (Target::Style,         None)          => "there is no section of this name".to_string(),
+    (Target::Variable,      None)          => "define it in [Variables], it is none of Rainmeter's built-in variables".to_string(),
+    (Target::MeterGroup,    None)          => "no meter has it in its Group key".to_string(),
+    (Target::MeasureGroup,  None)          => "no measure has it in its Group key".to_string(),
+    (Target::Group,         None)          => "no section has it in its Group key".to_string(),
+    }
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for references   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  use std::path::PathBuf;
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** unknown_references_are_reported()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that measures, styles, variables and section variables which do not exist are reported,   
+  /// **`            `** and built-in variables are not, also in formulas which do not parse   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn unknown_references_are_reported()
+  {
+  let skin = IniDocument::parse("[Variables]\nReadingColor=0,255,0\n\
+                                 [Measure_Total]\nMeasure=FreeDiskSpace\nIfCondition=(Measure_Total=0)\n\
+                                 [Measure_Used]\nMeasure=FreeDiskSpace\n\
+                                 [Measure_Percent]\nMeasure=Calc\nFormula=100/(Measure_Total / Measure_Usde) + Round(#Offset#)\n\
+                                 [Measure_Lonely]\nMeasure=FreeDiskSpace\nIfCondition=(Measure_Lonley > 0\n\
+                                 [Meter_Bar]\nMeter=BAR\nMeasureName=Measure_Percent\nMeasureName2=Measure_Used\nMeterStyle=StringStyle_Measures\n\
+                                 BarColor=#ReadingColour#\nImageName=#@#Images\\bar.png\nToolTipText=[Measure_Total]#CRLF#[Meter_Bar:X]\n\
+                                 X=([Meter_Frame:X]+38)\nLeftMouseUpAction=[!HideMeterGroup \"Group\"][\"C:\"]\n");
+
+  let warnings = cross_references(&PathBuf::from("t.arise"), &skin, &[]);
+  let messages: Vec<&str> = warnings.iter().map(|warning| warning.message.as_str()).collect();
+  assert_eq!(messages, ["unknown measure 'Measure_Usde' in Formula of section [Measure_Percent]",
+                        "unknown variable '#Offset#' in Formula of section [Measure_Percent]",
+                        "unknown measure 'Measure_Lonley' in IfCondition of section [Measure_Lonely]",
+                        "unknown section 'StringStyle_Measures' in MeterStyle of section [Meter_Bar]",
+                        "unknown variable '#ReadingColour#' in BarColor of section [Meter_Bar]",
+                        "unknown measure or meter '[Meter_Frame]' in X of section [Meter_Bar]",
+                        "measure [Measure_Lonely] is not used by any meter"]);
+  assert_eq!(warnings[0].help.as_deref(), Some("did you mean 'Measure_Used'?"));
+  assert_eq!(warnings[4].help.as_deref(), Some("did you mean '#ReadingColor#'?"));
+
+  assert!(cross_references(&PathBuf::from("t.arise"), &IniDocument::parse("[Rainmeter]\n@Include=#@#Common.inc\n[M]\nMeasureName=X\n"), &[]).is_empty());
+  }
+
+} // End of: mod test
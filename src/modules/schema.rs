@@ -0,0 +1,504 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** schema.rs 🦀   
+//! **`DESCRIPTION:`** the options Rainmeter knows for its meter types, measure types and common plugins   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | ImagePath of Image meters   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! Meter=BAR                   --> the general meter options, the mouse actions, and BarImage, BarOrientation, ...   
+//! Measure=FreeDiskSpace       --> the general measure options, and Drive, Total, Label, IgnoreRemovable, ...   
+//! Measure=Plugin   
+//! Plugin=UsageMonitor         --> the general measure options, and Category, Counter, Name, ...   
+//!```
+//! Each option has a name, the type of its values, the values allowed (for choices), and whether it is in pixels.   
+//! The schema describes the Rainmeter version in SCHEMA_VERSION; when Rainmeter gets new options, they are added here   
+//! and the version is raised.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+/// The Rainmeter version the schema describes.   
+pub(crate) const SCHEMA_VERSION: &str = "Rainmeter 4.5";
+
+const ALIGN:       &[&str] = &["Left", "Center", "Right", "LeftTop", "LeftCenter", "LeftBottom", "CenterTop", "CenterCenter",
+                               "CenterBottom", "RightTop", "RightCenter", "RightBottom"];
+const ORIENTATION: &[&str] = &["Horizontal", "Vertical"];
+const GRAPH_START: &[&str] = &["Right", "Left"];
+
+/// Options of every meter.   
+const GENERAL_METER: &[OptionSpec] =
+    &[
+    text("Meter"),                      position("X"),                      position("Y"),
+    pixel("W", ValueType::Number),      pixel("H", ValueType::Number),      text("MeterStyle"),
+    numbered("MeasureName", ValueType::Text),                               text("Group"),
+    option("Hidden", ValueType::Bool),  option("SolidColor", ValueType::Color), option("SolidColor2", ValueType::Color),
+    option("GradientAngle", ValueType::Number), choice("BevelType", &["0", "1", "2"]), text("Padding"),
+    option("AntiAlias", ValueType::Bool), option("DynamicVariables", ValueType::Bool), option("UpdateDivider", ValueType::Integer),
+    text("TransformationMatrix"),       text("Container"),                  text("ToolTipText"),
+    text("ToolTipTitle"),               text("ToolTipIcon"),                option("ToolTipType", ValueType::Bool),
+    pixel("ToolTipWidth", ValueType::Integer), option("ToolTipHidden", ValueType::Bool),
+    option("MouseActionCursor", ValueType::Bool), text("MouseActionCursorName"),
+    ];
+
+/// The mouse actions, of meters and of the skin.   
+const MOUSE_ACTIONS: &[OptionSpec] =
+    &[
+    action("LeftMouseUpAction"),        action("LeftMouseDownAction"),      action("LeftMouseDoubleClickAction"),
+    action("RightMouseUpAction"),       action("RightMouseDownAction"),     action("RightMouseDoubleClickAction"),
+    action("MiddleMouseUpAction"),      action("MiddleMouseDownAction"),    action("MiddleMouseDoubleClickAction"),
+    action("X1MouseUpAction"),          action("X1MouseDownAction"),        action("X1MouseDoubleClickAction"),
+    action("X2MouseUpAction"),          action("X2MouseDownAction"),        action("X2MouseDoubleClickAction"),
+    action("MouseOverAction"),          action("MouseLeaveAction"),
+    action("MouseScrollUpAction"),      action("MouseScrollDownAction"),    action("MouseScrollLeftAction"),
+    action("MouseScrollRightAction"),
+    ];
+
+/// Options of every measure.   
+const GENERAL_MEASURE: &[OptionSpec] =
+    &[
+    text("Measure"),                    option("MinValue", ValueType::Number), option("MaxValue", ValueType::Number),
+    option("InvertMeasure", ValueType::Bool), option("Disabled", ValueType::Bool), option("Paused", ValueType::Bool),
+    option("UpdateDivider", ValueType::Integer), option("AverageSize", ValueType::Integer), option("DynamicVariables", ValueType::Bool),
+    text("Substitute"),                 option("RegExpSubstitute", ValueType::Bool), text("Group"),
+    action("OnUpdateAction"),           action("OnChangeAction"),
+    option("IfAboveValue", ValueType::Number), option("IfBelowValue", ValueType::Number), option("IfEqualValue", ValueType::Number),
+    action("IfAboveAction"),            action("IfBelowAction"),            action("IfEqualAction"),
+    numbered("IfCondition", ValueType::Formula), numbered("IfTrueAction", ValueType::Action), numbered("IfFalseAction", ValueType::Action),
+    option("IfConditionMode", ValueType::Bool),
+    numbered("IfMatch", ValueType::Text), numbered("IfMatchAction", ValueType::Action), numbered("IfNotMatchAction", ValueType::Action),
+    option("IfMatchMode", ValueType::Bool),
+    ];
+
+/// The meter types, `Meter=`.   
+const METER_TYPES: &[TypeSpec] =
+    &[
+    TypeSpec { name: "Bar", options: &[
+        option("BarImage", ValueType::Path), option("BarColor", ValueType::Color), choice("BarOrientation", ORIENTATION),
+        pixel("BarBorder", ValueType::Integer), option("Flip", ValueType::Bool),
+        ] },
+    TypeSpec { name: "Bitmap", options: &[
+        option("BitmapImage", ValueType::Path), option("BitmapFrames", ValueType::Integer), option("BitmapZeroFrame", ValueType::Bool),
+        option("BitmapExtend", ValueType::Bool), option("BitmapDigits", ValueType::Integer), choice("BitmapAlign", &["Left", "Center", "Right"]),
+        pixel("BitmapSeparation", ValueType::Integer), option("BitmapTransitionFrames", ValueType::Integer),
+        ] },
+    TypeSpec { name: "Button", options: &[
+        option("ButtonImage", ValueType::Path), action("ButtonCommand"),
+        ] },
+    TypeSpec { name: "Histogram", options: &[
+        option("PrimaryColor", ValueType::Color), option("SecondaryColor", ValueType::Color), option("BothColor", ValueType::Color),
+        option("PrimaryImage", ValueType::Path), option("SecondaryImage", ValueType::Path), option("BothImage", ValueType::Path),
+        choice("GraphStart", GRAPH_START), choice("GraphOrientation", ORIENTATION), option("Flip", ValueType::Bool),
+        option("AutoScale", ValueType::Bool),
+        ] },
+    TypeSpec { name: "Image", options: &[
+        option("ImageName", ValueType::Path), option("ImagePath", ValueType::Path), text("ImageCrop"), option("ImageTint", ValueType::Color),
+        option("ImageAlpha", ValueType::Integer), option("Greyscale", ValueType::Bool), choice("ImageFlip", &["None", "Horizontal", "Vertical", "Both"]),
+        option("ImageRotate", ValueType::Number), option("UseExifOrientation", ValueType::Bool), text("ScaleMargins"),
+        choice("PreserveAspectRatio", &["0", "1", "2"]), option("Tile", ValueType::Bool), option("MaskImageName", ValueType::Path),
+        numbered("ColorMatrix", ValueType::Text),
+        ] },
+    TypeSpec { name: "Line", options: &[
+        option("LineCount", ValueType::Integer), numbered("LineColor", ValueType::Color), option("LineWidth", ValueType::Number),
+        option("HorizontalLines", ValueType::Bool), option("HorizontalLineColor", ValueType::Color), choice("GraphStart", GRAPH_START),
+        choice("GraphOrientation", ORIENTATION), option("Flip", ValueType::Bool), option("AutoScale", ValueType::Bool),
+        numbered("Scale", ValueType::Number),
+        ] },
+    TypeSpec { name: "Roundline", options: &[
+        option("StartAngle", ValueType::Number), option("RotationAngle", ValueType::Number), pixel("LineLength", ValueType::Number),
+        pixel("LineStart", ValueType::Number), option("LineWidth", ValueType::Number), option("LineColor", ValueType::Color),
+        option("Solid", ValueType::Bool), option("ControlAngle", ValueType::Bool), option("ControlLength", ValueType::Bool),
+        option("ControlStart", ValueType::Bool), option("ValueRemainder", ValueType::Integer),
+        ] },
+    TypeSpec { name: "Rotator", options: &[
+        option("ImageName", ValueType::Path), pixel("OffsetX", ValueType::Number), pixel("OffsetY", ValueType::Number),
+        option("StartAngle", ValueType::Number), option("RotationAngle", ValueType::Number), option("ValueRemainder", ValueType::Integer),
+        ] },
+    TypeSpec { name: "Shape", options: &[
+        numbered("Shape", ValueType::Text),
+        ] },
+    TypeSpec { name: "String", options: &[
+        text("Text"), text("Prefix"), text("Postfix"), text("FontFace"), option("FontSize", ValueType::Number),
+        option("FontColor", ValueType::Color), option("FontWeight", ValueType::Integer), choice("StringAlign", ALIGN),
+        choice("StringStyle", &["Normal", "Bold", "Italic", "BoldItalic"]), choice("StringCase", &["None", "Upper", "Lower", "Proper"]),
+        choice("StringEffect", &["None", "Shadow", "Border"]), option("FontEffectColor", ValueType::Color),
+        choice("ClipString", &["0", "1", "2"]), pixel("ClipStringW", ValueType::Integer), pixel("ClipStringH", ValueType::Integer),
+        option("NumOfDecimals", ValueType::Integer), option("Scale", ValueType::Number), option("Percentual", ValueType::Bool),
+        choice("AutoScale", &["0", "1", "2", "3", "1k", "2k", "3k"]), option("Angle", ValueType::Number),
+        numbered("InlineSetting", ValueType::Text), numbered("InlinePattern", ValueType::Text),
+        ] },
+    ];
+
+/// The measure types, `Measure=`.   
+const MEASURE_TYPES: &[TypeSpec] =
+    &[
+    TypeSpec { name: "Calc", options: &[
+        option("Formula", ValueType::Formula), option("UpdateRandom", ValueType::Bool), option("UniqueRandom", ValueType::Bool),
+        option("LowBound", ValueType::Integer), option("HighBound", ValueType::Integer),
+        ] },
+    TypeSpec { name: "CPU", options: &[
+        option("Processor", ValueType::Integer),
+        ] },
+    TypeSpec { name: "FreeDiskSpace", options: &[
+        text("Drive"), option("Total", ValueType::Bool), option("Label", ValueType::Bool), option("Type", ValueType::Bool),
+        option("IgnoreRemovable", ValueType::Bool), option("DiskQuota", ValueType::Bool),
+        ] },
+    TypeSpec { name: "Loop", options: &[
+        option("StartValue", ValueType::Integer), option("EndValue", ValueType::Integer), option("Increment", ValueType::Integer),
+        option("LoopCount", ValueType::Integer),
+        ] },
+    TypeSpec { name: "Memory",         options: &[option("Total", ValueType::Bool)] },
+    TypeSpec { name: "PhysicalMemory", options: &[option("Total", ValueType::Bool)] },
+    TypeSpec { name: "SwapMemory",     options: &[option("Total", ValueType::Bool)] },
+    TypeSpec { name: "NetIn",          options: &[option("Interface", ValueType::Text), option("Cumulative", ValueType::Bool)] },
+    TypeSpec { name: "NetOut",         options: &[option("Interface", ValueType::Text), option("Cumulative", ValueType::Bool)] },
+    TypeSpec { name: "NetTotal",       options: &[option("Interface", ValueType::Text), option("Cumulative", ValueType::Bool)] },
+    TypeSpec { name: "Plugin",         options: &[text("Plugin")] },
+    TypeSpec { name: "Registry", options: &[
+        choice("RegHKey", &["HKEY_CURRENT_USER", "HKEY_LOCAL_MACHINE", "HKEY_CLASSES_ROOT", "HKEY_CURRENT_CONFIG", "HKEY_USERS"]),
+        text("RegKey"), text("RegValue"), choice("OutputType", &["Value", "Name"]),
+        ] },
+    TypeSpec { name: "Script",         options: &[option("ScriptFile", ValueType::Path)] },
+    TypeSpec { name: "String",         options: &[text("String")] },
+    TypeSpec { name: "Time", options: &[
+        text("Format"), text("FormatLocale"), text("TimeZone"), option("DaylightSavingTime", ValueType::Bool),
+        text("TimeStamp"), text("TimeStampFormat"), text("TimeStampLocale"),
+        ] },
+    TypeSpec { name: "Uptime", options: &[
+        text("Format"), option("AddDaysToHours", ValueType::Bool), option("SecondsValue", ValueType::Integer),
+        ] },
+    TypeSpec { name: "WebParser", options: &[
+        text("URL"), text("RegExp"), option("StringIndex", ValueType::Integer), option("StringIndex2", ValueType::Integer),
+        option("UpdateRate", ValueType::Integer), option("Download", ValueType::Bool), text("DownloadFile"),
+        action("FinishAction"), action("OnRegExpErrorAction"), action("OnConnectErrorAction"), action("OnDownloadErrorAction"),
+        option("Debug", ValueType::Integer), text("UserAgent"), text("Header"), option("LogSubstringErrors", ValueType::Bool),
+        option("DecodeCharacterReference", ValueType::Integer), option("ForceReload", ValueType::Bool), option("CodePage", ValueType::Integer),
+        ] },
+    ];
+
+/// The plugins coming with Rainmeter, `Measure=Plugin` and `Plugin=`.   
+const PLUGINS: &[TypeSpec] =
+    &[
+    TypeSpec { name: "UsageMonitor", options: &[
+        choice("Category", &["Processor", "Memory", "LogicalDisk", "PhysicalDisk", "Network Interface", "Process", "GPU Engine",
+                             "GPU Process Memory", "GPU Adapter Memory"]),
+        text("Counter"), text("Name"), option("Index", ValueType::Integer), text("Blacklist"), text("Whitelist"),
+        ] },
+    TypeSpec { name: "PerfMon", options: &[
+        text("PerfMonObject"), text("PerfMonCounter"), text("PerfMonInstance"), option("PerfMonDifference", ValueType::Bool),
+        ] },
+    TypeSpec { name: "PowerPlugin", options: &[
+        choice("PowerState", &["ACLine", "Status", "Status2", "Lifetime", "Percent", "MHz", "Hz"]), text("Format"),
+        ] },
+    TypeSpec { name: "SysInfo", options: &[
+        text("SysInfoType"), option("SysInfoData", ValueType::Integer),
+        ] },
+    TypeSpec { name: "RecycleManager", options: &[
+        choice("RecycleType", &["Count", "Size"]), text("Drives"),
+        ] },
+    TypeSpec { name: "PingPlugin", options: &[
+        text("DestAddress"), option("Timeout", ValueType::Integer), option("TimeoutValue", ValueType::Number),
+        option("UpdateRate", ValueType::Integer), action("FinishAction"),
+        ] },
+    TypeSpec { name: "Process", options: &[
+        text("ProcessName"),
+        ] },
+    TypeSpec { name: "FileView", options: &[
+        option("Path", ValueType::Path), text("Type"), option("Index", ValueType::Integer), option("Count", ValueType::Integer),
+        option("Recursive", ValueType::Integer), option("ShowDotDot", ValueType::Bool), option("ShowFile", ValueType::Bool),
+        option("ShowFolder", ValueType::Bool), option("ShowHidden", ValueType::Bool), option("ShowSystem", ValueType::Bool),
+        text("Extensions"), text("SortType"), option("SortAscending", ValueType::Bool), action("FinishAction"),
+        ] },
+    ];
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// The type of the values of an option.   
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ValueType
+{
+    Text,                       // anything
+    Path,                       // a file name, like #@#Images\bar.png
+    Action,                     // bangs in brackets
+    Formula,                    // a Calc formula
+    Integer,                    // 12, -3
+    Number,                     // 1.5, 500e9
+    Bool,                       // 0 or 1
+    Color,                      // 255,128,0,200 or FF8000C8
+    Choice,                     // one of the allowed values
+    Position,                   // X and Y: a number, relative to the previous meter with r or R
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// An option of a meter, measure or plugin.   
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct OptionSpec
+{
+    pub(crate) name:     &'static str,              // in the casing of the Rainmeter manual
+    pub(crate) kind:     ValueType,
+    pub(crate) allowed:  &'static [&'static str],   // for ValueType::Choice
+    pub(crate) pixel:    bool,                      // in pixels, scaled with the skin
+    pub(crate) numbered: bool,                      // also with a number, like MeasureName2
+}
+
+/// A meter type, measure type or plugin with its own options.   
+#[derive(Debug)]
+pub(crate) struct TypeSpec
+{
+    pub(crate) name:    &'static str,
+    pub(crate) options: &'static [OptionSpec],
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+impl OptionSpec
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  matches / accepts / expected   
+/// **`TYPE:       `**  methods of OptionSpec   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Whether a key is this option (case is ignored, as by Rainmeter), whether a value is valid for it, and a text telling   
+/// the values expected. Values with variables or section variables, and formulas in parentheses for numbers, are only   
+/// known at runtime and are accepted. An empty value gives the default.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn matches(&self, key: &str) -> bool
+    {
+        match key.get(.. self.name.len())
+            {
+            Some(start) if start.eq_ignore_ascii_case(self.name) =>
+                {
+                let number = &key[self.name.len() ..];
+                number.is_empty() || (self.numbered && number.chars().all(|c| c.is_ascii_digit()))
+                },
+            _ => false,
+            }
+    }
+
+pub(crate) fn accepts(&self, value: &str) -> bool
+    {
+        let value = value.trim();
+        if value.is_empty() || value.contains('#') || value.contains('[') { return true; }
+        let formula = value.starts_with('(') && value.ends_with(')');
+        match self.kind
+            {
+            ValueType::Text | ValueType::Path | ValueType::Action | ValueType::Formula => true,
+            ValueType::Integer  => formula || value.parse::<i64>().is_ok(),
+            ValueType::Number   => formula || value.parse::<f64>().is_ok(),
+            ValueType::Bool     => formula || value == "0" || value == "1",
+            ValueType::Choice   => self.allowed.iter().any(|allowed| allowed.eq_ignore_ascii_case(value)),
+            ValueType::Position =>
+                {
+                let number = value.strip_suffix(['r', 'R']).unwrap_or(value);
+                (number.starts_with('(') && number.ends_with(')')) || number.parse::<f64>().is_ok()
+                },
+            ValueType::Color    =>
+                {
+                let parts: Vec<&str> = value.split(',').map(str::trim).collect();
+                if parts.len() == 1
+                    {
+                    (value.len() == 6 || value.len() == 8) && value.chars().all(|c| c.is_ascii_hexdigit())
+                    }
+                else
+                    {
+                    (parts.len() == 3 || parts.len() == 4)
+                    && parts.iter().all(|part| (part.starts_with('(') && part.ends_with(')')) || part.parse::<u8>().is_ok())
+                    }
+                },
+            }
+    }
+
+pub(crate) fn expected(&self) -> String
+    {
+        match self.kind
+            {
+            ValueType::Integer  => "a whole number".to_string(),
+            ValueType::Number   => "a number".to_string(),
+            ValueType::Bool     => "0 or 1".to_string(),
+            ValueType::Color    => "a color like 255,128,0 or 255,128,0,200 or FF8000".to_string(),
+            ValueType::Choice   => format!("one of {}", self.allowed.join(", ")),
+            ValueType::Position => "a number of pixels, relative to the previous meter with r or R".to_string(),
+            _                   => "any text".to_string(),
+            }
+    }
+
+} // End of impl: OptionSpec
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  option / text / action / pixel / position / numbered / choice   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The kinds of options, to keep the tables above short.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+const fn option(name: &'static str, kind: ValueType) -> OptionSpec
+{
+OptionSpec { name, kind, allowed: &[], pixel: false, numbered: false }
+}
+
+const fn text(name: &'static str) -> OptionSpec
+{
+option(name, ValueType::Text)
+}
+
+const fn action(name: &'static str) -> OptionSpec
+{
+option(name, ValueType::Action)
+}
+
+const fn pixel(name: &'static str, kind: ValueType) -> OptionSpec
+{
+OptionSpec { name, kind, allowed: &[], pixel: true, numbered: false }
+}
+
+const fn position(name: &'static str) -> OptionSpec
+{
+pixel(name, ValueType::Position)
+}
+
+const fn numbered(name: &'static str, kind: ValueType) -> OptionSpec
+{
+OptionSpec { name, kind, allowed: &[], pixel: false, numbered: true }
+}
+
+const fn choice(name: &'static str, allowed: &'static [&'static str]) -> OptionSpec
+{
+OptionSpec { name, kind: ValueType::Choice, allowed, pixel: false, numbered: false }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  meter_type / measure_type / plugin / meter_type_names / measure_type_names   
+/// **`TYPE:       `**  common functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name           `** the value of `Meter=`, `Measure=` or `Plugin=`   
+/// **`RETURNS:    `** **` Option         `** the type with its own options, None if the schema does not know it   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Plugins may be named by their file, like `Plugins\UsageMonitor.dll` in old skins. The names of all types are used   
+/// for suggestions.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn meter_type(name: &str) -> Option<&'static TypeSpec>
+{
+METER_TYPES.iter().find(|meter| meter.name.eq_ignore_ascii_case(name.trim()))
+}
+
+pub(crate) fn measure_type(name: &str) -> Option<&'static TypeSpec>
+{
+MEASURE_TYPES.iter().find(|measure| measure.name.eq_ignore_ascii_case(name.trim()))
+}
+
+pub(crate) fn plugin(name: &str) -> Option<&'static TypeSpec>
+{
+let file = name.trim().rsplit(['\\', '/']).next().unwrap_or("");
+let bare = file.get(file.len().saturating_sub(4) ..).filter(|end| end.eq_ignore_ascii_case(".dll")).map_or(file, |_| &file[.. file.len() - 4]);
+PLUGINS.iter().find(|plugin| plugin.name.eq_ignore_ascii_case(bare))
+}
+
+pub(crate) fn meter_type_names() -> impl Iterator<Item = &'static str>
+{
+METER_TYPES.iter().map(|meter| meter.name)
+}
+
+pub(crate) fn measure_type_names() -> impl Iterator<Item = &'static str>
+{
+MEASURE_TYPES.iter().map(|measure| measure.name)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  general_meter_options / general_measure_options   
+/// **`TYPE:       `**  common functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`RETURNS:    `** **` Iterator       `** the options of every meter (with the mouse actions), or of every measure   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn general_meter_options() -> impl Iterator<Item = &'static OptionSpec>
+{
+GENERAL_METER.iter().chain(MOUSE_ACTIONS.iter())
+}
+
+pub(crate) fn general_measure_options() -> impl Iterator<Item = &'static OptionSpec>
+{
+GENERAL_MEASURE.iter()
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for schema   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** options_and_values_are_known()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the lookup of types and plugins, numbered options and the values options accept   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn options_and_values_are_known()
+  {
+  let bar = meter_type("BAR").unwrap();
+  let orientation = bar.options.iter().find(|option| option.matches("barorientation")).unwrap();
+  assert!(orientation.accepts("HORIZONTAL") && !orientation.accepts("Diagonal"));
+  assert!(measure_type("FreeDiskSpace").unwrap().options.iter().any(|option| option.matches("IgnoreRemovable")));
+  assert_eq!(plugin("Plugins\\UsageMonitor.dll").unwrap().name, "UsageMonitor");
+  assert!(plugin("SomeoneElses").is_none());
+  let image = meter_type("Image").unwrap();
+  assert!(image.options.iter().any(|option| option.matches("ImagePath")) && !image.options.iter().any(|option| option.matches("Path")));
+
+  let measure_name = general_meter_options().find(|option| option.name == "MeasureName").unwrap();
+  assert!(measure_name.matches("MeasureName3") && !measure_name.matches("MeasureNameX"));
+  let x = general_meter_options().find(|option| option.name == "X").unwrap();
+  assert!(x.pixel && x.accepts("6r") && x.accepts("([Meter_Frame:X]+38)") && !x.accepts("6px"));
+  let color = option("SolidColor", ValueType::Color);
+  assert!(color.accepts("0,0,0,140") && color.accepts("FF8000") && !color.accepts("0,0,300") && !color.accepts("red"));
+  }
+
+} // End of: mod test
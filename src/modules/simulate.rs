@@ -0,0 +1,426 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** simulate.rs 🦀   
+//! **`DESCRIPTION:`** evaluates the Calc measures and IfConditions of a skin offline, with mock values for the other measures   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | a measure may name itself, like a counter   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! arise simulate StorageMon --values mock.json   
+//!   
+//! mock.json:  { "Measure_Drive_C_Total": 500e9, "Measure_Drive_C_Used": 0 }   
+//!   
+//! [Measure_Drive_C_Total] IfCondition=(Measure_Drive_C_Total=0) is false --> IfFalseAction=[!ShowMeterGroup "MeterGroup_Drive_C"]   
+//! [Measure_Drive_C_PercentUsed] Formula=100/(...) is 0, division by zero, 'Measure_Drive_C_Used' is 0   
+//!```
+//! The skin is evolved from `<skin>.arise` in the input directory, a file ending in `.ini` is read as it is. The mock   
+//! values are numbers, or texts of numbers, by the name of a measure; `#Name#` gives a value to a variable. Measures   
+//! which are no Calc measures and have no mock value are 0, like a drive which does not exist. A Calc measure which   
+//! fails is 0 for the formulas using it, as in Rainmeter. A Calc measure naming itself, like `Formula=Count + 1`, gets   
+//! its value of the update before there: its mock value, else 0.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::config::{AriseConfig, ARISE_FILE_EXTENSION, SKIN_FILE_EXTENSION};
+use crate::modules::core_logic::evolve_skin;
+use crate::modules::diagnostics::{Diagnostic, closest_name};
+use crate::modules::ini::{IniDocument, IniSection};
+use crate::modules::ini_syntax::SyntaxTree;
+use crate::modules::calc::{Formula, is_formula_key, parse_formula};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+//___ none ___
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// A formula of the skin and what it gave.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Outcome
+{
+    pub(crate) section: String,
+    pub(crate) key:     String,
+    pub(crate) formula: String,
+    pub(crate) value:   Result<f64, String>,
+    pub(crate) action:  Option<(String, String)>,       // the IfTrueAction or IfFalseAction which fires
+}
+
+/// The outcomes of all formulas, and the measures taken as 0 for lack of a mock value.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Simulation
+{
+    pub(crate) outcomes: Vec<Outcome>,
+    pub(crate) unmocked: Vec<String>,
+}
+
+/// The measures and variables of a skin, with the values found so far.   
+struct Evaluator<'a>
+{
+    measures:  BTreeMap<String, &'a IniSection>,
+    variables: BTreeMap<String, String>,
+    mocks:     &'a BTreeMap<String, f64>,
+    values:    BTreeMap<String, Result<f64, String>>,
+    busy:      Vec<String>,                           // the measures and #variables# being evaluated, the innermost last
+    unmocked:  Vec<String>,
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+impl Evaluator<'_>
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  measure / formula / leaf   
+/// **`TYPE:       `**  methods of Evaluator   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The value of a measure: its mock value, else the value of its formula for a Calc measure, else 0. A Calc measure   
+/// is evaluated once, when it is needed first; the result of its formula is kept, with the error if it failed.   
+/// A Calc measure naming itself is evaluated even with a mock value, which is its previous value in its formula.   
+/// Only measures depending on each other are an error.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | references to itself give the previous value   
+/// ___________________________________________________________________________________________________________________________
+fn measure(&mut self, name: &str) -> Result<f64, String>
+    {
+        let lower    = name.to_lowercase();
+        let previous = self.mocks.get(&lower).copied();
+        if self.busy.iter().rfind(|busy| !busy.starts_with('#')) == Some(&lower) { return Ok(previous.unwrap_or(0.0)); }
+        if let Some(start) = self.busy.iter().position(|busy| *busy == lower)
+            {
+            let cycle: Vec<String> = self.busy[start ..].iter().filter(|busy| !busy.starts_with('#'))
+                                                         .map(|busy| format!("[{}]", self.measures.get(busy).map_or(busy.as_str(), |section| section.name.as_str())))
+                                                         .collect();
+            return Err(format!("the measures {} depend on each other", cycle.join(", ")));
+            }
+        if let Some(value) = self.values.get(&lower) { return Ok(*value.as_ref().unwrap_or(&0.0)); }
+        let section = self.measures.get(&lower).copied();
+        let formula = section.filter(|section| value_of(section, "Measure").is_some_and(|kind| kind.eq_ignore_ascii_case("Calc")))
+                             .map(|section| value_of(section, "Formula").unwrap_or_default());
+        let counter = formula.as_deref().and_then(|formula| parse_formula(formula).ok())
+                             .is_some_and(|formula| formula.names().iter().any(|found| found.eq_ignore_ascii_case(name)));
+        if let Some(value) = previous.filter(|_| !counter) { return Ok(value); }
+        let Some(section) = section else { return Err(format!("unknown measure '{}'", name)); };
+
+        if !value_of(section, "Measure").is_some_and(|kind| kind.eq_ignore_ascii_case("Calc"))
+            {
+            self.unmocked.push(section.name.clone());
+            self.values.insert(lower, Ok(0.0));
+            return Ok(0.0);
+            }
+        self.busy.push(lower.clone());
+        let value = self.formula(&formula.unwrap_or_default());
+        self.busy.pop();
+        self.values.insert(lower, value.clone());
+        Ok(value.unwrap_or(0.0))
+    }
+
+fn formula(&mut self, text: &str) -> Result<f64, String>
+    {
+        let formula = parse_formula(text).map_err(|why| why.message)?;
+        formula.evaluate(&mut |leaf| self.leaf(leaf))
+    }
+
+fn leaf(&mut self, leaf: &Formula) -> Result<f64, String>
+    {
+        match leaf
+            {
+            Formula::Variable(name) =>
+                {
+                let lower = format!("#{}#", name.to_lowercase());
+                if let Some(value) = self.mocks.get(&lower) { return Ok(*value); }
+                let Some(text) = self.variables.get(&name.to_lowercase()).cloned() else { return Err(format!("unknown variable '#{}#'", name)); };
+                if self.busy.contains(&lower) { return Err(format!("variable #{}# depends on itself", name)); }
+                self.busy.push(lower);
+                let value = self.formula(&text).map_err(|why| format!("#{}#={}: {}", name, text, why));
+                self.busy.pop();
+                value
+                },
+            Formula::Name(name) | Formula::Section(name) => self.measure(name),
+            _                                            => Ok(0.0),
+            }
+    }
+
+} // End of impl: Evaluator
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  simulate_skin   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` conf_p        `** the configuration, a skin by name is read from its input directory   
+/// **`            `** **` skin_p        `** the name of the skin, or a Rainmeter skin (.ini)   
+/// **`            `** **` values_p      `** the JSON file with the mock values, if any   
+/// **`RETURNS:    `** **` Result -->    `** - OK(the report of the simulation)   
+/// **`            `** **`     or -->    `** - Error, if the skin or the mock values can't be read   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn simulate_skin(conf_p: &AriseConfig, skin_p: &str, values_p: Option<&Path>) -> Result<String, Box<dyn Error>>
+{
+let skin_file = if Path::new(skin_p).extension().is_some_and(|extension| extension.eq_ignore_ascii_case(SKIN_FILE_EXTENSION))
+    {
+    Path::new(skin_p).to_path_buf()
+    }
+else
+    {
+    conf_p.inp_pathpart.join(skin_p).with_extension(ARISE_FILE_EXTENSION)
+    };
+let mut bytes = fs::read(&skin_file).map_err(|why| Diagnostic::error(&skin_file, 0, format!("couldn't read the skin: {}", why)))?;
+if skin_file != Path::new(skin_p)
+    {
+    bytes = evolve_skin(&skin_file, &String::from_utf8_lossy(&bytes))?;
+    }
+let skin = SyntaxTree::decode(&bytes).map_err(|why| Diagnostic::error(&skin_file, 0, why))?.to_document();
+
+let mut mocks = BTreeMap::new();
+if let Some(values_file) = values_p
+    {
+    let text = fs::read_to_string(values_file).map_err(|why| Diagnostic::error(values_file, 0, format!("couldn't read the mock values: {}", why)))?;
+    mocks = parse_mocks(&text).map_err(|why| Diagnostic::error(values_file, 0, why))?;
+    }
+
+let simulation = simulate(&skin, &mocks);
+let mut report = vec![format!("simulated '{}' with {} mock value(s)", skin_file.display(), mocks.len())];
+for name in mocks.keys().filter(|name| !name.starts_with('#') && !skin.sections.iter().any(|section| section.name.eq_ignore_ascii_case(name)))
+    {
+    let closest = closest_name(name, skin.sections.iter().map(|section| section.name.as_str()));
+    report.push(format!("warning: the skin has no measure '{}'{}", name, closest.map(|closest| format!(", did you mean '{}'?", closest)).unwrap_or_default()));
+    }
+report.extend(simulation.outcomes.iter().map(describe));
+if !simulation.unmocked.is_empty()
+    {
+    report.push(format!("no mock value, taken as 0: {}", simulation.unmocked.join(", ")));
+    }
+let failed = simulation.outcomes.iter().filter(|outcome| outcome.value.is_err()).count();
+let fired  = simulation.outcomes.iter().filter(|outcome| outcome.action.is_some()).count();
+report.push(format!("{} formula(s) evaluated, {} failed, {} action(s) fire", simulation.outcomes.len(), failed, fired));
+info!("simulated '{}', {} formula(s), {} failed", skin_file.display(), simulation.outcomes.len(), failed);
+Ok(report.join("\n"))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_mocks   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text          `** a JSON object, like `{ "Measure_Drive_C_Total": 500e9, "#Scale#": "2" }`   
+/// **`RETURNS:    `** **` Result -->    `** - OK(the values by their name)   
+/// **`            `** **`     or -->    `** - Error, for no object or a value which is no number   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_mocks(text: &str) -> Result<BTreeMap<String, f64>, String>
+{
+let json: serde_json::Value = serde_json::from_str(text).map_err(|why| format!("the mock values are no valid JSON: {}", why))?;
+let Some(object) = json.as_object() else { return Err("the mock values are no JSON object of names and numbers".to_string()); };
+let mut mocks = BTreeMap::new();
+for (name, value) in object
+    {
+    let number = match value
+        {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(text)   => text.trim().parse::<f64>().ok(),
+        serde_json::Value::Bool(truth)    => Some(if *truth { 1.0 } else { 0.0 }),
+        _                                 => None,
+        };
+    let Some(number) = number else { return Err(format!("the mock value of '{}' is no number: {}", name, value)); };
+    mocks.insert(name.clone(), number);
+    }
+Ok(mocks)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  simulate   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` skin          `** the evolved skin   
+/// **`            `** **` mocks         `** the mock values by name, `#Name#` for variables   
+/// **`RETURNS:    `** **` Simulation    `** the outcomes of the formulas and IfConditions, in the order of the skin   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// An IfCondition fires its IfTrueAction (IfTrueAction2, ...) if its value is not 0, else its IfFalseAction; a   
+/// condition which fails fires nothing.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn simulate(skin: &IniDocument, mocks: &BTreeMap<String, f64>) -> Simulation
+{
+let mocks = mocks.iter().map(|(name, value)| (name.to_lowercase(), *value)).collect();
+let mut evaluator = Evaluator { measures: BTreeMap::new(), variables: BTreeMap::new(), mocks: &mocks, values: BTreeMap::new(),
+                                busy: Vec::new(), unmocked: Vec::new() };
+for section in &skin.sections
+    {
+    if section.name.eq_ignore_ascii_case("Variables")
+        {
+        evaluator.variables.extend(section.keys().map(|key| (key.name.to_lowercase(), key.value.trim().to_string())));
+        }
+    else if value_of(section, "Measure").is_some()
+        {
+        evaluator.measures.entry(section.name.to_lowercase()).or_insert(section);
+        }
+    }
+
+let mut outcomes = Vec::new();
+for section in skin.sections.iter().filter(|section| value_of(section, "Measure").is_some())
+    {
+    for key in section.keys().filter(|key| is_formula_key(&key.name) && !key.value.trim().is_empty())
+        {
+        let is_formula = key.name.eq_ignore_ascii_case("Formula");
+        let value = if is_formula
+            {
+            if !value_of(section, "Measure").is_some_and(|kind| kind.eq_ignore_ascii_case("Calc")) { continue; }
+            evaluator.measure(&section.name)
+                     .and_then(|value| evaluator.values.get(&section.name.to_lowercase()).cloned().unwrap_or(Ok(value)))
+            }
+        else
+            {
+            evaluator.formula(&key.value)
+            };
+        let action = match (&value, key.name.get("IfCondition".len() ..))
+            {
+            (Ok(value), Some(number)) if !is_formula =>
+                {
+                let name = format!("{}{}", if *value != 0.0 { "IfTrueAction" } else { "IfFalseAction" }, number);
+                value_of(section, &name).map(|action| (name, action))
+                },
+            _                         => None,
+            };
+        outcomes.push(Outcome { section: section.name.clone(), key: key.name.clone(), formula: key.value.trim().to_string(), value, action });
+        }
+    }
+Simulation { outcomes, unmocked: evaluator.unmocked }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  value_of / describe / number   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// The value of a key of a section; a line of the report; a number without needless decimals.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn value_of(section: &IniSection, name: &str) -> Option<String>
+{
+section.keys().find(|key| key.name.eq_ignore_ascii_case(name)).map(|key| key.value.trim().to_string())
+}
+
+fn describe(outcome: &Outcome) -> String
+{
+let head = format!("[{}] {}={}", outcome.section, outcome.key, outcome.formula);
+match (&outcome.value, &outcome.action)
+    {
+    (Err(why), _)                        => format!("{} is 0, {}", head, why),
+    (Ok(value), _) if outcome.key.eq_ignore_ascii_case("Formula") => format!("{} is {}", head, number(*value)),
+    (Ok(value), Some((name, action)))    => format!("{} is {} --> {}={}", head, *value != 0.0, name, action),
+    (Ok(value), None)                    => format!("{} is {}, no action", head, *value != 0.0),
+    }
+}
+
+fn number(value: f64) -> String
+{
+let text = format!("{:.6}", value);
+text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for simulate   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** drives_are_simulated()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the values of Calc measures, the actions of IfConditions and the report of a division by zero   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn drives_are_simulated()
+  {
+  let skin = IniDocument::parse("[Variables]\nScale=#Half# * 4\nHalf=0.5\n\
+                                 [Total]\nMeasure=FreeDiskSpace\nTotal=1\nIfCondition=(Total=0)\nIfTrueAction=[!HideMeterGroup C]\nIfFalseAction=[!ShowMeterGroup C]\n\
+                                 [Used]\nMeasure=FreeDiskSpace\n[Free]\nMeasure=FreeDiskSpace\n\
+                                 [PercentUsed]\nMeasure=Calc\nFormula=100/(Total / Used)\n\
+                                 [PercentFree]\nMeasure=Calc\nFormula=(Free = 0 ? 0 : Round(100/(Total / Free), 1)) * #Scale# / 2 + PercentUsed\n\
+                                 IfCondition=PercentFree > 50\nIfFalseAction=[!Log low]\n");
+  let mocks = parse_mocks(r#"{ "Total": 500e9, "used": 0, "Free": "150e9" }"#).unwrap();
+  let simulation = simulate(&skin, &mocks);
+
+  let lines: Vec<String> = simulation.outcomes.iter().map(describe).collect();
+  assert_eq!(lines, ["[Total] IfCondition=(Total=0) is false --> IfFalseAction=[!ShowMeterGroup C]",
+                     "[PercentUsed] Formula=100/(Total / Used) is 0, division by zero, 'Used' is 0",
+                     "[PercentFree] Formula=(Free = 0 ? 0 : Round(100/(Total / Free), 1)) * #Scale# / 2 + PercentUsed is 30",
+                     "[PercentFree] IfCondition=PercentFree > 50 is false --> IfFalseAction=[!Log low]"]);
+  assert!(simulation.unmocked.is_empty());
+  assert!(simulate(&skin, &BTreeMap::new()).unmocked == ["Total", "Used", "Free"]);
+  assert!(parse_mocks(r#"{ "Total": "big" }"#).is_err());
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** counters_count_up()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that a Calc measure naming itself counts up from its mock value or 0, and that a cycle of   
+  /// **`            `** two measures is an error   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn counters_count_up()
+  {
+  let skin = IniDocument::parse("[Count]\nMeasure=Calc\nFormula=Count + 1\n[Twice]\nMeasure=Calc\nFormula=Count * 2\n\
+                                 [Ping]\nMeasure=Calc\nFormula=Pong\n[Pong]\nMeasure=Calc\nFormula=Ping + 1\n");
+  let values = |mocks: &str| -> Vec<Result<f64, String>> { simulate(&skin, &parse_mocks(mocks).unwrap()).outcomes.into_iter().map(|outcome| outcome.value).collect() };
+
+  assert_eq!(values("{}"),               [Ok(1.0), Ok(2.0), Ok(0.0), Err("the measures [Ping], [Pong] depend on each other".to_string())]);
+  assert_eq!(values(r#"{ "Count": 5 }"#)[.. 2], [Ok(6.0), Ok(12.0)]);
+  }
+
+} // End of: mod test
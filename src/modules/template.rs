@@ -0,0 +1,1560 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** template.rs 🦀   
+//! **`DESCRIPTION:`** reads an arise generator definition (.arise) and splits it into control assignments, multiplier lists,   
+//! offset variables and the sections to be evolved into the skin   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation, literals moved here from core_logic.rs, multiplier lists from data files   
+//! 0.2      | 2026-10-19 | Clunion   | named blocks, the classic sections are aliases of them   
+//! 0.3      | 2026-10-19 | Clunion   | ARISE_ORDER and ARISE_SEPARATOR control assignments   
+//! 0.4      | 2026-10-19 | Clunion   | <:include "file":> inside blocks   
+//! 0.5      | 2026-10-19 | Clunion   | <:extends "file":>, templates inheriting blocks, controls and lists   
+//! 0.6      | 2026-10-19 | Clunion   | <:macro ...:> definitions, see macros.rs   
+//! 0.7      | 2026-10-19 | Clunion   | <:syntax ...:> directive for other delimiters   
+//! 0.8      | 2026-10-19 | Clunion   | the warning for unused multiplier lists moved to usage.rs   
+//! 0.9      | 2026-10-19 | Clunion   | types of control assignments, declared like NAME: color := ...   
+//! 0.10     | 2026-10-19 | Clunion   | ARISE_FRAME, ARISE_LINE_ENDING and ARISE_ENCODING control assignments   
+//! 0.11     | 2026-10-19 | Clunion   | ARISE_KEY_CASE control assignment   
+//! 0.12     | 2026-10-19 | Clunion   | cycles of extends are reported as such, not as includes   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! RAINMETER_EXE := "C:\Program Files\Rainmeter\Rainmeter.exe"      <-- control assignment   
+//!   
+//! <multiplier list begin>   
+//!     <:drive:>   :=   'CDEFGHIJKLMNOPQRSTUVWXYZ'                  <-- one item per character   
+//!     <:sensor:>  :=   @data("sensors.csv")                        <-- one item per row of the data file   
+//! <multiplier list end>   
+//!   
+//! <offset variables begin>   
+//!     <:vertical offset 1:>   := +20                               <-- 0, 20, 40, ... for the 1st, 2nd, 3rd item   
+//! <offset variables end>   
+//!   
+//! <header begin> ... <header end>                                  <-- and measures, meters, footer   
+//!   
+//! <block styles> ... <block end>                                   <-- a named block, evolved once   
+//! <block drives repeat=drive> ... <block end>                      <-- evolved once per item of <:drive:>   
+//!```
+//! All blocks are written into the skin in the order of the arise file. The classic sections are built-in aliases:   
+//! header and footer are evolved once, measures and meters once per item of the first multiplier list.   
+//!```text
+//! ARISE_ORDER     := interleaved          <-- measures C, meters C, measures D, meters D, ...   
+//! ARISE_ORDER     := grouped              <-- measures C, measures D, ..., meters C, meters D, ... (the default)   
+//! ARISE_SEPARATOR := ";-< <:drive:>: >"   <-- comment in front of every item, filled up with '-', "" for none   
+//! ARISE_FRAME     := none                 <-- no metainfo and no Start/End comments around the blocks   
+//! ARISE_LINE_ENDING := crlf               <-- lf (the default) or crlf   
+//! ARISE_ENCODING  := utf-16               <-- utf-8 (the default), utf-8-bom or utf-16, as Rainmeter writes it   
+//! ARISE_KEY_CASE  := canonical            <-- keys written like in the Rainmeter manual, Barimage becomes BarImage   
+//!```
+//! Interleaved are neighbouring blocks which are multiplied by the same list.   
+//!```text
+//! <header begin>   
+//!     <:include "common/styles.arise":>                 <-- all lines of the file, relative to this file   
+//!     <:include "common/base.arise" block=styles:>      <-- only the lines of its block 'styles'   
+//! <header end>   
+//!```
+//! A template may extend another one and only redefine what is different:   
+//!```text
+//! <:extends "base.arise":>                                          <-- relative to this file   
+//! ARISE_ORDER := interleaved                                        <-- replaces the control of the base   
+//! <meters begin> ... <meters end>                                   <-- replaces the meters of the base, in their place   
+//! <block extra> ... <block end>                                     <-- new blocks follow the blocks of the base   
+//!```
+//! Control assignments, multiplier lists, offset variables and macros of the base are inherited, unless redefined by name.   
+//!   
+//! Files which need the delimiters in their text, like Lua snippets, can change them in their very first line:   
+//!```text
+//! <:syntax placeholder="{{ }}" assign="=" comment="#" multiline="(* *)":>   
+//!   
+//! RAINMETER_EXE = "C:\Program Files\Rainmeter\Rainmeter.exe"     # a control assignment with the new delimiters   
+//! [Meter_{{drive}}]                                                 <-- every option may be left out   
+//!```
+//! A quoted text is written as it is, so `<:'<:':>` (or `{{'{{'}}`) writes a literal `<:` (or `{{`) into the skin.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::data_source::{DataRecord, DATA_SOURCE_KEYWORD, load_data_source};
+use crate::modules::core_logic::read_file_fully;
+use crate::modules::diagnostics::Diagnostic;
+use crate::modules::types::{Type, TYPE_SEPARATOR, assigned_type};
+use crate::modules::macros::{MacroDefinition, DIRECTIVE_MACRO, DIRECTIVE_ENDMACRO, parse_macro};
+use crate::modules::ini_syntax::{Encoding, LineEnding};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const COMMENT_SINGLELINE      : &str = ";";
+pub(crate) const COMMENT_MULTILINE_BEGIN : &str = "/*";
+pub(crate) const COMMENT_MULTILINE_END   : &str = "*/";
+
+pub(crate) const OPERATOR_ASSIGN         : &str = ":=";
+pub(crate) const OPERATOR_PLUS           : &str = "+";
+pub(crate) const OPERATOR_MINUS          : &str = "-";
+
+pub(crate) const KEY_NAME_BEGIN          : &str = "<:";
+pub(crate) const KEY_NAME_END            : &str = ":>";
+
+pub(crate) const MULTIPLIER_LIST_BEGIN   : &str = "<multiplier list begin>";
+pub(crate) const MULTIPLIER_LIST_END     : &str = "<multiplier list end>";
+
+pub(crate) const OFFSET_VARIABLES_BEGIN  : &str = "<offset variables begin>";
+pub(crate) const OFFSET_VARIABLES_END    : &str = "<offset variables end>";
+
+pub(crate) const SECTION_HEADER_BEGIN    : &str = "<header begin>";
+pub(crate) const SECTION_HEADER_END      : &str = "<header end>";
+pub(crate) const SECTION_MEASURES_BEGIN  : &str = "<measures begin>";
+pub(crate) const SECTION_MEASURES_END    : &str = "<measures end>";
+pub(crate) const SECTION_METERS_BEGIN    : &str = "<meters begin>";
+pub(crate) const SECTION_METERS_END      : &str = "<meters end>";
+pub(crate) const SECTION_FOOTER_BEGIN    : &str = "<footer begin>";
+pub(crate) const SECTION_FOOTER_END      : &str = "<footer end>";
+
+pub(crate) const BLOCK_BEGIN             : &str = "<block";
+pub(crate) const BLOCK_END               : &str = "<block end>";
+pub(crate) const BLOCK_OPTION_REPEAT     : &str = "repeat";
+
+pub(crate) const DIRECTIVE_INCLUDE       : &str = "include";
+pub(crate) const INCLUDE_OPTION_BLOCK    : &str = "block";
+pub(crate) const DIRECTIVE_EXTENDS       : &str = "extends";
+
+pub(crate) const DIRECTIVE_SYNTAX        : &str = "syntax";
+pub(crate) const SYNTAX_PLACEHOLDER      : &str = "placeholder";
+pub(crate) const SYNTAX_ASSIGN           : &str = "assign";
+pub(crate) const SYNTAX_COMMENT          : &str = "comment";
+pub(crate) const SYNTAX_MULTILINE        : &str = "multiline";
+
+pub(crate) const CONTROL_ORDER           : &str = "ARISE_ORDER";
+pub(crate) const ORDER_GROUPED           : &str = "grouped";
+pub(crate) const ORDER_INTERLEAVED       : &str = "interleaved";
+pub(crate) const CONTROL_SEPARATOR       : &str = "ARISE_SEPARATOR";
+pub(crate) const SEPARATOR_WIDTH         : usize = 54;      // as wide as the hand made ';-< C: >---...' comments
+pub(crate) const CONTROL_FRAME           : &str = "ARISE_FRAME";
+pub(crate) const FRAME_COMMENTS          : &str = "comments";
+pub(crate) const FRAME_NONE              : &str = "none";
+pub(crate) const CONTROL_LINE_ENDING     : &str = "ARISE_LINE_ENDING";
+pub(crate) const CONTROL_ENCODING        : &str = "ARISE_ENCODING";
+pub(crate) const CONTROL_KEY_CASE        : &str = "ARISE_KEY_CASE";
+pub(crate) const KEY_CASE_KEEP           : &str = "keep";
+pub(crate) const KEY_CASE_CANONICAL      : &str = "canonical";
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SectionKind
+{
+    Header,
+    Measures,
+    Meters,
+    Footer,
+    Block,      // a user defined <block name ...>
+}
+
+/// How the copies of neighbouring blocks multiplied by the same list are arranged.   
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum BlockOrder
+{
+    #[default]
+    Grouped,        // all copies of a block, then all copies of the next block
+    Interleaved,    // the copies of all blocks for the 1st item, then for the 2nd item, ...
+}
+
+/// What the evolved skin gets around the blocks.   
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SkinFrame
+{
+    #[default]
+    Comments,       // the metainfo and a Start/End comment around every block
+    None,           // only the evolved blocks, e.g. for templates regenerating an imported skin
+}
+
+/// How the keys of the meters and measures are written into the evolved skin.   
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum KeyCase
+{
+    #[default]
+    Keep,           // as written in the template
+    Canonical,      // like in the Rainmeter manual, for the options the schema knows
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParseState
+{
+    Control,
+    MultiplierList(usize),     // line number of the begin marker
+    OffsetVariables(usize),    // line number of the begin marker
+    Section(SectionKind),
+    Macro,
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// One line of an arise file, remembering where it came from (for diagnostics).   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SourceLine
+{
+    pub(crate) file:   PathBuf,
+    pub(crate) number: usize,
+    pub(crate) text:   String,
+}
+
+/// A `NAME := value` line of the generator control section, or `NAME: type := value` with a declared type.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ControlAssignment
+{
+    pub(crate) name:       String,
+    pub(crate) value:      String,
+    pub(crate) value_type: Type,          // declared, or inferred from the value
+    pub(crate) line:       SourceLine,
+}
+
+/// A `<:name:> := ...` line of the multiplier list, with all of its items.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Multiplier
+{
+    pub(crate) name:   String,
+    pub(crate) items:  Vec<DataRecord>,
+    pub(crate) source: Option<PathBuf>,    // data file the items were loaded from, None for inline lists
+    pub(crate) line:   SourceLine,
+}
+
+/// A `<:name:> := +20` line of the offset variables, the value grows by `step` with every item.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct OffsetVariable
+{
+    pub(crate) name: String,
+    pub(crate) step: i64,
+    pub(crate) line: SourceLine,
+}
+
+/// The delimiters of one arise file, the constants above unless changed by `<:syntax ...:>` in its first line.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Syntax
+{
+    pub(crate) placeholder_begin: String,
+    pub(crate) placeholder_end:   String,
+    pub(crate) assign:            String,
+    pub(crate) comment:           String,
+    pub(crate) multiline_begin:   String,
+    pub(crate) multiline_end:     String,
+}
+
+/// The lines between a `<... begin>` and `<... end>` marker, or a `<block name ...>` and `<block end>`.   
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TemplateSection
+{
+    pub(crate) kind:   SectionKind,
+    pub(crate) name:   String,
+    pub(crate) repeat: Option<String>,     // name of the multiplier list the block is evolved for, None: evolved once
+    pub(crate) begin:  SourceLine,
+    pub(crate) lines:  Vec<SourceLine>,
+}
+
+/// Everything read from one arise generator definition.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct AriseTemplate
+{
+    pub(crate) path:         PathBuf,
+    pub(crate) controls:     Vec<ControlAssignment>,
+    pub(crate) multipliers:  Vec<Multiplier>,
+    pub(crate) offsets:      Vec<OffsetVariable>,
+    pub(crate) sections:     Vec<TemplateSection>,
+    pub(crate) macros:       Vec<MacroDefinition>,
+    pub(crate) order:        BlockOrder,
+    pub(crate) frame:        SkinFrame,
+    pub(crate) line_ending:  LineEnding,
+    pub(crate) encoding:     Encoding,
+    pub(crate) key_case:     KeyCase,
+    pub(crate) dependencies: Vec<PathBuf>,   // all files the generated skin depends on: the template and its data files
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl Default for Syntax
+{
+fn default() -> Syntax
+    {
+        Syntax
+            {
+            placeholder_begin: KEY_NAME_BEGIN.to_string(),
+            placeholder_end:   KEY_NAME_END.to_string(),
+            assign:            OPERATOR_ASSIGN.to_string(),
+            comment:           COMMENT_SINGLELINE.to_string(),
+            multiline_begin:   COMMENT_MULTILINE_BEGIN.to_string(),
+            multiline_end:     COMMENT_MULTILINE_END.to_string(),
+            }
+    }
+
+} // End of impl: Default for Syntax
+
+
+impl Syntax
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  normalize   
+/// **`TYPE:       `**  method of Syntax   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` line         `** a line of the arise file, written with these delimiters   
+/// **`RETURNS:    `** **` String       `** the line with the placeholders written as `<:name:>`   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// All later steps only know `<:` and `:>`. A `<:` which is no placeholder in this file is turned into `<:'<:':>`,   
+/// so it is written into the skin as it is.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn normalize(&self, line: &str) -> String
+    {
+        if self.placeholder_begin == KEY_NAME_BEGIN && self.placeholder_end == KEY_NAME_END { return line.to_string(); }
+
+        let mut result = String::new();
+        let mut rest   = line;
+        while let Some(c) = rest.chars().next()
+            {
+            if let Some(after) = rest.strip_prefix(self.placeholder_begin.as_str())
+                {
+                if let Some(end) = after.find(self.placeholder_end.as_str())
+                    {
+                    result.push_str(&format!("{}{}{}", KEY_NAME_BEGIN, &after[.. end], KEY_NAME_END));
+                    rest = &after[end + self.placeholder_end.len() ..];
+                    continue;
+                    }
+                }
+            if let Some(after) = rest.strip_prefix(KEY_NAME_BEGIN)
+                {
+                result.push_str(&format!("{}'{}'{}", KEY_NAME_BEGIN, KEY_NAME_BEGIN, KEY_NAME_END));
+                rest = after;
+                continue;
+                }
+            result.push(c);
+            rest = &rest[c.len_utf8() ..];
+            }
+        result
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  normalize_assignment   
+/// **`TYPE:       `**  method of Syntax   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text         `** a line of the control section, the multiplier list or the offset variables   
+/// **`RETURNS:    `** **` String       `** the line with the first assignment operator written as `:=`   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn normalize_assignment(&self, text: &str) -> String
+    {
+        match text.find(self.assign.as_str())
+            {
+            Some(pos) if self.assign != OPERATOR_ASSIGN => format!("{} {} {}", text[.. pos].trim_end(), OPERATOR_ASSIGN, text[pos + self.assign.len() ..].trim_start()),
+            _                                           => text.to_string(),
+            }
+    }
+
+} // End of impl: Syntax
+
+
+impl SectionKind
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  from_begin_marker   
+/// **`TYPE:       `**  method of SectionKind   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` marker       `** a trimmed line of the arise file   
+/// **`RETURNS:    `** **` Option       `** the kind of section started by the marker, None if it is no begin marker   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn from_begin_marker(marker: &str) -> Option<SectionKind>
+    {
+        match marker
+            {
+            SECTION_HEADER_BEGIN   => Some(SectionKind::Header),
+            SECTION_MEASURES_BEGIN => Some(SectionKind::Measures),
+            SECTION_METERS_BEGIN   => Some(SectionKind::Meters),
+            SECTION_FOOTER_BEGIN   => Some(SectionKind::Footer),
+            _                      => None,
+            }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  end_marker   
+/// **`TYPE:       `**  method of SectionKind   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` &str         `** the literal closing this kind of section   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn end_marker(self) -> &'static str
+    {
+        match self
+            {
+            SectionKind::Header   => SECTION_HEADER_END,
+            SectionKind::Measures => SECTION_MEASURES_END,
+            SectionKind::Meters   => SECTION_METERS_END,
+            SectionKind::Footer   => SECTION_FOOTER_END,
+            SectionKind::Block    => BLOCK_END,
+            }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  block_name   
+/// **`TYPE:       `**  method of SectionKind   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` &str         `** the name of the block a classic section is an alias for, "" for named blocks   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn block_name(self) -> &'static str
+    {
+        match self
+            {
+            SectionKind::Header   => "header",
+            SectionKind::Measures => "measures",
+            SectionKind::Meters   => "meters",
+            SectionKind::Footer   => "footer",
+            SectionKind::Block    => "",
+            }
+    }
+
+} // End of impl: SectionKind
+
+
+impl AriseTemplate
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  block   
+/// **`TYPE:       `**  method of AriseTemplate   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** name of the block to look for, the classic sections are named header, measures, ...   
+/// **`RETURNS:    `** **` Option       `** the block, None if the template does not contain it   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, as section(kind)   
+/// 1.1     | 2026-10-19 | Clunion   | looks up blocks by name   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn block(&self, name: &str) -> Option<&TemplateSection>
+    {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  multiplier   
+/// **`TYPE:       `**  method of AriseTemplate   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** name of a multiplier list, without the placeholder brackets   
+/// **`RETURNS:    `** **` Option       `** the multiplier list, None if there is no such list   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn multiplier(&self, name: &str) -> Option<&Multiplier>
+    {
+        self.multipliers.iter().find(|multiplier| multiplier.name == name)
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  control   
+/// **`TYPE:       `**  method of AriseTemplate   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** name of a control assignment   
+/// **`RETURNS:    `** **` Option       `** the assigned value, None if there is no such assignment   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+#[allow(dead_code)]
+pub(crate) fn control(&self, name: &str) -> Option<&str>
+    {
+        self.controls.iter().rev().find(|assignment| assignment.name == name).map(|assignment| assignment.value.as_str())
+    }
+
+} // End of impl: AriseTemplate
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_template   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` path           `** path of the arise file, used for diagnostics and to resolve data files   
+/// **`            `** **` text           `** content of the arise file   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the parsed template)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Walks line by line through the arise definition. Multi line comments are removed everywhere, single line comments   
+/// only outside of the sections (inside they are Rainmeter comments and get copied into the skin).   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | named blocks   
+/// 1.2     | 2026-10-19 | Clunion   | block order   
+/// 1.3     | 2026-10-19 | Clunion   | includes   
+/// 1.4     | 2026-10-19 | Clunion   | extends, checks of the whole template moved here   
+/// 1.5     | 2026-10-19 | Clunion   | the warning for unused multiplier lists moved to usage.rs   
+/// 1.6     | 2026-10-19 | Clunion   | ARISE_KEY_CASE   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn parse_template(path: &Path, text: &str) -> Result<AriseTemplate, Box<dyn Error>>
+{
+let template = parse_included_template(path, text, &mut vec![canonical(path)])?;
+
+debug!("template {}: {} control assignments, {} multipliers, {} offset variables, {} sections",
+       path.display(), template.controls.len(), template.multipliers.len(), template.offsets.len(), template.sections.len());
+Ok(template)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_included_template   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` path           `** path of the arise file, used for diagnostics and to resolve data files   
+/// **`            `** **` text           `** content of the arise file   
+/// **`            `** **` includes       `** the files being included at the moment, this one last, to detect cycles   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the parsed template)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, the body of parse_template   
+/// 1.1     | 2026-10-19 | Clunion   | extends   
+/// 1.2     | 2026-10-19 | Clunion   | delimiters of the file   
+/// ___________________________________________________________________________________________________________________________
+fn parse_included_template(path: &Path, text: &str, includes: &mut Vec<PathBuf>) -> Result<AriseTemplate, Box<dyn Error>>
+{
+let mut template = AriseTemplate { path: path.to_path_buf(), dependencies: vec![path.to_path_buf()], ..AriseTemplate::default() };
+let (syntax, syntax_line) = parse_syntax(path, text)?;
+let mut state            = ParseState::Control;
+let mut in_comment       = false;
+let mut current: Option<TemplateSection> = None;
+let mut base:    Option<(SourceLine, String)> = None;
+let mut macro_definition: Option<(SourceLine, String, Vec<SourceLine>)> = None;     // the macro line, its head and its lines
+
+for (index, raw_line) in text.lines().enumerate()
+    {
+    let number = index + 1;
+    if Some(number) == syntax_line { continue; }
+    let (line, had_comment) = strip_multiline_comment(raw_line, &mut in_comment, &syntax);
+    let line    = syntax.normalize(&line);
+    let trimmed = line.trim();
+    if had_comment && trimmed.is_empty() { continue; }
+
+    let source_line = SourceLine { file: path.to_path_buf(), number, text: line.clone() };
+
+    match state
+        {
+        ParseState::Control =>
+            {
+            let trimmed = syntax.normalize_assignment(strip_singleline_comment(trimmed, &syntax).trim());
+            let trimmed = trimmed.as_str();
+            if trimmed.is_empty() { continue; }
+
+            if directive_arguments(trimmed, DIRECTIVE_SYNTAX).is_some()
+                {
+                return Err(Diagnostic::error(path, number, format!("'{}' has to be the first line of the file", trimmed))
+                                      .with_help("the delimiters can't change in the middle of a file").into());
+                }
+            if trimmed == MULTIPLIER_LIST_BEGIN  { state = ParseState::MultiplierList(number);  continue; }
+            if trimmed == OFFSET_VARIABLES_BEGIN { state = ParseState::OffsetVariables(number); continue; }
+            let section = match SectionKind::from_begin_marker(trimmed)
+                {
+                Some(kind)                                => Some(TemplateSection { kind, name: kind.block_name().to_string(), repeat: None, begin: source_line.clone(), lines: Vec::new() }),
+                None if is_block_begin(trimmed)           => Some(parse_block_begin(trimmed, source_line.clone())?),
+                None                                      => None,
+                };
+            if let Some(section) = section
+                {
+                if let Some(earlier) = template.block(&section.name)
+                    {
+                    return Err(Diagnostic::error(path, number, format!("block '{}' is defined twice", section.name))
+                                          .with_help(format!("it is defined first in line {}", earlier.begin.number)).into());
+                    }
+                state   = ParseState::Section(section.kind);
+                current = Some(section);
+                continue;
+                }
+
+            if let Some(head) = directive_arguments(trimmed, DIRECTIVE_MACRO)
+                {
+                macro_definition = Some((source_line, head.to_string(), Vec::new()));
+                state = ParseState::Macro;
+                continue;
+                }
+
+            if let Some(arguments) = directive_arguments(trimmed, DIRECTIVE_EXTENDS)
+                {
+                if let Some((earlier, _)) = &base
+                    {
+                    return Err(Diagnostic::error(path, number, "a template can extend only one other template")
+                                          .with_help(format!("it extends another one in line {}", earlier.number)).into());
+                    }
+                match quoted_file(arguments)
+                    {
+                    Some((file, "")) => base = Some((source_line, file.to_string())),
+                    _                => return Err(Diagnostic::error(path, number, format!("the file to extend has to be quoted: '{}'", trimmed))
+                                                              .with_help(format!("write it like {}{} \"base.arise\"{}", KEY_NAME_BEGIN, DIRECTIVE_EXTENDS, KEY_NAME_END)).into()),
+                    }
+                continue;
+                }
+
+            match split_assignment(trimmed)
+                {
+                Some((name, value)) =>
+                    {
+                    let (name, declared) = match name.split_once(TYPE_SEPARATOR)
+                        {
+                        Some((name, declared)) => (name.trim(), Some(declared.trim())),
+                        None                   => (name, None),
+                        };
+                    let value_type = assigned_type(declared, value).map_err(|(message, help)| Diagnostic::error(path, number, message).with_help(help))?;
+                    template.controls.push(ControlAssignment { name: name.to_string(), value: unquote(value), value_type, line: source_line });
+                    },
+                None                => return Err(Diagnostic::error(path, number, format!("unexpected text outside of any section: '{}'", trimmed))
+                                                             .with_help(format!("expected 'NAME {} value' or the begin of a section", OPERATOR_ASSIGN)).into()),
+                }
+            },
+
+        ParseState::MultiplierList(_) =>
+            {
+            let trimmed = syntax.normalize_assignment(strip_singleline_comment(trimmed, &syntax).trim());
+            let trimmed = trimmed.as_str();
+            if trimmed.is_empty() { continue; }
+            if trimmed == MULTIPLIER_LIST_END { state = ParseState::Control; continue; }
+
+            let multiplier = parse_multiplier(trimmed, source_line)?;
+            if let Some(source) = &multiplier.source { template.dependencies.push(source.clone()); }
+            template.multipliers.push(multiplier);
+            },
+
+        ParseState::OffsetVariables(_) =>
+            {
+            let trimmed = syntax.normalize_assignment(strip_singleline_comment(trimmed, &syntax).trim());
+            let trimmed = trimmed.as_str();
+            if trimmed.is_empty() { continue; }
+            if trimmed == OFFSET_VARIABLES_END { state = ParseState::Control; continue; }
+
+            template.offsets.push(parse_offset_variable(trimmed, source_line)?);
+            },
+
+        ParseState::Macro =>
+            {
+            let Some((macro_line, head, lines)) = macro_definition.as_mut() else { continue; };
+            if placeholder_name(trimmed) == Some(DIRECTIVE_ENDMACRO)
+                {
+                let definition = parse_macro(macro_line, head, lines)?;
+                if let Some(earlier) = template.macros.iter().find(|known| known.name == definition.name)
+                    {
+                    return Err(Diagnostic::error(path, macro_line.number, format!("macro '{}' is defined twice", definition.name))
+                                          .with_help(format!("it is defined first in line {}", earlier.line.number)).into());
+                    }
+                template.macros.push(definition);
+                macro_definition = None;
+                state = ParseState::Control;
+                continue;
+                }
+            match directive_arguments(trimmed, DIRECTIVE_INCLUDE)
+                {
+                Some(arguments) => lines.extend(include_lines(&source_line, arguments, &syntax, includes, &mut template.dependencies)?),
+                None            => lines.push(source_line),
+                }
+            },
+
+        ParseState::Section(kind) =>
+            {
+            if trimmed == kind.end_marker()
+                {
+                if let Some(section) = current.take() { template.sections.push(section); }
+                state = ParseState::Control;
+                continue;
+                }
+            if let Some(section) = current.as_mut()
+                {
+                match directive_arguments(trimmed, DIRECTIVE_INCLUDE)
+                    {
+                    Some(arguments) => section.lines.extend(include_lines(&source_line, arguments, &syntax, includes, &mut template.dependencies)?),
+                    None            => section.lines.push(source_line),
+                    }
+                }
+            },
+        }
+    }
+
+match state
+    {
+    ParseState::Control                 => {},
+    ParseState::MultiplierList(begin)   => return Err(Diagnostic::error(path, begin, format!("multiplier list is not closed, missing '{}'", MULTIPLIER_LIST_END)).into()),
+    ParseState::OffsetVariables(begin)  => return Err(Diagnostic::error(path, begin, format!("offset variables are not closed, missing '{}'", OFFSET_VARIABLES_END)).into()),
+    ParseState::Section(kind)           =>
+        {
+        let begin = current.map(|section| section.begin.number).unwrap_or(0);
+        return Err(Diagnostic::error(path, begin, format!("section is not closed, missing '{}'", kind.end_marker())).into());
+        },
+    ParseState::Macro                   =>
+        {
+        let (line, head, _) = macro_definition.unwrap_or_default();
+        let name = head.split('(').next().unwrap_or_default().trim().to_string();
+        return Err(Diagnostic::error(path, line.number, format!("macro '{}' is not closed, missing '{}{}{}'", name, KEY_NAME_BEGIN, DIRECTIVE_ENDMACRO, KEY_NAME_END)).into());
+        },
+    }
+
+if let Some((line, file)) = base
+    {
+    template = inherit(template, &line, &file, includes)?;
+    }
+
+// measures and meters are multiplied by the first list, named blocks by the list they name:
+for section in &mut template.sections
+    {
+    match section.kind
+        {
+        SectionKind::Measures | SectionKind::Meters =>
+            {
+            section.repeat = template.multipliers.first().map(|multiplier| multiplier.name.clone());
+            },
+        SectionKind::Block =>
+            {
+            if let Some(list) = &section.repeat
+                {
+                if !template.multipliers.iter().any(|multiplier| &multiplier.name == list)
+                    {
+                    let known: Vec<&str> = template.multipliers.iter().map(|multiplier| multiplier.name.as_str()).collect();
+                    return Err(Diagnostic::error(path, section.begin.number, format!("block '{}' repeats the unknown multiplier list '{}'", section.name, list))
+                                          .with_help(format!("known multiplier lists: {}", if known.is_empty() { "none".to_string() } else { known.join(", ") })).into());
+                    }
+                }
+            },
+        _ => {},
+        }
+    }
+
+template.order       = control_choice(&template, CONTROL_ORDER, &[(ORDER_GROUPED, BlockOrder::Grouped), (ORDER_INTERLEAVED, BlockOrder::Interleaved)])?;
+template.frame       = control_choice(&template, CONTROL_FRAME, &[(FRAME_COMMENTS, SkinFrame::Comments), (FRAME_NONE, SkinFrame::None)])?;
+template.line_ending = control_choice(&template, CONTROL_LINE_ENDING, &[("lf", LineEnding::Lf), ("crlf", LineEnding::CrLf)])?;
+template.encoding    = control_choice(&template, CONTROL_ENCODING, &[("utf-8", Encoding::Utf8), ("utf-8-bom", Encoding::Utf8Bom), ("utf-16", Encoding::Utf16Le)])?;
+template.key_case    = control_choice(&template, CONTROL_KEY_CASE, &[(KEY_CASE_KEEP, KeyCase::Keep), (KEY_CASE_CANONICAL, KeyCase::Canonical)])?;
+Ok(template)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  control_choice   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` template       `** the template with all of its control assignments   
+/// **`            `** **` name           `** the control, like ARISE_ORDER   
+/// **`            `** **` choices        `** the values allowed, the first one is the default   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the choice of the last assignment, the default without any)   
+/// **`            `** **`     or -->     `** - Error, for values which are no choice   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, generalized from the ARISE_ORDER check   
+/// ___________________________________________________________________________________________________________________________
+fn control_choice<T: Copy>(template: &AriseTemplate, name: &str, choices: &[(&str, T)]) -> Result<T, Diagnostic>
+{
+let Some(assignment) = template.controls.iter().rev().find(|assignment| assignment.name == name) else { return Ok(choices[0].1); };
+match choices.iter().find(|(value, _)| *value == assignment.value)
+    {
+    Some((_, choice)) => Ok(*choice),
+    None              =>
+        {
+        let values: Vec<String> = choices.iter().map(|(value, _)| format!("'{}'", value)).collect();
+        let (last, others) = values.split_last().unwrap_or((&values[0], &[]));
+        Err(Diagnostic::error(&assignment.line.file, assignment.line.number, format!("unknown {} '{}'", name, assignment.value))
+                       .with_help(format!("use {} or {}", others.join(", "), last)))
+        },
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  directive_arguments   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** trimmed line of the arise file   
+/// **`            `** **` keyword        `** the directive looked for, like include or extends   
+/// **`RETURNS:    `** **` Option         `** the text after the keyword, None if the line is no such directive   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, as include_directive   
+/// 1.1     | 2026-10-19 | Clunion   | any keyword   
+/// ___________________________________________________________________________________________________________________________
+fn directive_arguments<'a>(text: &'a str, keyword: &str) -> Option<&'a str>
+{
+let rest = placeholder_name(text)?.strip_prefix(keyword)?;
+if rest.starts_with(char::is_whitespace) { Some(rest.trim()) } else { None }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  quoted_file   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` arguments      `** the arguments of a directive, like `"base.arise" block=styles`   
+/// **`RETURNS:    `** **` Option         `** the quoted file name and the trimmed rest, None if there are no quotes   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn quoted_file(arguments: &str) -> Option<(&str, &str)>
+{
+let (file, rest) = arguments.strip_prefix('"')?.split_once('"')?;
+Some((file, rest.trim()))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  read_included   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` line           `** the line naming the file, for diagnostics and to resolve the path   
+/// **`            `** **` file           `** the file as written, relative to the file of the line   
+/// **`            `** **` includes       `** the files being read at the moment, the new one is pushed on success   
+/// **`            `** **` directive      `** the directive reading the file, DIRECTIVE_INCLUDE or DIRECTIVE_EXTENDS   
+/// **`RETURNS:    `** **` Result -->     `** - OK(path and content of the file)   
+/// **`            `** **`     or -->     `** - Error, if the file can't be read or is already being read   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, taken from include_lines   
+/// 1.1     | 2026-10-19 | Clunion   | cycles name the directive which closes them   
+/// ___________________________________________________________________________________________________________________________
+fn read_included(line: &SourceLine, file: &str, includes: &mut Vec<PathBuf>, directive: &str) -> Result<(PathBuf, String), Box<dyn Error>>
+{
+let path = match line.file.parent()
+    {
+    Some(dir) => dir.join(file),
+    None      => PathBuf::from(file),
+    };
+let identity = canonical(&path);
+if let Some(first) = includes.iter().position(|included| included == &identity)
+    {
+    let mut cycle: Vec<String> = includes[first ..].iter().map(|included| included.display().to_string()).collect();
+    cycle.push(identity.display().to_string());
+    let verb = if directive == DIRECTIVE_EXTENDS { DIRECTIVE_EXTENDS.to_string() } else { format!("{}s", directive) };
+    return Err(Diagnostic::error(&line.file, line.number, format!("'{}' {} itself", file, verb))
+                          .with_help(format!("{} cycle: {}", directive, cycle.join(" -> "))).into());
+    }
+let text = match read_file_fully(&path)
+    {
+    Ok(text) => text,
+    Err(why) => return Err(Diagnostic::error(&line.file, line.number, format!("couldn't read the included file '{}': {}", path.display(), why)).into()),
+    };
+includes.push(identity);
+Ok((path, text))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  inherit   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` template       `** the extending template, as written   
+/// **`            `** **` line           `** the line with the extends directive   
+/// **`            `** **` file           `** the template to extend, relative to the extending one   
+/// **`            `** **` includes       `** the files being read at the moment, to detect cycles   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the base template with the redefinitions of the extending one)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Controls, multiplier lists, offset variables and blocks are matched by name. A redefinition takes the place of the   
+/// inherited one, everything new is appended after the inherited ones.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn inherit(template: AriseTemplate, line: &SourceLine, file: &str, includes: &mut Vec<PathBuf>) -> Result<AriseTemplate, Box<dyn Error>>
+{
+let (path, text) = read_included(line, file, includes, DIRECTIVE_EXTENDS)?;
+let mut base = parse_included_template(&path, &text, includes)?;
+includes.pop();
+
+merge_by_name(&mut base.controls,    template.controls,    |control|    control.name.clone());
+merge_by_name(&mut base.multipliers, template.multipliers, |multiplier| multiplier.name.clone());
+merge_by_name(&mut base.offsets,     template.offsets,     |offset|     offset.name.clone());
+merge_by_name(&mut base.sections,    template.sections,    |section|    section.name.clone());
+merge_by_name(&mut base.macros,      template.macros,      |definition| definition.name.clone());
+
+let mut dependencies = template.dependencies;
+dependencies.extend(base.dependencies);
+Ok(AriseTemplate { path: template.path, dependencies, ..base })
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  merge_by_name   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` inherited      `** the entries of the base template, redefined in place   
+/// **`            `** **` redefined      `** the entries of the extending template   
+/// **`            `** **` name           `** gives the name an entry is matched by   
+/// **`RETURNS:    `** **` <none>         `**   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn merge_by_name<T>(inherited: &mut Vec<T>, redefined: Vec<T>, name: impl Fn(&T) -> String)
+{
+for entry in redefined
+    {
+    match inherited.iter().position(|existing| name(existing) == name(&entry))
+        {
+        Some(index) => inherited[index] = entry,
+        None        => inherited.push(entry),
+        }
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  include_lines   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` line           `** the line containing the include, for diagnostics and to resolve the path   
+/// **`            `** **` arguments      `** the text after the include keyword, like `"common/base.arise" block=styles`   
+/// **`            `** **` syntax         `** the delimiters of the including file, used for whole included files too   
+/// **`            `** **` includes       `** the files being included at the moment, to detect cycles   
+/// **`            `** **` dependencies   `** the files the skin depends on, extended by the included files   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the included lines, each remembering its own file and line number)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Without the block option all lines of the file are included, multi line comments removed. Such a file may include   
+/// other files, but can't contain blocks itself. With the block option the file is read as a whole arise file.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn include_lines(line: &SourceLine, arguments: &str, syntax: &Syntax, includes: &mut Vec<PathBuf>, dependencies: &mut Vec<PathBuf>) -> Result<Vec<SourceLine>, Box<dyn Error>>
+{
+let usage = format!("write it like {}{} \"common/styles.arise\"{} or {}{} \"base.arise\" {}=styles{}",
+                    KEY_NAME_BEGIN, DIRECTIVE_INCLUDE, KEY_NAME_END, KEY_NAME_BEGIN, DIRECTIVE_INCLUDE, INCLUDE_OPTION_BLOCK, KEY_NAME_END);
+let (file, options) = match quoted_file(arguments)
+    {
+    Some(parts) => parts,
+    None        => return Err(Diagnostic::error(&line.file, line.number, format!("the file to include has to be quoted: '{}'", line.text.trim())).with_help(usage).into()),
+    };
+let block = match options.split_once('=')
+    {
+    None if options.is_empty()                    => None,
+    Some((INCLUDE_OPTION_BLOCK, name))            => Some(name.trim()),
+    _                                             => return Err(Diagnostic::error(&line.file, line.number, format!("unknown include option '{}'", options)).with_help(usage).into()),
+    };
+
+let (path, text) = read_included(line, file, includes, DIRECTIVE_INCLUDE)?;
+dependencies.push(path.clone());
+
+let lines = match block
+    {
+    Some(name) =>
+        {
+        let template = parse_included_template(&path, &text, includes)?;
+        dependencies.extend(template.dependencies.iter().skip(1).cloned());
+        match template.block(name)
+            {
+            Some(section) => Ok(section.lines.clone()),
+            None          => Err(Diagnostic::error(&line.file, line.number, format!("'{}' has no block '{}'", path.display(), name))
+                                            .with_help(format!("its blocks are: {}", template.sections.iter().map(|section| section.name.as_str()).collect::<Vec<_>>().join(", "))).into()),
+            }
+        },
+    None =>
+        {
+        let mut lines      = Vec::new();
+        let mut in_comment = false;
+        for (index, raw_line) in text.lines().enumerate()
+            {
+            let (text, had_comment) = strip_multiline_comment(raw_line, &mut in_comment, syntax);
+            let text    = syntax.normalize(&text);
+            let trimmed = text.trim();
+            if had_comment && trimmed.is_empty() { continue; }
+
+            let included = SourceLine { file: path.clone(), number: index + 1, text: text.clone() };
+            if SectionKind::from_begin_marker(trimmed).is_some() || is_block_begin(trimmed)
+                {
+                includes.pop();
+                return Err(Diagnostic::error(&path, index + 1, format!("the included file contains the block '{}'", trimmed))
+                                      .with_help(format!("include a single block with {}=name", INCLUDE_OPTION_BLOCK)).into());
+                }
+            match directive_arguments(trimmed, DIRECTIVE_INCLUDE)
+                {
+                Some(arguments) => lines.extend(include_lines(&included, arguments, syntax, includes, dependencies)?),
+                None            => lines.push(included),
+                }
+            }
+        Ok(lines)
+        },
+    };
+includes.pop();
+lines
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_syntax   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` path           `** path of the arise file, for diagnostics   
+/// **`            `** **` text           `** content of the arise file   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the delimiters of the file, and the number of the line changing them)   
+/// **`            `** **`     or -->     `** - Error, for unknown options or empty delimiters   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Only the first line which is not empty may change the delimiters, it is written with the default ones.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn parse_syntax(path: &Path, text: &str) -> Result<(Syntax, Option<usize>), Diagnostic>
+{
+let mut syntax = Syntax::default();
+let Some((index, first)) = text.lines().enumerate().find(|(_, line)| !line.trim().is_empty()) else { return Ok((syntax, None)); };
+let Some(mut rest) = directive_arguments(first.trim(), DIRECTIVE_SYNTAX) else { return Ok((syntax, None)); };
+
+let number = index + 1;
+let usage  = format!("write it like {}{} {}=\"{{{{ }}}}\" {}=\"=\" {}=\"#\" {}=\"(* *)\"{}",
+                     KEY_NAME_BEGIN, DIRECTIVE_SYNTAX, SYNTAX_PLACEHOLDER, SYNTAX_ASSIGN, SYNTAX_COMMENT, SYNTAX_MULTILINE, KEY_NAME_END);
+while !rest.is_empty()
+    {
+    let (option, value, after) = match rest.split_once('=').and_then(|(option, value)| Some((option.trim(), quoted_file(value.trim_start())?)))
+        {
+        Some((option, (value, after))) => (option, value, after),
+        None                           => return Err(Diagnostic::error(path, number, format!("the values of '{}' have to be quoted", first.trim())).with_help(usage)),
+        };
+    rest = after;
+
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    let pair = match parts.as_slice()
+        {
+        [begin, end] => Some((begin.to_string(), end.to_string())),
+        _            => None,
+        };
+    match (option, pair)
+        {
+        (SYNTAX_PLACEHOLDER, Some((begin, end)))          => { syntax.placeholder_begin = begin; syntax.placeholder_end = end; },
+        (SYNTAX_MULTILINE,   Some((begin, end)))          => { syntax.multiline_begin   = begin; syntax.multiline_end   = end; },
+        (SYNTAX_ASSIGN,  _) if parts.len() == 1           => syntax.assign  = value.trim().to_string(),
+        (SYNTAX_COMMENT, _) if parts.len() == 1           => syntax.comment = value.trim().to_string(),
+        (SYNTAX_PLACEHOLDER, None) | (SYNTAX_MULTILINE, None) =>
+            {
+            return Err(Diagnostic::error(path, number, format!("'{}' needs a begin and an end delimiter, like \"{{{{ }}}}\", got \"{}\"", option, value)).with_help(usage));
+            },
+        (SYNTAX_ASSIGN, _) | (SYNTAX_COMMENT, _)          =>
+            {
+            return Err(Diagnostic::error(path, number, format!("'{}' needs one delimiter without spaces, got \"{}\"", option, value)).with_help(usage));
+            },
+        (other, _)                                        =>
+            {
+            return Err(Diagnostic::error(path, number, format!("unknown {} option '{}'", DIRECTIVE_SYNTAX, other))
+                                  .with_help(format!("known options: {}, {}, {}, {}", SYNTAX_PLACEHOLDER, SYNTAX_ASSIGN, SYNTAX_COMMENT, SYNTAX_MULTILINE)));
+            },
+        }
+    }
+debug!("{}: placeholders {} {}, assignments {}, comments {} and {} {}", path.display(), syntax.placeholder_begin, syntax.placeholder_end,
+       syntax.assign, syntax.comment, syntax.multiline_begin, syntax.multiline_end);
+Ok((syntax, Some(number)))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  canonical   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` path           `** path of a file   
+/// **`RETURNS:    `** **` PathBuf        `** the absolute path without '..', the path itself if the file does not exist   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn canonical(path: &Path) -> PathBuf
+{
+std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  is_block_begin   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** trimmed line of the arise file   
+/// **`RETURNS:    `** **` bool           `** true if the line starts a named block, like `<block styles>`   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn is_block_begin(text: &str) -> bool
+{
+text != BLOCK_END && text.ends_with('>') && text.strip_prefix(BLOCK_BEGIN).is_some_and(|rest| rest.starts_with(char::is_whitespace))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_block_begin   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** trimmed line starting a named block, like `<block drives repeat=drive>`   
+/// **`            `** **` line           `** the source line, for diagnostics   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the still empty block)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn parse_block_begin(text: &str, line: SourceLine) -> Result<TemplateSection, Diagnostic>
+{
+let inner = text[BLOCK_BEGIN.len() .. text.len() - 1].trim();
+let mut words = inner.split_whitespace();
+let name = words.next().unwrap_or_default();
+
+if !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') || name == "end"
+    {
+    return Err(Diagnostic::error(&line.file, line.number, format!("'{}' is not a valid block name", name))
+                          .with_help("a block name consists of letters, digits, '_' and '-'"));
+    }
+
+let mut repeat = None;
+for option in words
+    {
+    match option.split_once('=')
+        {
+        Some((BLOCK_OPTION_REPEAT, list)) => repeat = Some(placeholder_name(list).unwrap_or(list).trim().to_string()),
+        _                                 => return Err(Diagnostic::error(&line.file, line.number, format!("unknown option '{}' of block '{}'", option, name))
+                                                                   .with_help(format!("known options: {}=list", BLOCK_OPTION_REPEAT))),
+        }
+    }
+
+Ok(TemplateSection { kind: SectionKind::Block, name: name.to_string(), repeat, begin: line, lines: Vec::new() })
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_multiplier   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** trimmed line of the multiplier list, like `<:drive:> := 'CDE'`   
+/// **`            `** **` line           `** the source line, for diagnostics and to resolve data files   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the multiplier with all of its items)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version, inline character lists and @data("file")   
+/// ___________________________________________________________________________________________________________________________
+fn parse_multiplier(text: &str, line: SourceLine) -> Result<Multiplier, Box<dyn Error>>
+{
+let (name, value) = match split_assignment(text).and_then(|(name, value)| Some((placeholder_name(name)?, value)))
+    {
+    Some(parts) => parts,
+    None        => return Err(Diagnostic::error(&line.file, line.number, format!("invalid multiplier definition: '{}'", text))
+                                         .with_help(format!("expected '{}name{} {} 'list'' or '... {} {}(\"file\")'", KEY_NAME_BEGIN, KEY_NAME_END, OPERATOR_ASSIGN, OPERATOR_ASSIGN, DATA_SOURCE_KEYWORD)).into()),
+    };
+
+if let Some(data_path) = data_source_path(value)
+    {
+    let data_path = match line.file.parent()
+        {
+        Some(dir) => dir.join(data_path),
+        None      => PathBuf::from(data_path),
+        };
+    let items = match load_data_source(&data_path)
+        {
+        Ok(items) => items,
+        Err(why)  => return Err(Diagnostic::error(&line.file, line.number, format!("couldn't load data for multiplier '{}': {}", name, why)).into()),
+        };
+    return Ok(Multiplier { name: name.to_string(), items, source: Some(data_path), line });
+    }
+
+if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'')
+    {
+    let items = value[1 .. value.len() - 1].chars().map(|c| DataRecord::single(name, &c.to_string())).collect();
+    return Ok(Multiplier { name: name.to_string(), items, source: None, line });
+    }
+
+Err(Diagnostic::error(&line.file, line.number, format!("invalid list for multiplier '{}': {}", name, value))
+               .with_help(format!("use a list of characters like 'CDE' or {}(\"file.csv\")", DATA_SOURCE_KEYWORD)).into())
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  parse_offset_variable   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** trimmed line of the offset variables, like `<:vertical offset 1:> := +20`   
+/// **`            `** **` line           `** the source line, for diagnostics   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the offset variable)   
+/// **`            `** **`     or -->     `** - Error   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn parse_offset_variable(text: &str, line: SourceLine) -> Result<OffsetVariable, Box<dyn Error>>
+{
+let parts = split_assignment(text).and_then(|(name, value)| Some((placeholder_name(name)?, value)));
+let (name, value) = match parts
+    {
+    Some(parts) => parts,
+    None        => return Err(Diagnostic::error(&line.file, line.number, format!("invalid offset variable: '{}'", text)).into()),
+    };
+
+let step = match value.strip_prefix(OPERATOR_PLUS).unwrap_or(value).trim().parse::<i64>()
+    {
+    Ok(step) => step,
+    Err(_)   => return Err(Diagnostic::error(&line.file, line.number, format!("offset '{}' is not a number: '{}'", name, value))
+                                      .with_help(format!("write the offset like '{}20' or '{}4'", OPERATOR_PLUS, OPERATOR_MINUS)).into()),
+    };
+
+Ok(OffsetVariable { name: name.to_string(), step, line })
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  data_source_path   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` value          `** right hand side of a multiplier definition   
+/// **`RETURNS:    `** **` Option         `** the (unresolved) path in `@data("path")`, None if value is no data source   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn data_source_path(value: &str) -> Option<&str>
+{
+let arguments = value.strip_prefix(DATA_SOURCE_KEYWORD)?.trim();
+let argument  = arguments.strip_prefix('(')?.strip_suffix(')')?.trim();
+let path      = argument.strip_prefix('"')?.strip_suffix('"')?;
+Some(path)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  strip_multiline_comment   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` line           `** a raw line of the arise file   
+/// **`            `** **` in_comment     `** state: true if a multi line comment is open at the start of the line   
+/// **`            `** **` syntax         `** the delimiters of the file   
+/// **`RETURNS:    `** **` (String,bool)  `** the line without comment text, true if some comment text was removed   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | delimiters of the file   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn strip_multiline_comment(line: &str, in_comment: &mut bool, syntax: &Syntax) -> (String, bool)
+{
+let mut result  = String::new();
+let mut rest    = line;
+let mut removed = false;
+
+loop
+    {
+    if *in_comment
+        {
+        removed = true;
+        match rest.find(syntax.multiline_end.as_str())
+            {
+            Some(pos) => { rest = &rest[pos + syntax.multiline_end.len() ..]; *in_comment = false; },
+            None      => break,
+            }
+        }
+    else
+        {
+        match rest.find(syntax.multiline_begin.as_str())
+            {
+            Some(pos) => { result.push_str(&rest[.. pos]); rest = &rest[pos + syntax.multiline_begin.len() ..]; *in_comment = true; },
+            None      => { result.push_str(rest); break; },
+            }
+        }
+    }
+(result, removed)
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  strip_singleline_comment   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a line of the control section   
+/// **`            `** **` syntax         `** the delimiters of the file   
+/// **`RETURNS:    `** **` &str           `** the line up to the first comment char which is not inside quotes   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | delimiters of the file   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn strip_singleline_comment<'a>(text: &'a str, syntax: &Syntax) -> &'a str
+{
+let mut in_quotes = false;
+for (pos, c) in text.char_indices()
+    {
+    if c == '"' { in_quotes = !in_quotes; }
+    if !in_quotes && text[pos ..].starts_with(syntax.comment.as_str()) { return &text[.. pos]; }
+    }
+text
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  split_assignment   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a line like `NAME := value`   
+/// **`RETURNS:    `** **` Option         `** the trimmed name and value, None if there is no assignment operator   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn split_assignment(text: &str) -> Option<(&str, &str)>
+{
+let pos = text.find(OPERATOR_ASSIGN)?;
+let name = text[.. pos].trim();
+if name.is_empty() { return None; }
+Some((name, text[pos + OPERATOR_ASSIGN.len() ..].trim()))
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  placeholder_name   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a text like `<:drive:>`   
+/// **`RETURNS:    `** **` Option         `** the trimmed name between the key name literals, None if text is no placeholder   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn placeholder_name(text: &str) -> Option<&str>
+{
+let name = text.strip_prefix(KEY_NAME_BEGIN)?.strip_suffix(KEY_NAME_END)?.trim();
+if name.is_empty() { None } else { Some(name) }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  unquote   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` value          `** a value, maybe enclosed in double quotes   
+/// **`RETURNS:    `** **` String         `** the value without the enclosing quotes   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn unquote(value: &str) -> String
+{
+match value.strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+    {
+    Some(inner) => inner.to_string(),
+    None        => value.to_string(),
+    }
+}
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for template   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** parse_all_parts()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that controls, multipliers, offsets and sections are found, comments are removed   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn parse_all_parts()
+  {
+  let text = "; comment\n/* multi\nline */\nRAINMETER_EXE := \"C:\\Rainmeter.exe\"   ; trailing comment\n\
+              <multiplier list begin>\n  <:drive:> := 'CD'\n<multiplier list end>\n\
+              <offset variables begin>\n  <:y:> := +20\n<offset variables end>\n\
+              <header begin>\n  [Rainmeter]\n  ; kept\n<header end>\n";
+  let template = parse_template(Path::new("Test.arise"), text).unwrap();
+
+  assert_eq!(template.control("RAINMETER_EXE"), Some("C:\\Rainmeter.exe"));
+  assert_eq!(template.multipliers.len(),        1);
+  assert_eq!(template.multipliers[0].name,      "drive");
+  assert_eq!(template.multipliers[0].items,     vec![DataRecord::single("drive", "C"), DataRecord::single("drive", "D")]);
+  assert_eq!(template.offsets[0].step,          20);
+  let header = template.block("header").unwrap();
+  assert_eq!(header.lines.len(),                2);
+  assert_eq!(header.lines[1].number,            13);
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** unclosed_section_is_reported()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that a missing end marker gives an error pointing to the begin marker   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn unclosed_section_is_reported()
+  {
+  let error = parse_template(Path::new("Test.arise"), "\n<meters begin>\n[Meter]\n").unwrap_err();
+  assert!(error.to_string().contains("missing '<meters end>'"));
+  assert!(error.to_string().contains("Test.arise:2"));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** named_blocks()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks named blocks with and without multiplier lists, next to the classic sections   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn named_blocks()
+  {
+  let text = "<multiplier list begin>\n<:drive:> := 'CD'\n<:core:> := '01'\n<multiplier list end>\n\
+              <block styles>\n[Style]\n<block end>\n<meters begin>\n<meters end>\n<block cores repeat=core>\n<block end>\n";
+  let template = parse_template(Path::new("Test.arise"), text).unwrap();
+
+  let names: Vec<(&str, Option<&str>)> = template.sections.iter().map(|section| (section.name.as_str(), section.repeat.as_deref())).collect();
+  assert_eq!(names, vec![("styles", None), ("meters", Some("drive")), ("cores", Some("core"))]);
+  assert_eq!(template.block("styles").unwrap().lines.len(), 1);
+
+  let error = parse_template(Path::new("Test.arise"), "<block drives repeat=drive>\n<block end>\n").unwrap_err();
+  assert!(error.to_string().contains("block 'drives' repeats the unknown multiplier list 'drive'\n  --> Test.arise:1"));
+  let error = parse_template(Path::new("Test.arise"), "<block a>\n<block end>\n<block a>\n<block end>\n").unwrap_err();
+  assert!(error.to_string().contains("block 'a' is defined twice"));
+
+  assert_eq!(template.order, BlockOrder::Grouped);
+  assert_eq!(parse_template(Path::new("Test.arise"), "ARISE_ORDER := interleaved\n").unwrap().order, BlockOrder::Interleaved);
+  let error = parse_template(Path::new("Test.arise"), "\nARISE_ORDER := mixed\n").unwrap_err();
+  assert!(error.to_string().contains("unknown ARISE_ORDER 'mixed'\n  --> Test.arise:2"));
+
+  let template = parse_template(Path::new("Test.arise"), "ARISE_FRAME := none\nARISE_LINE_ENDING := crlf\nARISE_ENCODING := utf-16\n").unwrap();
+  assert_eq!((template.frame, template.line_ending, template.encoding), (SkinFrame::None, LineEnding::CrLf, Encoding::Utf16Le));
+  let error = parse_template(Path::new("Test.arise"), "ARISE_ENCODING := latin-1\n").unwrap_err();
+  assert!(error.to_string().contains("unknown ARISE_ENCODING 'latin-1'"));
+  assert!(error.to_string().contains("use 'utf-8', 'utf-8-bom' or 'utf-16'"));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** includes()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks including whole files and single blocks, relative paths, and that cycles are found   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn includes()
+  {
+  let dir = std::env::temp_dir().join(format!("arise_includes_{}", std::process::id()));
+  std::fs::create_dir_all(dir.join("common")).unwrap();
+  std::fs::write(dir.join("common/styles.arise"), "/* shared */\n[Style]\n<:include \"../base.arise\" block=colors:>\n").unwrap();
+  std::fs::write(dir.join("base.arise"),          "<block colors>\nColor=1\n<block end>\n").unwrap();
+  std::fs::write(dir.join("loop.arise"),          "<block a>\n<:include \"loop.arise\" block=a:>\n<block end>\n").unwrap();
+
+  let template = parse_template(&dir.join("Skin.arise"), "<header begin>\n<:include \"common/styles.arise\":>\nX=1\n<header end>\n").unwrap();
+  let lines: Vec<(String, usize, &str)> = template.block("header").unwrap().lines.iter()
+                                          .map(|line| (line.file.file_name().unwrap().to_string_lossy().to_string(), line.number, line.text.as_str())).collect();
+  assert_eq!(lines, vec![("styles.arise".to_string(), 2, "[Style]"), ("base.arise".to_string(), 2, "Color=1"), ("Skin.arise".to_string(), 3, "X=1")]);
+  assert_eq!(template.dependencies.len(), 3);
+
+  let error = parse_template(&dir.join("Skin.arise"), "<header begin>\n<:include \"loop.arise\" block=a:>\n<header end>\n").unwrap_err();
+  assert!(error.to_string().contains("'loop.arise' includes itself"));
+  assert!(error.to_string().contains("loop.arise:2"));
+
+  std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** extends()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that blocks, controls and lists are inherited and replaced by name, and extends cycles   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn extends()
+  {
+  let dir = std::env::temp_dir().join(format!("arise_extends_{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  std::fs::write(dir.join("base.arise"), "A := 1\nB := 2\n<multiplier list begin>\n<:drive:> := 'CD'\n<multiplier list end>\n\
+                                          <header begin>\n[Rainmeter]\n<header end>\n<meters begin>\n[Meter_<:drive:>]\n<meters end>\n<footer begin>\n; end\n<footer end>\n").unwrap();
+  std::fs::write(dir.join("cycle.arise"), "<:extends \"cycle.arise\":>\n").unwrap();
+
+  let template = parse_template(&dir.join("Tile.arise"), "<:extends \"base.arise\":>\nB := 3\n<multiplier list begin>\n<:drive:> := 'XYZ'\n<multiplier list end>\n\
+                                                          <meters begin>\n[Bar_<:drive:>]\n<meters end>\n<block extra>\n; extra\n<block end>\n").unwrap();
+  let names: Vec<&str> = template.sections.iter().map(|section| section.name.as_str()).collect();
+  assert_eq!(names, vec!["header", "meters", "footer", "extra"]);
+  assert_eq!(template.block("meters").unwrap().lines[0].text, "[Bar_<:drive:>]");
+  assert_eq!(template.block("meters").unwrap().repeat.as_deref(), Some("drive"));
+  assert_eq!(template.control("A"), Some("1"));
+  assert_eq!(template.control("B"), Some("3"));
+  assert_eq!(template.multiplier("drive").unwrap().items.len(), 3);
+  assert_eq!(template.path, dir.join("Tile.arise"));
+
+  let error = parse_template(&dir.join("cycle.arise"), "<:extends \"cycle.arise\":>\n").unwrap_err();
+  assert!(error.to_string().contains("'cycle.arise' extends itself"));
+
+  std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** custom_delimiters()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that the delimiters of a file can be changed and that literal delimiters are kept   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn custom_delimiters()
+  {
+  let text = "\n<:syntax placeholder=\"{{ }}\" assign=\"=\" comment=\"#\" multiline=\"(* *)\":>\nNAME = \"a;b\" # comment\n\
+              <multiplier list begin>\n{{drive}} = 'CD'\n<multiplier list end>\n(* comment *)\n<header begin>\nX={{drive|lower}} <:x:> {{'{{'}};\n<header end>\n";
+  let template = parse_template(Path::new("Test.arise"), text).unwrap();
+  assert_eq!(template.control("NAME"), Some("a;b"));
+  assert_eq!(template.multiplier("drive").unwrap().items.len(), 2);
+  let lines: Vec<&str> = template.block("header").unwrap().lines.iter().map(|line| line.text.as_str()).collect();
+  assert_eq!(lines, vec!["X=<:drive|lower:> <:'<:':>x:> <:'{{':>;"]);
+
+  let error = parse_template(Path::new("Test.arise"), "<:syntax placeholder=\"{{\":>\n").unwrap_err();
+  assert!(error.to_string().contains("'placeholder' needs a begin and an end delimiter"));
+  let error = parse_template(Path::new("Test.arise"), "A := 1\n<:syntax assign=\"=\":>\n").unwrap_err();
+  assert!(error.to_string().contains("has to be the first line of the file\n  --> Test.arise:2"));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** data_source_syntax()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the recognition of @data("file")   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn data_source_syntax()
+  {
+  assert_eq!(data_source_path("@data(\"drives.csv\")"),    Some("drives.csv"));
+  assert_eq!(data_source_path("@data ( \"a b.json\" )"),   Some("a b.json"));
+  assert_eq!(data_source_path("'CDE'"),                    None);
+  }
+
+} // End of: mod test
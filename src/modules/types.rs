@@ -0,0 +1,772 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** types.rs 🦀   
+//! **`DESCRIPTION:`** the types of template values, inferred from their text or declared, and the check of all   
+//! expressions of a template against them, before anything is evolved   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | colors in hex notation, and the types of the color functions   
+//! 0.3      | 2026-10-19 | Clunion   | gradients   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! BAR_HEIGHT        := +20                          <-- integer   
+//! BAR_SCALE         := 1.5                          <-- float   
+//! RAINMETER_EXE     := "C:\Program Files\..."       <-- string, as everything quoted or not looking like another type   
+//! BAR_COLOR         := 255,0,0,200                  <-- color, Rainmeter's r,g,b[,a] notation   
+//! BAR_BACKGROUND    := 202020C8                     <-- color, Rainmeter's RRGGBB[AA] notation   
+//! MODES             := ['Read', 'Write']            <-- list, usable like a multiplier list: <:for m in MODES:>   
+//! BAR_WIDTH: float  := 20                           <-- declared, the value has to fit the type   
+//!```
+//! The items of a multiplier list are records: `<:drive:>` and every field like `<:drive.label:>` get the type   
+//! common to all items. Mixing types, like adding a string to a number or comparing a color with a number, is   
+//! reported for every block and macro before anything is written, even for branches which are never evolved.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::fmt;
+use std::collections::BTreeMap;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::template::{AriseTemplate, SourceLine};
+use crate::modules::expression::{Expr, Value, BinaryOp, UnaryOp, FUNCTION_DEFINED, parse_expression};
+use crate::modules::expansion::{Node, Loop, parse_nodes, placeholders, META_INDEX, META_NUMBER, META_COUNT, LOOP_INDEX, LOOP_NUMBER, LOOP_COUNT, LOOP_SUFFIX};
+use crate::modules::filters::FILTER_FIXED;
+use crate::modules::colors::{Color, ColorArgument, COLOR_FUNCTIONS, GRADIENT_FUNCTIONS, FUNCTION_TO_HEX, FUNCTION_TO_RGB};
+use crate::modules::diagnostics::{Diagnostic, closest_name};
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+pub(crate) const TYPE_SEPARATOR : char = ':';           // declares the type of a control assignment: NAME: color := ...
+
+const TYPE_NAMES : [&str; 6] = ["integer", "float", "string", "color", "list", "record"];
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+/// The type of a template value.   
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) enum Type
+{
+    Integer,
+    Float,
+    #[default]
+    String,
+    Color,
+    List(Box<Type>),    // with the type of its elements
+    Record,             // an item of a multiplier list, its fields have types of their own
+    Bool,               // result of conditions, can't be declared
+    Any,                // not known before evolving, like the parameters of macros
+}
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+/// The types of all names known at a point of a template, the field types of the multiplier lists as well.   
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TypeScope
+{
+    names:   BTreeMap<String, Type>,
+    records: BTreeMap<String, Vec<(String, Type)>>,    // multiplier list name -> its fields, the item itself first
+}
+
+//___ METHODS: ________________________________________________________________________________________________________________
+
+impl Type
+{
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  from_name   
+/// **`TYPE:       `**  method of Type   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` name         `** a declared type, like `color`   
+/// **`RETURNS:    `** **` Result -->   `** - OK(the type)   
+/// **`            `** **`     or -->   `** - Error(message, hint), for unknown types   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn from_name(name: &str) -> Result<Type, (String, String)>
+    {
+        match name
+            {
+            "integer" => Ok(Type::Integer),
+            "float"   => Ok(Type::Float),
+            "string"  => Ok(Type::String),
+            "color"   => Ok(Type::Color),
+            "list"    => Ok(Type::List(Box::new(Type::Any))),
+            "record"  => Ok(Type::Record),
+            _         =>
+                {
+                let help = match closest_name(name, TYPE_NAMES)
+                    {
+                    Some(similar) => format!("did you mean '{}'?", similar),
+                    None          => format!("known types: {}", TYPE_NAMES.join(", ")),
+                    };
+                Err((format!("unknown type '{}'", name), help))
+                },
+            }
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  is_number   
+/// **`TYPE:       `**  method of Type   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` bool         `** true for integers and floats, and for values whose type is not known yet   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn is_number(&self) -> bool
+    {
+        matches!(self, Type::Integer | Type::Float | Type::Any)
+    }
+
+/// ___________________________________________________________________________________________________________________________
+/// **`METHOD:     `**  with_article   
+/// **`TYPE:       `**  method of Type   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` <none>       `**   
+/// **`RETURNS:    `** **` String       `** the name of the type for messages, like "an integer" or "a color"   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn with_article(&self) -> String
+    {
+        let name = self.to_string();
+        if name.starts_with(['a', 'e', 'i', 'o', 'u']) { format!("an {}", name) } else { format!("a {}", name) }
+    }
+
+} // End of impl: Type
+
+
+impl fmt::Display for Type
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+            {
+            Type::Integer                         => write!(f, "integer"),
+            Type::Float                           => write!(f, "float"),
+            Type::String                          => write!(f, "string"),
+            Type::Color                           => write!(f, "color"),
+            Type::List(element) if **element == Type::Any => write!(f, "list"),
+            Type::List(element)                   => write!(f, "list of {}", element),
+            Type::Record                          => write!(f, "record"),
+            Type::Bool                            => write!(f, "boolean"),
+            Type::Any                             => write!(f, "unknown value"),
+            }
+    }
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  infer_type   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a value, like `+20`, `255,0,0` or `C`   
+/// **`RETURNS:    `** **` Type           `** the most specific type the text fits, string for all other texts   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn infer_type(text: &str) -> Type
+{
+let text = text.trim();
+let unsigned = text.strip_prefix('+').unwrap_or(text);
+if unsigned.parse::<i64>().is_ok() { return Type::Integer; }
+if unsigned.chars().any(|c| c.is_ascii_digit()) && unsigned.chars().all(|c| c.is_ascii_digit() || c == '.' || c == '-') && unsigned.parse::<f64>().is_ok() { return Type::Float; }
+if Color::parse(text).is_some() { return Type::Color; }
+if let Some(items) = list_items(text)
+    {
+    let element = items.iter().map(|item| infer_type(item)).reduce(unify).unwrap_or(Type::Any);
+    return Type::List(Box::new(element));
+    }
+Type::String
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  assigned_type   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` declared       `** the declared type, like `color` in `NAME: color := ...`, None if not declared   
+/// **`            `** **` value          `** the assigned value, as written (maybe quoted)   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the declared type, or the type inferred from the value)   
+/// **`            `** **`     or -->     `** - Error(message, hint), for unknown types and values not fitting them   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// A quoted value is always a string, unless declared otherwise.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn assigned_type(declared: Option<&str>, value: &str) -> Result<Type, (String, String)>
+{
+let quoted = value.len() >= 2 && ['"', '\''].iter().any(|quote| value.starts_with(*quote) && value.ends_with(*quote));
+let inner = if quoted { &value[1 .. value.len() - 1] } else { value };
+let inferred = if quoted { Type::String } else { infer_type(value) };
+
+let declared = match declared
+    {
+    None       => return Ok(inferred),
+    Some(name) => Type::from_name(name)?,
+    };
+let fits = match &declared
+    {
+    Type::String  => true,
+    Type::Float   => matches!(infer_type(inner), Type::Integer | Type::Float),
+    Type::List(_) => matches!(infer_type(inner), Type::List(_)),
+    Type::Record  => return Err(("a control variable can't be a record".to_string(), "records are the items of multiplier lists".to_string())),
+    other         => infer_type(inner) == *other,
+    };
+match (fits, &declared)
+    {
+    (true, Type::List(_)) => Ok(infer_type(inner)),
+    (true, _)             => Ok(declared),
+    (false, _)            => Err((format!("'{}' is not {}", inner, declared.with_article()), format!("it is declared as {}", declared))),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  list_items   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` text           `** a value, like `['Read', 'Write']`   
+/// **`RETURNS:    `** **` Option         `** the items, None if the text is no list of numbers and quoted texts   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn list_items(text: &str) -> Option<Vec<String>>
+{
+// a Rainmeter section reference like [MeasureCPU] is no list, so only literals are accepted as items:
+if !text.trim_start().starts_with('[') { return None; }
+match parse_expression(text).ok()?
+    {
+    Expr::List(items) => items.iter().map(|item| match item { Expr::Literal(value) => Some(value.to_string()), _ => None }).collect(),
+    _                 => None,
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  unify   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` a, b           `** the types of two values of the same list   
+/// **`RETURNS:    `** **` Type           `** the type fitting both: the same type, float for numbers, string for all else   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn unify(a: Type, b: Type) -> Type
+{
+match (a, b)
+    {
+    (a, b) if a == b                               => a,
+    (Type::Any, other) | (other, Type::Any)         => other,
+    (Type::Integer, Type::Float) | (Type::Float, Type::Integer) => Type::Float,
+    _                                              => Type::String,
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  check_types   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` template       `** the parsed template   
+/// **`RETURNS:    `** **` Vec            `** an error for every expression or filter mixing types, empty if all is fine   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Checks all branches of all blocks and macros, without evolving them. Blocks and macros which can't be parsed are   
+/// skipped here, their errors are reported when they are evolved.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn check_types(template: &AriseTemplate) -> Vec<Diagnostic>
+{
+let scope = template_types(template);
+let mut errors: Vec<Diagnostic> = Vec::new();
+
+for section in &template.sections
+    {
+    if let Ok(nodes) = parse_nodes(&section.lines) { check_nodes(&nodes, &scope, &mut errors); }
+    }
+for definition in &template.macros
+    {
+    let mut inner = scope.clone();
+    for parameter in &definition.parameters
+        {
+        if let Some(default) = &parameter.default { check_expression(default, &scope, &definition.line, &mut errors); }
+        inner.names.insert(parameter.name.clone(), Type::Any);      // the arguments may be of any type
+        }
+    check_nodes(&definition.body, &inner, &mut errors);
+    }
+
+debug!("{} type errors found", errors.len());
+errors
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  template_types   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` template       `** the parsed template   
+/// **`RETURNS:    `** **` TypeScope      `** the types of the control assignments, multiplier items, offsets and metadata   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn template_types(template: &AriseTemplate) -> TypeScope
+{
+let mut scope = TypeScope::default();
+for assignment in &template.controls
+    {
+    scope.names.insert(assignment.name.clone(), assignment.value_type.clone());
+    }
+for multiplier in &template.multipliers
+    {
+    // empty values are missing ones, they don't decide the type:
+    let common = |values: Vec<&str>| values.into_iter().filter(|value| !value.trim().is_empty()).map(infer_type).reduce(unify).unwrap_or(Type::String);
+    let mut fields = vec![(multiplier.name.clone(), common(multiplier.items.iter().map(|item| item.primary(&multiplier.name)).collect()))];
+    for item in &multiplier.items
+        {
+        for (field, _) in &item.fields
+            {
+            if field == &multiplier.name || fields.iter().any(|(known, _)| known == field) { continue; }
+            fields.push((field.clone(), common(multiplier.items.iter().filter_map(|item| item.get(field)).collect())));
+            }
+        }
+    bind_record(&mut scope, &multiplier.name, &fields);
+    scope.records.insert(multiplier.name.clone(), fields);
+    }
+for offset in &template.offsets
+    {
+    scope.names.insert(offset.name.clone(), Type::Integer);
+    }
+for meta in [META_INDEX, META_NUMBER, META_COUNT]
+    {
+    scope.names.insert(meta.to_string(), Type::Integer);
+    }
+scope
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  bind_record   
+/// **`TYPE:       `**  local helper function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` scope          `** the scope to extend   
+/// **`            `** **` variable       `** the name the item is known by, like `drive` or a loop variable   
+/// **`            `** **` fields         `** the field types of the items, the item itself first   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn bind_record(scope: &mut TypeScope, variable: &str, fields: &[(String, Type)])
+{
+for (index, (field, field_type)) in fields.iter().enumerate()
+    {
+    if index == 0 { scope.names.insert(variable.to_string(), field_type.clone()); }
+    else          { scope.names.insert(format!("{}.{}", variable, field), field_type.clone()); }
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  check_nodes   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` nodes          `** the parsed lines of a block or a macro   
+/// **`            `** **` scope          `** the types known at this point   
+/// **`            `** **` errors         `** collects the errors found   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn check_nodes(nodes: &[Node], scope: &TypeScope, errors: &mut Vec<Diagnostic>)
+{
+for node in nodes
+    {
+    match node
+        {
+        Node::Line(line)        => check_placeholders(line, scope, errors),
+        Node::If(conditional)   =>
+            {
+            for branch in &conditional.branches
+                {
+                check_expression(&branch.condition, scope, &branch.line, errors);
+                check_nodes(&branch.body, scope, errors);
+                }
+            if let Some(otherwise) = &conditional.otherwise { check_nodes(otherwise, scope, errors); }
+            },
+        Node::For(repeat)       => check_loop(repeat, scope, errors),
+        Node::Call(call)        =>
+            {
+            for argument in &call.arguments { check_expression(argument, scope, &call.line, errors); }
+            },
+        }
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  check_loop   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` repeat         `** a `<:for x in list:>` block   
+/// **`            `** **` scope          `** the types known in front of the loop   
+/// **`            `** **` errors         `** collects the errors found   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn check_loop(repeat: &Loop, scope: &TypeScope, errors: &mut Vec<Diagnostic>)
+{
+let fields = match &repeat.list
+    {
+    Expr::Name(name) if scope.records.contains_key(name) => scope.records[name].clone(),
+    list                                                  => match expression_type(list, scope)
+        {
+        Ok(Type::List(element)) => vec![(repeat.variable.clone(), *element)],
+        Ok(Type::Any)           => vec![(repeat.variable.clone(), Type::Any)],
+        Ok(other)               => { errors.push(line_error(&repeat.line, format!("a loop needs a list, but gets {}", other.with_article()))); return; },
+        Err(why)                => { errors.push(line_error(&repeat.line, why)); return; },
+        },
+    };
+
+let mut inner = scope.clone();
+bind_record(&mut inner, &repeat.variable, &fields);
+for meta in [LOOP_INDEX, LOOP_NUMBER, LOOP_COUNT]
+    {
+    inner.names.insert(meta.to_string(), Type::Integer);
+    }
+inner.names.insert(LOOP_SUFFIX.to_string(), Type::String);
+check_nodes(&repeat.body, &inner, errors);
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  check_placeholders   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` line           `** a line of a block or a macro   
+/// **`            `** **` scope          `** the types known at this point   
+/// **`            `** **` errors         `** collects the errors found   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Expression placeholders like `<:= lighten(ACCENT, 20):>` are checked as every other expression.   
+/// Only the filter `fixed` needs a number, all other filters work on any text.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | renamed from check_filters, checks expression placeholders   
+/// ___________________________________________________________________________________________________________________________
+fn check_placeholders(line: &SourceLine, scope: &TypeScope, errors: &mut Vec<Diagnostic>)
+{
+for placeholder in placeholders(&line.text).unwrap_or_default()
+    {
+    if let Some(expression) = &placeholder.expression
+        {
+        check_expression(expression, scope, line, errors);
+        continue;
+        }
+    let Some(value_type) = scope.names.get(&placeholder.name) else { continue; };
+    for filter in placeholder.filters.iter().filter(|filter| filter.name == FILTER_FIXED && !value_type.is_number())
+        {
+        errors.push(Diagnostic::error(&line.file, line.number, format!("filter '{}' needs a number, but '{}' is {}", filter.name, placeholder.name, value_type.with_article()))
+                               .with_span(&line.text, filter.column, filter.length));
+        }
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  check_expression / line_error   
+/// **`TYPE:       `**  local helper functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Adds an error for an expression mixing types, pointing to the directive of the line.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn check_expression(expression: &Expr, scope: &TypeScope, line: &SourceLine, errors: &mut Vec<Diagnostic>)
+{
+if let Err(why) = expression_type(expression, scope) { errors.push(line_error(line, why)); }
+}
+
+fn line_error(line: &SourceLine, message: String) -> Diagnostic
+{
+let indent = line.text.chars().count() - line.text.trim_start().chars().count();
+Diagnostic::error(&line.file, line.number, message).with_span(&line.text, indent, line.text.trim().chars().count())
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  expression_type   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` expression     `** a parsed expression   
+/// **`            `** **` scope          `** the types known at this point   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the type of the value the expression evaluates to)   
+/// **`            `** **`     or -->     `** - Error(message), for operations on values of the wrong type   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Unknown names are of any type: they may be tested with `defined(...)`, and are reported when evolved.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | color literals and the color functions   
+/// 1.2     | 2026-10-19 | Clunion   | gradients   
+/// ___________________________________________________________________________________________________________________________
+fn expression_type(expression: &Expr, scope: &TypeScope) -> Result<Type, String>
+{
+match expression
+    {
+    Expr::Literal(Value::Number(n)) if n.fract() == 0.0 => Ok(Type::Integer),
+    Expr::Literal(Value::Number(_))                     => Ok(Type::Float),
+    Expr::Literal(Value::Bool(_))                       => Ok(Type::Bool),
+    Expr::Literal(Value::List(_))                       => Ok(Type::List(Box::new(Type::Any))),
+    Expr::Literal(Value::Text(_))                       => Ok(Type::String),
+    Expr::Literal(Value::Color(_))                      => Ok(Type::Color),
+    Expr::Name(name)                                    => Ok(scope.names.get(name).cloned().unwrap_or(Type::Any)),
+    Expr::Call(name, _) if name == FUNCTION_DEFINED     => Ok(Type::Bool),
+    Expr::Call(name, arguments) if COLOR_FUNCTIONS.iter().any(|(function, _)| function == name) =>
+        {
+        let (_, parameters) = COLOR_FUNCTIONS.iter().find(|(function, _)| function == name).unwrap();
+        if arguments.len() != parameters.len() { return Err(format!("'{}' takes {} arguments, got {}", name, parameters.len(), arguments.len())); }
+        for (position, (argument, parameter)) in arguments.iter().zip(parameters.iter()).enumerate()
+            {
+            let argument_type = expression_type(argument, scope)?;
+            let fits = match parameter
+                {
+                ColorArgument::Color  => matches!(argument_type, Type::Color | Type::Any),
+                ColorArgument::Number => argument_type.is_number(),
+                };
+            if !fits
+                {
+                let needed = if *parameter == ColorArgument::Color { "a color" } else { "a number" };
+                return Err(format!("argument {} of '{}' has to be {}, but gets {}", position + 1, name, needed, argument_type.with_article()));
+                }
+            }
+        if name == FUNCTION_TO_HEX || name == FUNCTION_TO_RGB { Ok(Type::String) } else { Ok(Type::Color) }
+        },
+    Expr::Call(name, arguments) if GRADIENT_FUNCTIONS.contains(&name.as_str()) =>
+        {
+        if arguments.len() < 4 { return Err(format!("'{}' takes two or more colors, the index and the count, got {} arguments", name, arguments.len())); }
+        for (position, argument) in arguments.iter().enumerate()
+            {
+            let argument_type = expression_type(argument, scope)?;
+            let is_stop = position < arguments.len() - 2;
+            if is_stop && !matches!(argument_type, Type::Color | Type::Any)
+                {
+                return Err(format!("argument {} of '{}' has to be a color, but gets {}", position + 1, name, argument_type.with_article()));
+                }
+            if !is_stop && !matches!(argument_type, Type::Integer | Type::Any)
+                {
+                return Err(format!("argument {} of '{}' has to be an integer, but gets {}", position + 1, name, argument_type.with_article()));
+                }
+            }
+        Ok(Type::Color)
+        },
+    Expr::Call(_, arguments)                            =>
+        {
+        for argument in arguments { expression_type(argument, scope)?; }
+        Ok(Type::Any)
+        },
+    Expr::List(items)                                   =>
+        {
+        let element = items.iter().map(|item| expression_type(item, scope)).collect::<Result<Vec<Type>, String>>()?.into_iter().reduce(unify);
+        Ok(Type::List(Box::new(element.unwrap_or(Type::Any))))
+        },
+    Expr::Range(first, last)                            =>
+        {
+        for bound in [first, last]
+            {
+            let bound_type = expression_type(bound, scope)?;
+            if !matches!(bound_type, Type::Integer | Type::Any) { return Err(format!("a range needs integers, but gets {}", bound_type.with_article())); }
+            }
+        Ok(Type::List(Box::new(Type::Integer)))
+        },
+    Expr::Unary(UnaryOp::Not, inner)                    => { expression_type(inner, scope)?; Ok(Type::Bool) },
+    Expr::Unary(UnaryOp::Negate, inner)                 =>
+        {
+        let inner_type = expression_type(inner, scope)?;
+        if inner_type.is_number() { Ok(inner_type) } else { Err(format!("can't negate {}", inner_type.with_article())) }
+        },
+    Expr::Binary(op, left, right)                       => binary_type(*op, expression_type(left, scope)?, expression_type(right, scope)?),
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  binary_type   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` op             `** the operator   
+/// **`            `** **` left, right    `** the types of the operands   
+/// **`RETURNS:    `** **` Result -->     `** - OK(the type of the result)   
+/// **`            `** **`     or -->     `** - Error(message)   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Equality may be tested for all values, ordering needs two numbers or two strings, arithmetic two numbers.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn binary_type(op: BinaryOp, left: Type, right: Type) -> Result<Type, String>
+{
+match op
+    {
+    BinaryOp::Or | BinaryOp::And | BinaryOp::Equal | BinaryOp::NotEqual => Ok(Type::Bool),
+    BinaryOp::Less | BinaryOp::LessEqual | BinaryOp::Greater | BinaryOp::GreaterEqual =>
+        {
+        let comparable = (left.is_number() && right.is_number())
+                      || matches!((&left, &right), (Type::String, Type::String) | (Type::String, Type::Any) | (Type::Any, Type::String));
+        if comparable { Ok(Type::Bool) } else { Err(format!("can't compare {} with {}", left.with_article(), right.with_article())) }
+        },
+    _ =>
+        {
+        let verb = match op
+            {
+            BinaryOp::Add       => "add",
+            BinaryOp::Subtract  => "subtract",
+            BinaryOp::Multiply  => "multiply",
+            BinaryOp::Divide    => "divide",
+            _                   => "take the remainder of",
+            };
+        if !left.is_number() || !right.is_number() { return Err(format!("can't {} {} and {}", verb, left.with_article(), right.with_article())); }
+        match (op, left, right)
+            {
+            (BinaryOp::Divide, _, _)             => Ok(Type::Float),
+            (_, Type::Integer, Type::Integer)    => Ok(Type::Integer),
+            (_, Type::Any, _) | (_, _, Type::Any) => Ok(Type::Any),
+            _                                    => Ok(Type::Float),
+            }
+        },
+    }
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for types   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+  use std::path::Path;
+  use crate::modules::template::parse_template;
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** types_are_inferred_and_declared()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the types inferred from values, and declared types with values not fitting them   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn types_are_inferred_and_declared()
+  {
+  assert_eq!(infer_type("+20"), Type::Integer);
+  assert_eq!(infer_type("-1.5"), Type::Float);
+  assert_eq!(infer_type("64,64,64,128"), Type::Color);
+  assert_eq!(infer_type("256,0,0"), Type::String);
+  assert_eq!(infer_type("202020C8"), Type::Color);
+  assert_eq!(infer_type("['Read', 'Write']"), Type::List(Box::new(Type::String)));
+  assert_eq!(infer_type("[MeasureCPU]"), Type::String);
+  assert_eq!(infer_type("C:\\Program Files"), Type::String);
+
+  assert_eq!(assigned_type(None, "\"255,0,0\""), Ok(Type::String));
+  assert_eq!(assigned_type(Some("float"), "20"), Ok(Type::Float));
+  assert_eq!(assigned_type(Some("list"), "[1, 2.5]"), Ok(Type::List(Box::new(Type::Float))));
+  assert_eq!(assigned_type(Some("color"), "255,0"), Err(("'255,0' is not a color".to_string(), "it is declared as color".to_string())));
+  assert_eq!(assigned_type(Some("colour"), "255,0,0"), Err(("unknown type 'colour'".to_string(), "did you mean 'color'?".to_string())));
+
+  let template = parse_template(Path::new("Test.arise"), "WIDTH: integer := 20\nMODES := ['Read', 'Write']\n").unwrap();
+  assert_eq!(template.controls[0].name, "WIDTH");
+  assert_eq!(template.controls[1].value_type, Type::List(Box::new(Type::String)));
+  let error = parse_template(Path::new("Test.arise"), "\nWIDTH: integer := wide\n").unwrap_err();
+  assert!(error.to_string().contains("'wide' is not an integer\n  --> Test.arise:2"));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** type_errors_are_reported()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the errors for mixed types in conditions, loops, macro calls and filters, in all branches   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn type_errors_are_reported()
+  {
+  let text = "LABEL := Disk\nACCENT := 255,0,0\nMODES := ['Read', 'Write']\n\
+              <multiplier list begin>\n<:drive:> := @data(\"drives.csv\")\n<multiplier list end>\n\
+              <meters begin>\n<:if drive.removable == 1 and #index + 1 < 3:>\n<:for m in MODES:>\nX=<:m:>\n<:end:>\n\
+              <:elif LABEL + 1 > 2:>\n<:else:>\n  <:for c in ACCENT:>\n  <:end:>\n<:end:>\n<:call bar(ACCENT > 5):>\n\
+              W=<:ACCENT|fixed(1):> <:#index|fixed(1):>\n<meters end>\n\
+              <:macro bar(x):>\n<:if x > 1:>\n<:end:>\n<:endmacro:>\n";
+  let directory = std::env::temp_dir().join("arise_types_test");
+  std::fs::create_dir_all(&directory).unwrap();
+  std::fs::write(directory.join("drives.csv"), "drive,removable\nC,0\nD,1\n").unwrap();
+  let template = parse_template(&directory.join("Test.arise"), text).unwrap();
+  let messages: Vec<String> = check_types(&template).iter().map(|error| format!("{}:{}", error.line, error.message)).collect();
+  assert_eq!(messages, vec!["12:can't add a string and an integer",
+                            "14:a loop needs a list, but gets a color",
+                            "17:can't compare a color with an integer",
+                            "18:filter 'fixed' needs a number, but 'ACCENT' is a color"]);
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** color_functions_are_typed()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the types of color literals and color functions in expression placeholders   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn color_functions_are_typed()
+  {
+  let text = "LABEL := Disk\nACCENT := 00A0FF\n<meters begin>\n\
+              A=<:= lighten(ACCENT, 20):> <:= to_hex(mix(ACCENT, #FF0000, 0.5)):>\n\
+              B=<:= lighten(LABEL, 5):>\nC=<:= alpha(ACCENT):>\nD=<:= to_rgb(ACCENT) + 1:>\n\
+              E=<:= gradient(ACCENT, #FF0000, 1, 3):> <:= gradient_hsl(ACCENT, 2, 1.5):> <:= gradient(ACCENT, 5, 0.5, 2):>\n<meters end>\n";
+  let template = parse_template(Path::new("Test.arise"), text).unwrap();
+  let messages: Vec<String> = check_types(&template).iter().map(|error| format!("{}:{}", error.line, error.message)).collect();
+  assert_eq!(messages, vec!["5:argument 1 of 'lighten' has to be a color, but gets a string",
+                            "6:'alpha' takes 2 arguments, got 1",
+                            "7:can't add a string and an integer",
+                            "8:'gradient_hsl' takes two or more colors, the index and the count, got 3 arguments",
+                            "8:argument 2 of 'gradient' has to be a color, but gets an integer"]);
+  }
+
+} // End of: mod test
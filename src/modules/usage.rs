@@ -0,0 +1,276 @@
+#![deny(clippy::all)]
+#![forbid(unsafe_code)]
+#![allow(clippy::suspicious_else_formatting)]
+#![allow(clippy::collapsible_if)]
+
+//! ___________________________________________________________________________________________________________________________
+//! **`PROJECT:    `** ARISE - A RaInmeter Skin Evolver   
+//! **`HOME:       `** [arise on GitHub](https://github.com/clunion/arise)   
+//! **`SYNOPSIS:   `** A Rainmeter (tm) Skin Evolver, a parameterized generator for rainmeter ini-files   
+//! ___________________________________________________________________________________________________________________________
+//! **`FILE:       `** usage.rs 🦀   
+//! **`DESCRIPTION:`** finds the control assignments, multiplier lists and offset variables of a template, which are   
+//! defined but never used   
+//! ___________________________________________________________________________________________________________________________
+//! **`LICENSE:    `**   
+//! Copyright 2020 by Christian Lunau (clunion)   
+//! MIT-License, see LICENSE.md file   
+//! ___________________________________________________________________________________________________________________________
+//! VERSION: | DATE:      | AUTHOR:   | CHANGES:   
+//! :---     | :---       | :---:     | :---   
+//! 0.1      | 2026-10-19 | Clunion   | creation   
+//! 0.2      | 2026-10-19 | Clunion   | only the macros which are called use names   
+//! ___________________________________________________________________________________________________________________________
+//!# Examples   
+//!```text
+//! warning: the offset variable 'vertical offset 2' is never used   
+//!   --> input/StorageMon.arise:61   
+//!    = help: remove it, or use it like <:vertical offset 2:>   
+//!```
+//! A name is used, if a placeholder, a condition, a loop or a macro call of any block or called macro refers to it.   
+//! Multiplier lists are used by the blocks they multiply as well. Control assignments named ARISE_... and   
+//! RAINMETER_... are read by arise itself and are never reported.   
+//! ___________________________________________________________________________________________________________________________
+//!   
+
+//___ DECLARATIONS OF SUBMODULES: _____________________________________________________________________________________________
+//___ none ___
+
+//___ PATHS TO MODULES TO USE: ________________________________________________________________________________________________
+use std::collections::BTreeSet;
+
+#[allow(unused_imports)]
+use log::{trace, debug, info, warn, error};
+
+use crate::modules::template::{AriseTemplate, KEY_NAME_BEGIN, KEY_NAME_END, BLOCK_BEGIN, BLOCK_OPTION_REPEAT};
+use crate::modules::expansion::{Node, parse_nodes, placeholders};
+use crate::modules::expression::Expr;
+use crate::modules::diagnostics::Diagnostic;
+
+//___ CONSTANTS: ______________________________________________________________________________________________________________
+const RESERVED_CONTROL_PREFIXES : [&str; 2] = ["ARISE_", "RAINMETER_"];
+
+//___ TYPES: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ ENUMS: __________________________________________________________________________________________________________________
+//___ none ___
+
+//___ MACROS: _________________________________________________________________________________________________________________
+//___ none ___
+
+//___ STRUCTS: ________________________________________________________________________________________________________________
+//___ none ___
+
+//___ METHODS: ________________________________________________________________________________________________________________
+//___ none ___
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  unused_definitions   
+/// **`TYPE:       `**  common function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` template       `** the parsed template   
+/// **`RETURNS:    `** **` Vec            `** a warning for every definition which is never used, in the order of the template   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Blocks and macros which can't be parsed are skipped here, their errors are reported when they are evolved.   
+/// A macro nobody calls is never evolved, so the names in its body and in its default arguments are not used.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | only called macros, and the macros called by them   
+/// ___________________________________________________________________________________________________________________________
+pub(crate) fn unused_definitions(template: &AriseTemplate) -> Vec<Diagnostic>
+{
+let mut used:   BTreeSet<String> = BTreeSet::new();
+let mut called: BTreeSet<String> = BTreeSet::new();
+for section in &template.sections
+    {
+    if let Ok(nodes) = parse_nodes(&section.lines) { used_in_nodes(&nodes, &mut used, &mut called); }
+    if let Some(list) = &section.repeat { used.insert(list.clone()); }
+    }
+
+// a called macro may call further macros, so this goes on until no more are found:
+let mut evolved: BTreeSet<String> = BTreeSet::new();
+while let Some(definition) = template.macros.iter().find(|definition| called.contains(&definition.name) && !evolved.contains(&definition.name))
+    {
+    evolved.insert(definition.name.clone());
+    used_in_nodes(&definition.body, &mut used, &mut called);
+    for default in definition.parameters.iter().filter_map(|parameter| parameter.default.as_ref()) { used_in_expression(default, &mut used); }
+    }
+for assignment in &template.controls
+    {
+    used_in_text(&assignment.value, &mut used);
+    }
+
+let mut warnings: Vec<Diagnostic> = Vec::new();
+for assignment in &template.controls
+    {
+    if RESERVED_CONTROL_PREFIXES.iter().any(|prefix| assignment.name.starts_with(prefix)) || used.contains(&assignment.name) { continue; }
+    // a control assignment may be repeated, the last one counts, so only that one is reported:
+    if template.controls.iter().rev().find(|last| last.name == assignment.name) != Some(assignment) { continue; }
+    warnings.push(Diagnostic::warning(&assignment.line.file, assignment.line.number, format!("the control variable '{}' is never used", assignment.name))
+                            .with_help(format!("remove it, or use it like {}{}{}", KEY_NAME_BEGIN, assignment.name, KEY_NAME_END)));
+    }
+for (index, multiplier) in template.multipliers.iter().enumerate()
+    {
+    if used.contains(&multiplier.name) { continue; }
+    let help = if index == 0 { "there are no measures and meters to multiply, remove it".to_string() }
+               else          { format!("only the first list multiplies measures and meters, use '{} name {}={}>' for the others", BLOCK_BEGIN, BLOCK_OPTION_REPEAT, multiplier.name) };
+    warnings.push(Diagnostic::warning(&multiplier.line.file, multiplier.line.number, format!("the multiplier list '{}' is never used", multiplier.name)).with_help(help));
+    }
+for offset in &template.offsets
+    {
+    if used.contains(&offset.name) { continue; }
+    warnings.push(Diagnostic::warning(&offset.line.file, offset.line.number, format!("the offset variable '{}' is never used", offset.name))
+                            .with_help(format!("remove it, or use it like {}{}{}", KEY_NAME_BEGIN, offset.name, KEY_NAME_END)));
+    }
+
+warnings.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+debug!("{} definitions are never used", warnings.len());
+warnings
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  used_in_nodes   
+/// **`TYPE:       `**  local function   
+/// ___________________________________________________________________________________________________________________________
+/// **`PARAMETER:  `** **` nodes          `** the parsed lines of a block or a macro   
+/// **`            `** **` used           `** collects the names referred to   
+/// **`            `** **` called         `** collects the names of the macros called   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// 1.1     | 2026-10-19 | Clunion   | the macros called   
+/// ___________________________________________________________________________________________________________________________
+fn used_in_nodes(nodes: &[Node], used: &mut BTreeSet<String>, called: &mut BTreeSet<String>)
+{
+for node in nodes
+    {
+    match node
+        {
+        Node::Line(line)        => used_in_text(&line.text, used),
+        Node::If(conditional)   =>
+            {
+            for branch in &conditional.branches
+                {
+                used_in_expression(&branch.condition, used);
+                used_in_nodes(&branch.body, used, called);
+                }
+            if let Some(otherwise) = &conditional.otherwise { used_in_nodes(otherwise, used, called); }
+            },
+        Node::For(repeat)       =>
+            {
+            used_in_expression(&repeat.list, used);
+            used_in_nodes(&repeat.body, used, called);
+            },
+        Node::Call(call)        =>
+            {
+            called.insert(call.name.clone());
+            for argument in &call.arguments { used_in_expression(argument, used); }
+            },
+        }
+    }
+}
+
+/// ___________________________________________________________________________________________________________________________
+/// **`FUNCTION:   `**  used_in_text / used_in_expression   
+/// **`TYPE:       `**  local functions   
+/// ___________________________________________________________________________________________________________________________
+/// **`DESCRIPTION:`**   
+/// Collects the names referred to by the placeholders of a text or by an expression. For fields like `drive.label`   
+/// the list name `drive` is collected as well.   
+/// ___________________________________________________________________________________________________________________________
+/// VERSION:| DATE:      | AUTHOR:   | CHANGES:   
+/// :---    | :---       | :---:     | :---   
+/// 1.0     | 2026-10-19 | Clunion   | initial version   
+/// ___________________________________________________________________________________________________________________________
+fn used_in_text(text: &str, used: &mut BTreeSet<String>)
+{
+for placeholder in placeholders(text).unwrap_or_default()
+    {
+    match &placeholder.expression
+        {
+        Some(expression) => used_in_expression(expression, used),
+        None             => use_name(&placeholder.name, used),
+        }
+    }
+}
+
+fn used_in_expression(expression: &Expr, used: &mut BTreeSet<String>)
+{
+match expression
+    {
+    Expr::Literal(_)              => {},
+    Expr::Name(name)              => use_name(name, used),
+    Expr::Call(_, arguments)      => for argument in arguments { used_in_expression(argument, used); },
+    Expr::List(items)             => for item in items { used_in_expression(item, used); },
+    Expr::Range(first, last)      => { used_in_expression(first, used); used_in_expression(last, used); },
+    Expr::Unary(_, inner)         => used_in_expression(inner, used),
+    Expr::Binary(_, left, right)  => { used_in_expression(left, used); used_in_expression(right, used); },
+    }
+}
+
+fn use_name(name: &str, used: &mut BTreeSet<String>)
+{
+if let Some((list, _)) = name.split_once('.') { used.insert(list.to_string()); }
+used.insert(name.to_string());
+}
+
+
+
+/// ___________________________________________________________________________________________________________________________
+/// **`TESTMODULE: `** for usage   
+/// **`TYPE:       `** unit tests   
+/// ___________________________________________________________________________________________________________________________
+#[cfg(test)]
+mod tests
+{
+  use super::*;            // importing names from outer (for mod tests) scope
+  use std::path::Path;
+  use crate::modules::template::parse_template;
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** unused_definitions_are_reported()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks the warnings for unused control variables, multiplier lists and offset variables   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn unused_definitions_are_reported()
+  {
+  let text = "ARISE_ORDER := interleaved\nLABEL := Disk\nCOLOR := 255\nSIZE := 10\n\
+              <multiplier list begin>\n<:drive:> := 'CD'\n<:core:> := '12'\n<:fan:> := '12'\n<multiplier list end>\n\
+              <offset variables begin>\n<:x offset:> := +10\n<:y offset:> := +20\n<offset variables end>\n\
+              <measures begin>\n[M_<:drive:>]\nX=<:x offset|pad(3):>\n<:if defined(LABEL):>\n<:call label(COLOR):>\n<:end:>\n<measures end>\n\
+              <block cores repeat=core>\n<block end>\n\
+              <:macro label(color):>\nText=<:LABEL:>\n<:endmacro:>\n";
+  let template = parse_template(Path::new("Test.arise"), text).unwrap();
+  let messages: Vec<String> = unused_definitions(&template).iter().map(|warning| format!("{}:{}", warning.line, warning.message)).collect();
+  assert_eq!(messages, vec!["4:the control variable 'SIZE' is never used",
+                            "8:the multiplier list 'fan' is never used",
+                            "12:the offset variable 'y offset' is never used"]);
+  assert!(unused_definitions(&template)[1].to_string().contains("use '<block name repeat=fan>' for the others"));
+  }
+
+  /// ___________________________________________________________________________________________________________________________
+  /// **`FUNCTION:   `** only_called_macros_use_names()   
+  /// **`TYPE:       `** unit test   
+  /// **`TESTS:      `** checks that names used only by a macro nobody calls are reported, also through nested calls   
+  /// ___________________________________________________________________________________________________________________________
+  #[test]
+  fn only_called_macros_use_names()
+  {
+  let text = "DEEP := 1\nNOTE := 2\nSIZE := 3\n<measures begin>\n[M]\n<:call outer():>\n<measures end>\n\
+              <:macro outer():>\n<:call inner():>\n<:endmacro:>\n<:macro inner():>\nText=<:DEEP:>\n<:endmacro:>\n\
+              <:macro unused(size = SIZE):>\nText=<:NOTE:>\n<:endmacro:>\n";
+  let template = parse_template(Path::new("Test.arise"), text).unwrap();
+  let messages: Vec<String> = unused_definitions(&template).iter().map(|warning| format!("{}:{}", warning.line, warning.message)).collect();
+  assert_eq!(messages, vec!["2:the control variable 'NOTE' is never used",
+                            "3:the control variable 'SIZE' is never used"]);
+  }
+
+} // End of: mod test